@@ -0,0 +1,60 @@
+//! Scanning a measurements file for records, with and without SWAR tricks.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use rowlab::WeatherStations;
+use rowlab::scan::{Records, find_byte, find_byte_scalar};
+
+/// The number of measurements in the benchmarked buffer.
+const ROWS: usize = 1_000_000;
+
+/// Splits a buffer into `(station, temperature)` records the naive way, with `split`.
+fn split_records(bytes: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    bytes
+        .split(|&b| b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut columns = line.splitn(2, |&b| b == b';');
+            (columns.next().unwrap(), columns.next().unwrap())
+        })
+}
+
+pub fn scan(c: &mut Criterion) {
+    // Build the kind of buffer we would read from a measurements file on disk.
+    let stations = WeatherStations::new();
+    let bytes: Vec<u8> = stations
+        .measurements()
+        .take(ROWS)
+        .flat_map(|(station, measurement)| format!("{station};{measurement:.1}\n").into_bytes())
+        .collect();
+
+    c.bench_function("find_byte", |b| {
+        b.iter(|| black_box(find_byte(black_box(&bytes), b'\0')))
+    });
+
+    c.bench_function("find_byte_scalar", |b| {
+        b.iter(|| black_box(find_byte_scalar(black_box(&bytes), b'\0')))
+    });
+
+    c.bench_function("records", |b| {
+        b.iter(|| {
+            Records::new(black_box(&bytes)).fold(0i64, |sum, (_, tenths)| sum + tenths as i64)
+        })
+    });
+
+    c.bench_function("split", |b| {
+        b.iter(|| {
+            split_records(black_box(&bytes)).fold(0.0, |sum, (_, value)| {
+                let value: f64 = std::str::from_utf8(value).unwrap().parse().unwrap();
+                sum + value
+            })
+        })
+    });
+}
+
+criterion_main!(benches);
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+                .sample_size(10);
+    targets = scan
+}
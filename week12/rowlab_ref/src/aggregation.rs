@@ -1,6 +1,14 @@
 use itertools::Itertools;
+use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fmt::{Display, Write};
+use std::hash::Hash;
+use std::io::{self, Read};
+
+/// The most stations that [`AggregationResults::read_snapshot`] reserves space for up front. The
+/// challenge has at most 10,000 distinct stations, and the station count comes from an untrusted
+/// file, so we don't want a corrupt count to make us allocate gigabytes before reading anything.
+const MAX_PREALLOCATED_STATIONS: usize = 10_000;
 
 /// Aggregate statistics for a specific [`WeatherStation`].
 #[derive(Debug, Clone, Copy)]
@@ -64,6 +72,38 @@ impl StationAggregation {
     pub fn mean(&self) -> f64 {
         self.mean
     }
+
+    /// Writes the raw fields of the aggregation to a binary snapshot.
+    ///
+    /// The mean is not written since it can be recomputed from the sum and count.
+    fn write_snapshot<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        for field in [
+            self.min,
+            self.max,
+            self.sum_measurements,
+            self.num_measurements,
+        ] {
+            writer.write_all(&field.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads an aggregation previously written with [`StationAggregation::write_snapshot`].
+    fn read_snapshot<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let min = read_f64(reader)?;
+        let max = read_f64(reader)?;
+        let sum_measurements = read_f64(reader)?;
+        let num_measurements = read_f64(reader)?;
+
+        Ok(Self {
+            min,
+            max,
+            mean: sum_measurements / num_measurements,
+            sum_measurements,
+            num_measurements,
+        })
+    }
 }
 
 impl Display for StationAggregation {
@@ -74,16 +114,18 @@ impl Display for StationAggregation {
 
 /// The aggregation results for the billion row challenge.
 ///
+/// The results are grouped by a key of type `K`, which defaults to the weather station name.
+///
 /// TODO(student): This is purposefully not an ideal structure! You are allowed to change what
 /// types this struct contains. Think about what this structure should represent, and where the data
 /// might best be located.
 #[derive(Debug)]
-pub struct AggregationResults {
-    /// A map from weather station identifier to its aggregate metrics.
-    results: HashMap<String, StationAggregation>,
+pub struct AggregationResults<K = String> {
+    /// A map from group key (by default, the weather station identifier) to its aggregate metrics.
+    results: HashMap<K, StationAggregation>,
 }
 
-impl AggregationResults {
+impl<K> AggregationResults<K> {
     /// Creates an empty `AggregationResult`.
     pub fn new() -> Self {
         Self {
@@ -91,16 +133,27 @@ impl AggregationResults {
         }
     }
 
-    // Updates the metrics for the given station with a measurement.
-    pub fn insert_measurement(&mut self, station: &str, measurement: f64) {
+    /// Iterates over every key and its aggregate metrics, in an arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &StationAggregation)> {
+        self.results.iter()
+    }
+}
+
+impl<K: Hash + Eq> AggregationResults<K> {
+    // Updates the metrics for the given key with a measurement.
+    pub fn insert_measurement<Q>(&mut self, key: &Q, measurement: f64)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
         // We don't use the `entry` API in the `Some` case since it would require us to always turn
-        // `station` into an owned `String`, since `.entry()` requires an owned type.
-        // So the problem here is that the map requires a `String` key... could you change that?
-        match self.results.get_mut(station) {
+        // `key` into an owned `K`, since `.entry()` requires an owned type.
+        // So the problem here is that the map requires an owned key... could you change that?
+        match self.results.get_mut(key) {
             Some(val) => val.add_measurement(measurement),
             None => self
                 .results
-                .entry(station.to_string())
+                .entry(key.to_owned())
                 .or_default()
                 .add_measurement(measurement),
         }
@@ -108,52 +161,146 @@ impl AggregationResults {
 
     /// Merge another `AggregationResult` into the current aggregations.
     pub fn merge_aggregation(&mut self, other: Self) {
-        for (station, aggregation) in other.results {
-            match self.results.get_mut(&station) {
+        for (key, aggregation) in other.results {
+            match self.results.get_mut(&key) {
                 Some(existing) => existing.merge(&aggregation),
                 None => {
-                    self.results.insert(station, aggregation);
+                    self.results.insert(key, aggregation);
                 }
             }
         }
     }
 
-    /// Retrieve the stats of a specific station, if it exists. Used for testing purposes.
-    pub fn get_metrics(&self, station: &str) -> Option<StationAggregation> {
-        self.results.get(station).copied()
+    /// Converts every key of the results with `f`, merging the aggregations of any keys that end up
+    /// equal. Stops at (and returns) the first error.
+    pub fn try_map_keys<K2, E, F>(self, mut f: F) -> Result<AggregationResults<K2>, E>
+    where
+        K2: Hash + Eq,
+        F: FnMut(K) -> Result<K2, E>,
+    {
+        let mut mapped = AggregationResults::new();
+
+        for (key, aggregation) in self.results {
+            mapped
+                .results
+                .entry(f(key)?)
+                .or_insert_with(StationAggregation::new)
+                .merge(&aggregation);
+        }
+
+        Ok(mapped)
+    }
+
+    /// Retrieve the stats of a specific key, if it exists. Used for testing purposes.
+    pub fn get_metrics<Q>(&self, key: &Q) -> Option<StationAggregation>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.results.get(key).copied()
     }
 }
 
-impl Display for AggregationResults {
+impl AggregationResults {
+    /// Serializes the results into a compact binary snapshot.
+    ///
+    /// The snapshot is the number of stations, followed by each station's length-prefixed name and
+    /// raw aggregation fields. All integers and floats are little-endian.
+    pub fn write_snapshot<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&(self.results.len() as u64).to_le_bytes())?;
+
+        for (station, aggregation) in &self.results {
+            writer.write_all(&(station.len() as u32).to_le_bytes())?;
+            writer.write_all(station.as_bytes())?;
+            aggregation.write_snapshot(writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Deserializes results previously written with [`AggregationResults::write_snapshot`].
+    pub fn read_snapshot<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let num_stations = read_u64(reader)? as usize;
+        let mut results = HashMap::with_capacity(num_stations.min(MAX_PREALLOCATED_STATIONS));
+
+        for _ in 0..num_stations {
+            let mut name_len = [0; 4];
+            reader.read_exact(&mut name_len)?;
+            let name_len = u32::from_le_bytes(name_len) as usize;
+
+            // Only read as many bytes as are actually there, rather than trusting the length
+            // enough to allocate a buffer of that size first.
+            let mut name = Vec::new();
+            reader
+                .by_ref()
+                .take(name_len as u64)
+                .read_to_end(&mut name)?;
+            if name.len() != name_len {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "snapshot ended in the middle of a station name",
+                ));
+            }
+            let station = String::from_utf8(name)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            results.insert(station, StationAggregation::read_snapshot(reader)?);
+        }
+
+        Ok(Self { results })
+    }
+}
+
+/// Reads a little-endian `u64` from a binary snapshot.
+pub(crate) fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads a little-endian `f64` from a binary snapshot.
+fn read_f64<R: Read>(reader: &mut R) -> io::Result<f64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(f64::from_le_bytes(bytes))
+}
+
+impl<K: Display + Ord> Display for AggregationResults<K> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Sort the results by weather station ID and join into the output string format.
-        let sorted_results: Vec<_> = self
-            .results
-            .iter()
-            .sorted_by(|a, b| Ord::cmp(&a.0, &b.0))
-            .collect();
-
-        f.write_char('{')?;
-
-        // Append each weather station's metrics to the output string.
-        for (station, aggregation) in sorted_results.iter().take(sorted_results.len() - 1) {
-            f.write_str(station)?;
-            f.write_char('=')?;
-            // Note that implementing `Display` on `StationAggregation` means that you can call
-            // `to_string` and it will do a similar thing as `Display::fmt`.
-            f.write_str(&aggregation.to_string())?;
+        // Sort the results by key and join into the output string format.
+        let sorted_results = self.results.iter().sorted_by(|a, b| Ord::cmp(&a.0, &b.0));
+
+        write_report(f, sorted_results)
+    }
+}
+
+/// Writes aggregations in the output format of the challenge, `{key=min/mean/max, ...}`, in the
+/// order that they are given.
+pub(crate) fn write_report<'a, K, I>(
+    f: &mut std::fmt::Formatter<'_>,
+    entries: I,
+) -> std::fmt::Result
+where
+    K: Display + 'a,
+    I: IntoIterator<Item = (&'a K, &'a StationAggregation)>,
+{
+    f.write_char('{')?;
+
+    // Append each weather station's metrics to the output string, separating them with commas.
+    for (i, (station, aggregation)) in entries.into_iter().enumerate() {
+        if i > 0 {
             f.write_char(',')?;
             f.write_char(' ')?;
         }
 
-        let (last_station, last_aggregation) =
-            sorted_results.last().expect("somehow empty results");
-        f.write_str(last_station)?;
+        write!(f, "{station}")?;
         f.write_char('=')?;
-        f.write_str(&last_aggregation.to_string())?;
-
-        f.write_char('}')
+        // Note that implementing `Display` on `StationAggregation` means that you can call
+        // `to_string` and it will do a similar thing as `Display::fmt`.
+        f.write_str(&aggregation.to_string())?;
     }
+
+    f.write_char('}')
 }
 
 impl Default for StationAggregation {
@@ -162,7 +309,7 @@ impl Default for StationAggregation {
     }
 }
 
-impl Default for AggregationResults {
+impl<K> Default for AggregationResults<K> {
     fn default() -> Self {
         Self::new()
     }
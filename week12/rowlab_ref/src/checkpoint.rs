@@ -0,0 +1,114 @@
+use crate::aggregation::{AggregationResults, read_u64};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// The bytes every checkpoint file starts with, used to reject files that are not checkpoints.
+const CHECKPOINT_MAGIC: &[u8; 8] = b"ROWLAB01";
+
+/// A snapshot of a partially completed aggregation.
+///
+/// A checkpoint records how many rows of the input have already been consumed, alongside the
+/// aggregation results for exactly those rows. Resuming from a checkpoint means skipping that many
+/// rows of the input and merging everything after them into the restored results.
+#[derive(Debug)]
+pub struct Checkpoint {
+    /// The number of rows that have been aggregated into `results`.
+    rows: usize,
+    /// The aggregation results of the first `rows` rows.
+    results: AggregationResults,
+}
+
+impl Checkpoint {
+    /// Creates an empty checkpoint that has not consumed any rows.
+    pub fn new() -> Self {
+        Self {
+            rows: 0,
+            results: AggregationResults::new(),
+        }
+    }
+
+    /// The number of rows that have been aggregated into this checkpoint.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The aggregation results recorded by this checkpoint.
+    pub fn results(&self) -> &AggregationResults {
+        &self.results
+    }
+
+    /// Consumes the checkpoint, returning the aggregation results.
+    pub fn into_results(self) -> AggregationResults {
+        self.results
+    }
+
+    /// Records that `rows` more rows have been consumed, merging in their aggregation results.
+    pub fn advance(&mut self, rows: usize, results: AggregationResults) {
+        self.rows += rows;
+        self.results.merge_aggregation(results);
+    }
+
+    /// Serializes the checkpoint into a compact binary snapshot.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(CHECKPOINT_MAGIC)?;
+        writer.write_all(&(self.rows as u64).to_le_bytes())?;
+        self.results.write_snapshot(writer)
+    }
+
+    /// Deserializes a checkpoint previously written with [`Checkpoint::write_to`].
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != CHECKPOINT_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a rowlab checkpoint",
+            ));
+        }
+
+        let rows = read_u64(reader)? as usize;
+        let results = AggregationResults::read_snapshot(reader)?;
+
+        Ok(Self { rows, results })
+    }
+
+    /// Writes the checkpoint to the file at `path`.
+    ///
+    /// The checkpoint is first written to a temporary file and then renamed over `path`, so a crash
+    /// in the middle of saving never leaves behind a corrupted checkpoint. The temporary file is
+    /// named after the whole file name of `path` plus `.tmp`, so it never collides with `path`
+    /// itself or with a file that only shares its stem.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp_name = path
+            .file_name()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "checkpoint path has no file name",
+                )
+            })?
+            .to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        self.write_to(&mut writer)?;
+        writer.into_inner()?.sync_all()?;
+
+        fs::rename(tmp_path, path)
+    }
+
+    /// Loads a checkpoint from the file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Self::read_from(&mut reader)
+    }
+}
+
+impl Default for Checkpoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,404 @@
+//! Distributing the aggregation of a file across several worker processes.
+//!
+//! The coordinator splits a file into byte ranges and hands them out to workers over TCP. Every
+//! message in either direction is a single frame: a little-endian `u32` length followed by that
+//! many bytes of payload.
+//!
+//! A job frame (coordinator to worker) contains the start and end of the byte range as `u64`s, the
+//! delimiter as a `u32`, the key and value columns as `u64`s, and then the UTF-8 path of the file.
+//!
+//! A result frame (worker to coordinator) starts with a status byte. On success the status is
+//! `STATUS_OK` followed by an [`AggregationResults`] snapshot, and on failure it is
+//! `STATUS_ERR` followed by a UTF-8 error message.
+
+use crate::aggregation::read_u64;
+use crate::{AggregationResults, FileFormat, aggregate_file_range, split_file};
+use std::collections::VecDeque;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// The status byte of a result frame for a job that succeeded.
+const STATUS_OK: u8 = 0;
+
+/// The status byte of a result frame for a job that failed.
+const STATUS_ERR: u8 = 1;
+
+/// How many times in a row a worker may fail before the coordinator stops using it.
+const MAX_CONSECUTIVE_FAILURES: usize = 3;
+
+/// How long the coordinator waits when connecting to a worker.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long the coordinator waits for a worker to accept a job or send back its results before
+/// treating the worker as failed, unless [`coordinate_with_timeout`] says otherwise.
+pub const RESPONSE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long the coordinator waits before retrying after a worker fails.
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// The largest frame either side will send or accept. Result snapshots are by far the largest
+/// frames, and even with many thousands of keys they stay well below this.
+const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+/// A request for a worker to aggregate one byte range of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    /// The file to aggregate. Every worker must be able to read it at this path.
+    pub path: PathBuf,
+    /// The byte range of the file to aggregate.
+    pub range: Range<u64>,
+    /// How to read the lines of the file.
+    pub format: FileFormat,
+}
+
+impl Job {
+    /// Encodes the job into the payload of a job frame.
+    fn encode(&self) -> io::Result<Vec<u8>> {
+        let path = self.path.to_str().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "file path is not valid UTF-8")
+        })?;
+
+        let mut payload = Vec::with_capacity(36 + path.len());
+        payload.extend_from_slice(&self.range.start.to_le_bytes());
+        payload.extend_from_slice(&self.range.end.to_le_bytes());
+        payload.extend_from_slice(&(self.format.delimiter as u32).to_le_bytes());
+        payload.extend_from_slice(&(self.format.key_column as u64).to_le_bytes());
+        payload.extend_from_slice(&(self.format.value_column as u64).to_le_bytes());
+        payload.extend_from_slice(path.as_bytes());
+
+        Ok(payload)
+    }
+
+    /// Decodes a job from the payload of a job frame.
+    fn decode(mut payload: &[u8]) -> io::Result<Self> {
+        let reader = &mut payload;
+
+        let start = read_u64(reader)?;
+        let end = read_u64(reader)?;
+
+        let mut delimiter = [0; 4];
+        reader.read_exact(&mut delimiter)?;
+        let delimiter = char::from_u32(u32::from_le_bytes(delimiter))
+            .ok_or_else(|| invalid_data("delimiter is not a valid character"))?;
+
+        let key_column = read_u64(reader)? as usize;
+        let value_column = read_u64(reader)? as usize;
+
+        let path = std::str::from_utf8(reader).map_err(invalid_data)?;
+
+        Ok(Self {
+            path: PathBuf::from(path),
+            range: start..end,
+            format: FileFormat {
+                delimiter,
+                key_column,
+                value_column,
+            },
+        })
+    }
+}
+
+/// Writes a single length-prefixed frame.
+fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> io::Result<()> {
+    if payload.len() > MAX_FRAME_LEN {
+        return Err(invalid_data(format!(
+            "frame of {} bytes is larger than the maximum of {MAX_FRAME_LEN}",
+            payload.len()
+        )));
+    }
+
+    let len = payload.len() as u32;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+/// Reads a single length-prefixed frame. Frames longer than [`MAX_FRAME_LEN`] are rejected with
+/// an [`io::ErrorKind::InvalidData`] error.
+fn read_frame<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+
+    // The length comes straight from the other side of the connection, so don't allocate a buffer
+    // for it before checking that it is reasonable.
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(invalid_data(format!(
+            "frame of {len} bytes is larger than the maximum of {MAX_FRAME_LEN}"
+        )));
+    }
+
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload)?;
+
+    Ok(payload)
+}
+
+/// Creates an [`io::ErrorKind::InvalidData`] error.
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Runs a worker that accepts connections on `listener` forever, aggregating every job it is sent.
+///
+/// Each connection is handled on its own thread, and may carry any number of jobs one after the
+/// other.
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+
+        thread::spawn(move || {
+            // A connection that breaks is the coordinator's problem, so there is nothing to do here
+            // except stop serving it.
+            let _ = handle_connection(stream);
+        });
+    }
+
+    Ok(())
+}
+
+/// Serves jobs from a single coordinator connection until it is closed.
+fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = BufWriter::new(stream);
+
+    loop {
+        let payload = match read_frame(&mut reader) {
+            Ok(payload) => payload,
+            // The coordinator hung up, which is how it says it has no more jobs for us.
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let result = Job::decode(&payload)
+            .and_then(|job| aggregate_file_range(&job.path, job.range, &job.format));
+
+        let mut response = Vec::new();
+        match result {
+            Ok(results) => {
+                response.push(STATUS_OK);
+                results.write_snapshot(&mut response)?;
+            }
+            Err(e) => {
+                response.push(STATUS_ERR);
+                response.extend_from_slice(e.to_string().as_bytes());
+            }
+        }
+
+        write_frame(&mut writer, &response)?;
+    }
+}
+
+/// The jobs that the coordinator still has to see through.
+struct Jobs {
+    /// Jobs waiting for a worker.
+    pending: VecDeque<Job>,
+    /// The number of jobs currently being run by a worker.
+    in_flight: usize,
+    /// The most recent worker failure, used to explain why the workers were given up on.
+    last_failure: Option<io::Error>,
+}
+
+/// Why a job sent to a worker did not produce any results.
+enum JobError {
+    /// The worker could not be reached or stopped responding. The job should be retried.
+    Worker(io::Error),
+    /// The worker ran the job and reported an error. Retrying the job will not help.
+    Job(io::Error),
+}
+
+/// A connection from the coordinator to a single worker.
+struct WorkerConnection {
+    /// The address of the worker.
+    addr: SocketAddr,
+    /// The open connection to the worker, if any.
+    stream: Option<(BufReader<TcpStream>, BufWriter<TcpStream>)>,
+    /// How long to wait on any single read from or write to the worker.
+    timeout: Duration,
+}
+
+impl WorkerConnection {
+    /// Sends a job to the worker and waits for its results, connecting to the worker if needed.
+    fn run(&mut self, job: &Job) -> Result<AggregationResults, JobError> {
+        let payload = job.encode().map_err(JobError::Job)?;
+
+        let response = self.exchange(&payload).map_err(|e| {
+            // Whatever state the connection is in, it can't be trusted anymore.
+            self.stream = None;
+            JobError::Worker(e)
+        })?;
+
+        match response.split_first() {
+            Some((&STATUS_OK, mut snapshot)) => {
+                AggregationResults::read_snapshot(&mut snapshot).map_err(JobError::Worker)
+            }
+            Some((&STATUS_ERR, message)) => Err(JobError::Job(invalid_data(format!(
+                "worker {} failed on bytes {:?}: {}",
+                self.addr,
+                job.range,
+                String::from_utf8_lossy(message)
+            )))),
+            _ => Err(JobError::Worker(invalid_data("malformed result frame"))),
+        }
+    }
+
+    /// Sends a single frame to the worker and reads back its response frame.
+    fn exchange(&mut self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        if self.stream.is_none() {
+            let stream = TcpStream::connect_timeout(&self.addr, CONNECT_TIMEOUT)?;
+            // Without these, a worker that takes a job and then hangs would block us forever,
+            // instead of failing so that the job can be retried elsewhere.
+            stream.set_read_timeout(Some(self.timeout))?;
+            stream.set_write_timeout(Some(self.timeout))?;
+            let reader = BufReader::new(stream.try_clone()?);
+            self.stream = Some((reader, BufWriter::new(stream)));
+        }
+
+        let (reader, writer) = self.stream.as_mut().expect("just connected");
+        write_frame(writer, payload)?;
+        read_frame(reader)
+    }
+}
+
+/// Aggregates the file at `path` by splitting it into `parts` byte ranges and distributing them
+/// across the workers listening on `workers`.
+///
+/// Ranges are handed out to whichever worker is free. If a worker cannot be reached or its
+/// connection breaks, the range it was working on is retried (possibly by a different worker). A
+/// worker that fails too many times in a row is not used again, and if every worker has been given
+/// up on, the aggregation fails.
+///
+/// A worker that does not respond within [`RESPONSE_TIMEOUT`] counts as failed. See
+/// [`coordinate_with_timeout`] to pick a different timeout.
+pub fn coordinate<P: AsRef<Path>>(
+    path: P,
+    format: &FileFormat,
+    workers: &[SocketAddr],
+    parts: usize,
+) -> io::Result<AggregationResults> {
+    coordinate_with_timeout(path, format, workers, parts, RESPONSE_TIMEOUT)
+}
+
+/// Like [`coordinate`], but a worker counts as failed if any single read from or write to it takes
+/// longer than `timeout`. Since a worker only replies once it has aggregated its whole range, the
+/// timeout needs to be longer than that takes.
+pub fn coordinate_with_timeout<P: AsRef<Path>>(
+    path: P,
+    format: &FileFormat,
+    workers: &[SocketAddr],
+    parts: usize,
+    timeout: Duration,
+) -> io::Result<AggregationResults> {
+    assert!(!workers.is_empty(), "cannot coordinate without any workers");
+
+    let path = path.as_ref();
+
+    let jobs = Mutex::new(Jobs {
+        pending: split_file(path, parts)?
+            .into_iter()
+            .map(|range| Job {
+                path: path.to_path_buf(),
+                range,
+                format: *format,
+            })
+            .collect(),
+        in_flight: 0,
+        last_failure: None,
+    });
+    let results = Mutex::new(AggregationResults::new());
+    let fatal_error = Mutex::new(None);
+
+    // Each worker thread gets its own address, but shares everything else.
+    let (jobs_ref, results_ref, fatal_error_ref) = (&jobs, &results, &fatal_error);
+
+    thread::scope(|s| {
+        for &addr in workers {
+            let (jobs, results, fatal_error) = (jobs_ref, results_ref, fatal_error_ref);
+
+            s.spawn(move || {
+                let mut worker = WorkerConnection {
+                    addr,
+                    stream: None,
+                    timeout,
+                };
+                let mut consecutive_failures = 0;
+
+                while consecutive_failures < MAX_CONSECUTIVE_FAILURES {
+                    if fatal_error.lock().unwrap().is_some() {
+                        return;
+                    }
+
+                    let next = {
+                        let mut jobs = jobs.lock().unwrap();
+                        match jobs.pending.pop_front() {
+                            Some(job) => {
+                                jobs.in_flight += 1;
+                                Some(job)
+                            }
+                            // Nothing is pending and nothing can fail and be retried, so we're done.
+                            None if jobs.in_flight == 0 => return,
+                            None => None,
+                        }
+                    };
+
+                    // Another worker might still fail and hand its job back, so wait around.
+                    let Some(job) = next else {
+                        thread::sleep(RETRY_DELAY);
+                        continue;
+                    };
+
+                    let result = worker.run(&job);
+
+                    let mut jobs = jobs.lock().unwrap();
+                    jobs.in_flight -= 1;
+
+                    match result {
+                        Ok(partial) => {
+                            consecutive_failures = 0;
+                            results.lock().unwrap().merge_aggregation(partial);
+                        }
+                        Err(JobError::Job(e)) => {
+                            fatal_error.lock().unwrap().get_or_insert(e);
+                        }
+                        Err(JobError::Worker(e)) => {
+                            // Hand the job back so that another worker (or this one, once it
+                            // recovers) can retry it.
+                            consecutive_failures += 1;
+                            jobs.pending.push_back(job);
+                            jobs.last_failure =
+                                Some(io::Error::new(e.kind(), format!("{addr}: {e}")));
+                            drop(jobs);
+                            thread::sleep(RETRY_DELAY);
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    if let Some(e) = fatal_error.into_inner().unwrap() {
+        return Err(e);
+    }
+
+    // If every worker gave up, there may still be jobs that nobody finished.
+    let jobs = jobs.into_inner().unwrap();
+    if let Some(job) = jobs.pending.front() {
+        let cause = jobs
+            .last_failure
+            .map_or_else(String::new, |e| format!(" (last failure: {e})"));
+        return Err(io::Error::other(format!(
+            "no workers left to aggregate bytes {:?}{cause}",
+            job.range
+        )));
+    }
+
+    Ok(results.into_inner().unwrap())
+}
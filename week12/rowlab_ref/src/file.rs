@@ -0,0 +1,166 @@
+use crate::scan::Records;
+use crate::{AggregationResults, aggregate_by};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::Path;
+
+/// Describes how to pull a group key and a value out of each line of a delimited text file.
+///
+/// The default format is the one from the original challenge: `<station>;<temperature>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileFormat {
+    /// The character separating the columns of a line.
+    pub delimiter: char,
+    /// The (zero-indexed) column containing the group key.
+    pub key_column: usize,
+    /// The (zero-indexed) column containing the value to aggregate.
+    pub value_column: usize,
+}
+
+impl FileFormat {
+    /// Extracts the key and value columns from a single line.
+    fn parse_line<'a>(&self, line: &'a str) -> Option<(&'a str, f64)> {
+        let columns: Vec<_> = line.split(self.delimiter).collect();
+
+        let key = columns.get(self.key_column)?;
+        let value = columns.get(self.value_column)?.trim().parse().ok()?;
+
+        Some((key, value))
+    }
+}
+
+impl Default for FileFormat {
+    fn default() -> Self {
+        Self {
+            delimiter: ';',
+            key_column: 0,
+            value_column: 1,
+        }
+    }
+}
+
+/// Aggregates the lines of the file at `path`, grouping them by their key column.
+///
+/// Empty lines are skipped. Returns an [`io::ErrorKind::InvalidData`] error naming the first line
+/// that is missing a column or whose value column is not a number.
+pub fn aggregate_file<P: AsRef<Path>>(
+    path: P,
+    format: &FileFormat,
+) -> io::Result<AggregationResults> {
+    let contents = fs::read(path)?;
+    aggregate_bytes(&contents, format)
+}
+
+/// Aggregates the lines within the given byte range of the file at `path`.
+///
+/// The range should start at the beginning of a line and end just after a newline (or at the end
+/// of the file), like the ranges returned by [`split_file`].
+///
+/// Like [`aggregate_file`], a malformed line is reported with its line number, but since only the
+/// range is read, lines are counted from the start of the range rather than the start of the file.
+/// The error message says which byte the counting started at.
+pub fn aggregate_file_range<P: AsRef<Path>>(
+    path: P,
+    range: Range<u64>,
+    format: &FileFormat,
+) -> io::Result<AggregationResults> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(range.start))?;
+
+    let mut contents = Vec::new();
+    file.take(range.end - range.start)
+        .read_to_end(&mut contents)?;
+
+    aggregate_bytes(&contents, format).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("{e} (counting lines from byte {} of the file)", range.start),
+        )
+    })
+}
+
+/// Splits the file at `path` into at most `parts` byte ranges of roughly equal size.
+///
+/// Every range boundary is moved forward to just after the next newline, so no line is ever split
+/// across two ranges. Empty ranges are dropped, so fewer than `parts` ranges may be returned.
+pub fn split_file<P: AsRef<Path>>(path: P, parts: usize) -> io::Result<Vec<Range<u64>>> {
+    assert!(parts > 0, "cannot split a file into zero parts");
+
+    let mut file = BufReader::new(File::open(path)?);
+    let len = file.get_ref().metadata()?.len();
+
+    let mut ranges = Vec::with_capacity(parts);
+    let mut start = 0;
+
+    for part in 1..=parts as u64 {
+        let mut end = len * part / parts as u64;
+
+        // Move the boundary to just after the next newline (unless it is already past this one).
+        if end > start && end < len {
+            file.seek(SeekFrom::Start(end - 1))?;
+            end = end - 1 + file.skip_until(b'\n')? as u64;
+        }
+
+        if end > start {
+            ranges.push(start..end);
+            start = end;
+        }
+    }
+
+    Ok(ranges)
+}
+
+/// Aggregates the lines of `contents`, grouping them by their key column.
+fn aggregate_bytes(contents: &[u8], format: &FileFormat) -> io::Result<AggregationResults> {
+    // The challenge's own format can go through the fast scanner. If the scanner rejects a line
+    // (for example, a temperature like `12` or `1.25`), fall back to the general parser, which
+    // either accepts it or reports which line is wrong.
+    if *format == FileFormat::default() {
+        let mut records = Records::new(contents);
+        let results = aggregate_by(
+            records.by_ref(),
+            |(station, _)| *station,
+            |(_, tenths)| *tenths as f64 / 10.0,
+        );
+
+        if records.error().is_none() {
+            // Station names only need to be validated once each, rather than once per line.
+            return results
+                .try_map_keys(String::from_utf8)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+        }
+    }
+
+    let contents =
+        std::str::from_utf8(contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    aggregate_str(contents, format)
+}
+
+/// Aggregates the lines of `contents`, grouping them by their key column.
+fn aggregate_str(contents: &str, format: &FileFormat) -> io::Result<AggregationResults> {
+    // Stop at the first malformed line and remember where it was, since we can't return an error
+    // from the middle of the aggregation.
+    let mut invalid_line = None;
+    let rows = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map_while(|(i, line)| {
+            let row = format.parse_line(line);
+            if row.is_none() {
+                invalid_line = Some(i + 1);
+            }
+            row
+        });
+
+    let results = aggregate_by(rows, |(key, _)| *key, |(_, value)| *value);
+
+    match invalid_line {
+        Some(line) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {line} does not match the file format"),
+        )),
+        None => Ok(results),
+    }
+}
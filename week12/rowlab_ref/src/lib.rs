@@ -1,11 +1,27 @@
 #![doc = include_str!("../README.md")]
 
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::hash::Hash;
+use std::io;
+use std::path::Path;
 use std::sync::mpsc;
 use std::thread;
 
 mod aggregation;
-use aggregation::AggregationResults;
+pub use aggregation::{AggregationResults, StationAggregation};
+
+mod checkpoint;
+pub use checkpoint::Checkpoint;
+
+mod file;
+pub use file::{FileFormat, aggregate_file, aggregate_file_range, split_file};
+
+pub mod distributed;
+
+mod query;
+pub use query::{Metric, Order, Query};
+
+pub mod scan;
 
 mod measurements;
 pub use measurements::WeatherStations;
@@ -18,6 +34,9 @@ pub const BILLION: usize = 1_000_000_000;
 /// TODO(student): Is this a good size?
 const CHUNK_SIZE: usize = 10_000;
 
+/// The default number of rows aggregated between two checkpoints.
+pub const CHECKPOINT_INTERVAL: usize = 50_000_000;
+
 /// Given an iterator that yields measurements for weather stations, aggregate each weather
 /// station's data.
 ///
@@ -32,13 +51,36 @@ const CHUNK_SIZE: usize = 10_000;
 /// (specifically `mmap`), you could eliminate a large amount of overhead. Regardless, for this
 /// assignment the lower bound is approximately the same as the time it takes to run this function
 /// but with the `s.spawn` completely commented out.
-pub fn aggregate<'a, I>(mut measurements: I) -> AggregationResults
+pub fn aggregate<'a, I>(measurements: I) -> AggregationResults
 where
     I: Iterator<Item = (&'a str, f64)> + Send,
 {
-    assert_eq!(BILLION % CHUNK_SIZE, 0);
-    let num_chunks = BILLION / CHUNK_SIZE;
-    let mut chunk_results = Vec::with_capacity(num_chunks);
+    aggregate_by(
+        measurements,
+        |(station, _)| *station,
+        |(_, measurement)| *measurement,
+    )
+}
+
+/// Given an iterator of arbitrary rows, group the rows by the key that `key_fn` extracts and
+/// aggregate the values that `value_fn` extracts for each group.
+///
+/// The key may be borrowed from the row (for example, a `&str` column of a line), in which case it
+/// is only turned into an owned key the first time it is seen.
+pub fn aggregate_by<K, I, T, FK, FV>(
+    mut rows: I,
+    key_fn: FK,
+    value_fn: FV,
+) -> AggregationResults<K::Owned>
+where
+    K: Hash + Eq + ToOwned + ?Sized,
+    K::Owned: Hash + Eq + Send,
+    I: Iterator<Item = T> + Send,
+    T: Send,
+    FK: Fn(&T) -> &K + Sync,
+    FV: Fn(&T) -> f64 + Sync,
+{
+    let mut chunk_results = Vec::new();
 
     // Create an `mpsc` channel that threads will use to send their aggregation results back to the
     // main (current) thread.
@@ -47,17 +89,22 @@ where
 
     // This scope is just an more ergonomic way to spawn threads and wait for all of them to finish
     // (join all threads), while also allowing the threads to access local data (like the
-    // measurements iterator and the channel, for example).
+    // rows iterator and the channel, for example).
     thread::scope(|s| {
-        for _ in 0..num_chunks {
-            let chunk = measurements.by_ref().take(CHUNK_SIZE).collect::<Vec<_>>();
+        // Keep going until the rows run out. Capping the number of rows (such as at one billion for
+        // the challenge) is up to the caller.
+        loop {
+            let chunk = rows.by_ref().take(CHUNK_SIZE).collect::<Vec<_>>();
+            if chunk.is_empty() {
+                break;
+            }
 
             // Spawn a thread to process each chunk.
             s.spawn(|| {
                 let mut results = AggregationResults::new();
 
-                for (station, measurement) in chunk {
-                    results.insert_measurement(station, measurement);
+                for row in chunk {
+                    results.insert_measurement(key_fn(&row), value_fn(&row));
                 }
 
                 tx.send(results)
@@ -87,3 +134,44 @@ where
             a
         })
 }
+
+/// Aggregates measurements like [`aggregate`], but saves a [`Checkpoint`] to `path` after every
+/// `interval` rows.
+///
+/// The given `checkpoint` is the state to resume from: the first `checkpoint.rows()` measurements
+/// are skipped, and everything after them is merged into the checkpoint's results. Pass
+/// [`Checkpoint::new`] to start from scratch. As long as `measurements` is deterministic, the final
+/// results are the same as those of an uninterrupted run.
+pub fn aggregate_with_checkpoints<'a, I, P>(
+    measurements: I,
+    mut checkpoint: Checkpoint,
+    interval: usize,
+    path: P,
+) -> io::Result<AggregationResults>
+where
+    I: Iterator<Item = (&'a str, f64)> + Send,
+    P: AsRef<Path>,
+{
+    assert!(interval > 0, "checkpoint interval must be positive");
+
+    // Skip over the rows that were already aggregated before the checkpoint was taken.
+    let mut measurements = measurements.skip(checkpoint.rows());
+
+    loop {
+        let mut consumed = 0;
+        let batch = measurements
+            .by_ref()
+            .take(interval)
+            .inspect(|_| consumed += 1);
+
+        let results = aggregate(batch);
+        if consumed == 0 {
+            break;
+        }
+
+        checkpoint.advance(consumed, results);
+        checkpoint.save(&path)?;
+    }
+
+    Ok(checkpoint.into_results())
+}
@@ -1,7 +1,200 @@
-use rowlab::{BILLION, WeatherStations, aggregate};
+use regex::Regex;
+use rowlab::distributed::{coordinate, serve};
+use rowlab::{
+    AggregationResults, BILLION, CHECKPOINT_INTERVAL, Checkpoint, FileFormat, Metric, Order,
+    WeatherStations, aggregate, aggregate_file, aggregate_with_checkpoints,
+};
+use std::net::{SocketAddr, TcpListener};
+use std::ops::Bound;
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
 use std::time::Instant;
 
+/// How to invoke the `rowlab` binary.
+const USAGE: &str = "usage: rowlab [--checkpoint <path>] [--resume <path>]
+       rowlab --file <path> [--delimiter <char>] [--key-column <n>] [--value-column <n>]
+              [--workers <addr,addr,...> [--parts <n>]]
+       rowlab --listen <addr>
+
+query options (applied to the results of any of the above, in this order):
+       [--prefix <prefix>] [--regex <regex>] [--mean-min <x>] [--mean-max <x>]
+       [--top <k> [--by <min|mean|max|spread>] [--order <hottest|coldest>]]";
+
+/// The default number of byte ranges a file is split into for each worker.
+const PARTS_PER_WORKER: usize = 4;
+
+/// Command-line arguments for the `rowlab` binary.
+#[derive(Debug, Default)]
+struct Args {
+    /// Where to periodically save checkpoints, if anywhere.
+    checkpoint: Option<PathBuf>,
+    /// A checkpoint to resume the aggregation from, if any.
+    resume: Option<PathBuf>,
+    /// A delimited file to aggregate instead of the generated measurements, if any.
+    file: Option<PathBuf>,
+    /// How to read the lines of `file`.
+    format: FileFormat,
+    /// Worker addresses to distribute the aggregation of `file` across, if any.
+    workers: Vec<SocketAddr>,
+    /// How many byte ranges to split `file` into when distributing it.
+    parts: Option<usize>,
+    /// The address to listen on when running as a worker, if any.
+    listen: Option<SocketAddr>,
+    /// Only report stations whose name starts with this prefix.
+    prefix: Option<String>,
+    /// Only report stations whose name matches this regex.
+    regex: Option<Regex>,
+    /// Only report stations whose mean is at least this value.
+    mean_min: Option<f64>,
+    /// Only report stations whose mean is at most this value.
+    mean_max: Option<f64>,
+    /// Only report this many stations, ranked by `by` in `order`.
+    top: Option<usize>,
+    /// The metric to rank stations by. Defaults to the mean.
+    by: Option<Metric>,
+    /// Which end of the ranking to report. Defaults to the hottest stations.
+    order: Option<Order>,
+}
+
+impl Args {
+    /// Parses the command-line arguments, exiting with a usage message if they are invalid.
+    fn parse() -> Self {
+        let mut args = Self::default();
+        let mut iter = std::env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .unwrap_or_else(|| exit_with_usage(&format!("`{arg}` requires a value")))
+            };
+
+            match arg.as_str() {
+                "--checkpoint" => args.checkpoint = Some(PathBuf::from(value())),
+                "--resume" => args.resume = Some(PathBuf::from(value())),
+                "--file" => args.file = Some(PathBuf::from(value())),
+                "--delimiter" => args.format.delimiter = parse_value(&arg, &value()),
+                "--key-column" => args.format.key_column = parse_value(&arg, &value()),
+                "--value-column" => args.format.value_column = parse_value(&arg, &value()),
+                "--workers" => {
+                    let value = value();
+                    args.workers = value
+                        .split(',')
+                        .map(|addr| parse_value(&arg, addr))
+                        .collect();
+                }
+                "--parts" => args.parts = Some(parse_value(&arg, &value())),
+                "--listen" => args.listen = Some(parse_value(&arg, &value())),
+                "--prefix" => args.prefix = Some(value()),
+                "--regex" => args.regex = Some(parse_value(&arg, &value())),
+                "--mean-min" => args.mean_min = Some(parse_value(&arg, &value())),
+                "--mean-max" => args.mean_max = Some(parse_value(&arg, &value())),
+                "--top" => args.top = Some(parse_value(&arg, &value())),
+                "--by" => args.by = Some(parse_value(&arg, &value())),
+                "--order" => args.order = Some(parse_value(&arg, &value())),
+                _ => exit_with_usage(&format!("unexpected argument `{arg}`")),
+            }
+        }
+
+        if args.file.is_some() && (args.checkpoint.is_some() || args.resume.is_some()) {
+            exit_with_usage("checkpoints are only supported for generated measurements");
+        }
+        if args.file.is_none() && (!args.workers.is_empty() || args.parts.is_some()) {
+            exit_with_usage("`--workers` and `--parts` require `--file`");
+        }
+        if args.parts == Some(0) {
+            exit_with_usage("`--parts` must be positive");
+        }
+        if args.top.is_none() && (args.by.is_some() || args.order.is_some()) {
+            exit_with_usage("`--by` and `--order` require `--top`");
+        }
+
+        // Keep checkpointing to the same file we resumed from, unless told otherwise.
+        if args.checkpoint.is_none() {
+            args.checkpoint = args.resume.clone();
+        }
+
+        args
+    }
+}
+
+/// Parses the value of a command-line option, exiting with a usage message if it is invalid.
+fn parse_value<T: FromStr>(arg: &str, value: &str) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| exit_with_usage(&format!("invalid value `{value}` for `{arg}`")))
+}
+
+/// Prints the results, narrowed down by whichever query options were given.
+fn print_results(res: &AggregationResults, args: &Args) {
+    let mut query = res.query();
+
+    if let Some(prefix) = &args.prefix {
+        query = query.prefix(prefix);
+    }
+    if let Some(regex) = &args.regex {
+        query = query.matching(regex);
+    }
+
+    let mean_min = args.mean_min.map_or(Bound::Unbounded, Bound::Included);
+    let mean_max = args.mean_max.map_or(Bound::Unbounded, Bound::Included);
+    query = query.mean_in((mean_min, mean_max));
+
+    if let Some(k) = args.top {
+        let by = args.by.unwrap_or(Metric::Mean);
+        let order = args.order.unwrap_or(Order::Hottest);
+        query = query.top(k, by, order);
+    }
+
+    println!("{query}");
+}
+
+/// Prints an error message and the usage string, and then exits.
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("error: {message}\n{USAGE}");
+    process::exit(1);
+}
+
 fn main() {
+    let args = Args::parse();
+
+    if let Some(addr) = args.listen {
+        let listener = TcpListener::bind(addr).unwrap_or_else(|e| {
+            eprintln!("error: unable to listen on {addr}: {e}");
+            process::exit(1);
+        });
+
+        // Print the address we actually bound, since the port may have been picked by the OS.
+        let addr = listener.local_addr().expect("listener has an address");
+        println!("Listening on {addr}");
+
+        if let Err(e) = serve(listener) {
+            eprintln!("error: worker stopped: {e}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(path) = &args.file {
+        let start = Instant::now();
+        let res = if args.workers.is_empty() {
+            aggregate_file(path, &args.format)
+        } else {
+            let parts = args.parts.unwrap_or(args.workers.len() * PARTS_PER_WORKER);
+            coordinate(path, &args.format, &args.workers, parts)
+        };
+        let res = res.unwrap_or_else(|e| {
+            eprintln!("error: unable to aggregate {}: {e}", path.display());
+            process::exit(1);
+        });
+        let elapsed = start.elapsed();
+
+        println!("Elapsed time: {:?}", elapsed);
+
+        print_results(&res, &args);
+        return;
+    }
+
     // Create the measurements iterator. In the real challenge, you would be reading these values
     // from a file on disk.
     let stations = WeatherStations::new();
@@ -11,11 +204,30 @@ fn main() {
     let start = Instant::now();
 
     // If you want to test your code, you can make this number smaller.
-    let res = aggregate(measurements.take(BILLION));
+    let measurements = measurements.take(BILLION);
+
+    let res = match &args.checkpoint {
+        Some(path) => {
+            let checkpoint = match &args.resume {
+                Some(resume) => Checkpoint::load(resume).unwrap_or_else(|e| {
+                    eprintln!("error: unable to load checkpoint {}: {e}", resume.display());
+                    process::exit(1);
+                }),
+                None => Checkpoint::new(),
+            };
+
+            aggregate_with_checkpoints(measurements, checkpoint, CHECKPOINT_INTERVAL, path)
+                .unwrap_or_else(|e| {
+                    eprintln!("error: unable to save checkpoint {}: {e}", path.display());
+                    process::exit(1);
+                })
+        }
+        None => aggregate(measurements),
+    };
 
     let elapsed = start.elapsed();
 
     println!("Elapsed time: {:?}", elapsed);
 
-    println!("{}", res);
+    print_results(&res, &args);
 }
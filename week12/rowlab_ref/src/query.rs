@@ -0,0 +1,156 @@
+use crate::aggregation::write_report;
+use crate::{AggregationResults, StationAggregation};
+use regex::Regex;
+use std::fmt::Display;
+use std::ops::RangeBounds;
+use std::str::FromStr;
+
+/// A statistic of a [`StationAggregation`] that query results can be ranked by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// The minimum measurement.
+    Min,
+    /// The mean measurement.
+    Mean,
+    /// The maximum measurement.
+    Max,
+    /// The difference between the maximum and minimum measurements.
+    Spread,
+}
+
+impl Metric {
+    /// Computes this metric for an aggregation.
+    pub fn of(self, aggregation: &StationAggregation) -> f64 {
+        match self {
+            Self::Min => aggregation.min(),
+            Self::Mean => aggregation.mean(),
+            Self::Max => aggregation.max(),
+            Self::Spread => aggregation.max() - aggregation.min(),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "min" => Ok(Self::Min),
+            "mean" => Ok(Self::Mean),
+            "max" => Ok(Self::Max),
+            "spread" => Ok(Self::Spread),
+            _ => Err(format!("unknown metric `{s}`")),
+        }
+    }
+}
+
+/// Which end of a ranking to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// The stations with the highest values of a metric come first.
+    Hottest,
+    /// The stations with the lowest values of a metric come first.
+    Coldest,
+}
+
+impl FromStr for Order {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hottest" => Ok(Self::Hottest),
+            "coldest" => Ok(Self::Coldest),
+            _ => Err(format!("unknown order `{s}`")),
+        }
+    }
+}
+
+/// A query over [`AggregationResults`], built up by chaining filters and rankings.
+///
+/// A query starts out with every station sorted by name, and each method is applied to whatever the
+/// previous ones left behind. For example, `results.query().prefix("S").top(3, Metric::Mean,
+/// Order::Hottest)` is the three hottest stations starting with `S`, while swapping the two calls
+/// would be whichever of the three hottest stations overall start with `S`.
+///
+/// The `Display` implementation prints the remaining stations (in order) in the same format as the
+/// full [`AggregationResults`] report.
+#[derive(Debug, Clone)]
+pub struct Query<'a, K> {
+    /// The stations (and their aggregations) that are left, in order.
+    entries: Vec<(&'a K, &'a StationAggregation)>,
+}
+
+impl<K: Ord> AggregationResults<K> {
+    /// Starts a query over every station in the results.
+    pub fn query(&self) -> Query<'_, K> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+        Query { entries }
+    }
+}
+
+impl<'a, K> Query<'a, K> {
+    /// Keeps the `k` stations that rank highest (or lowest) by `metric`, in ranked order.
+    ///
+    /// Stations that tie keep their current relative order.
+    pub fn top(mut self, k: usize, metric: Metric, order: Order) -> Self {
+        self.entries.sort_by(|a, b| {
+            let (a, b) = (metric.of(a.1), metric.of(b.1));
+            match order {
+                Order::Hottest => b.total_cmp(&a),
+                Order::Coldest => a.total_cmp(&b),
+            }
+        });
+        self.entries.truncate(k);
+
+        self
+    }
+
+    /// Keeps the stations whose mean falls within `range`.
+    pub fn mean_in<R: RangeBounds<f64>>(mut self, range: R) -> Self {
+        self.entries
+            .retain(|(_, aggregation)| range.contains(&aggregation.mean()));
+
+        self
+    }
+
+    /// The number of stations left in the query.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the query has no stations left.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the remaining stations and their aggregations, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a K, &'a StationAggregation)> + '_ {
+        self.entries.iter().copied()
+    }
+}
+
+impl<K: AsRef<str>> Query<'_, K> {
+    /// Keeps the stations whose name starts with `prefix`.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.entries
+            .retain(|(station, _)| station.as_ref().starts_with(prefix));
+
+        self
+    }
+
+    /// Keeps the stations whose name matches `regex` anywhere.
+    pub fn matching(mut self, regex: &Regex) -> Self {
+        self.entries
+            .retain(|(station, _)| regex.is_match(station.as_ref()));
+
+        self
+    }
+}
+
+impl<K: Display> Display for Query<'_, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_report(f, self.iter())
+    }
+}
@@ -0,0 +1,166 @@
+//! A fast scanner for files in the challenge's `<station>;<temperature>` format.
+//!
+//! Instead of looking at one byte at a time, delimiters are found eight bytes at a time using SWAR
+//! ("SIMD within a register") tricks on `u64` words, falling back to scalar code for the last few
+//! bytes of the input. Temperatures are parsed straight into integer tenths of a degree, and station
+//! names are handed out as raw bytes, so nothing is ever validated as UTF-8 on a per-line basis.
+
+/// A `u64` with every byte set to `0x01`.
+const LO: u64 = 0x0101_0101_0101_0101;
+
+/// A `u64` with every byte set to `0x80`.
+const HI: u64 = 0x8080_8080_8080_8080;
+
+/// Returns a mask with the high bit set in the bytes of `word` that are equal to `byte`.
+///
+/// The mask can have false positives, but only in bytes that come after a true match, so the lowest
+/// set bit always marks the first matching byte.
+fn match_mask(word: u64, byte: u8) -> u64 {
+    let x = word ^ (LO * byte as u64);
+    x.wrapping_sub(LO) & !x & HI
+}
+
+/// Finds the first index in `haystack` for which `is_match` returns a nonzero mask, scanning eight
+/// bytes at a time and falling back to `scalar` for the remainder.
+fn find_with<M, S>(haystack: &[u8], is_match: M, scalar: S) -> Option<usize>
+where
+    M: Fn(u64) -> u64,
+    S: Fn(&[u8]) -> Option<usize>,
+{
+    let mut words = haystack.chunks_exact(8);
+
+    for (i, word) in words.by_ref().enumerate() {
+        let word = u64::from_le_bytes(word.try_into().expect("chunk has eight bytes"));
+
+        let mask = is_match(word);
+        if mask != 0 {
+            // The words are little-endian, so the first byte is the least significant one.
+            return Some(i * 8 + (mask.trailing_zeros() / 8) as usize);
+        }
+    }
+
+    let offset = haystack.len() - words.remainder().len();
+    scalar(words.remainder()).map(|i| offset + i)
+}
+
+/// Finds the index of the first `needle` in `haystack`, eight bytes at a time.
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    find_with(
+        haystack,
+        |word| match_mask(word, needle),
+        |rest| find_byte_scalar(rest, needle),
+    )
+}
+
+/// Finds the index of the first `needle` in `haystack`, one byte at a time.
+pub fn find_byte_scalar(haystack: &[u8], needle: u8) -> Option<usize> {
+    haystack.iter().position(|&b| b == needle)
+}
+
+/// Finds the index of the first byte in `haystack` that is either `a` or `b`, eight bytes at a time.
+pub fn find_either(haystack: &[u8], a: u8, b: u8) -> Option<usize> {
+    find_with(
+        haystack,
+        |word| match_mask(word, a) | match_mask(word, b),
+        |rest| rest.iter().position(|&byte| byte == a || byte == b),
+    )
+}
+
+/// Parses a temperature such as `-12.3` into tenths of a degree (`-123`).
+///
+/// The temperature must be an optional minus sign, at least one digit, a period, and exactly one
+/// more digit. Returns `None` for anything else, or if the result does not fit in an `i32`.
+pub fn parse_tenths(bytes: &[u8]) -> Option<i32> {
+    let (negative, bytes) = match bytes.split_first() {
+        Some((b'-', rest)) => (true, rest),
+        _ => (false, bytes),
+    };
+
+    let [integer @ .., b'.', fraction] = bytes else {
+        return None;
+    };
+    if integer.is_empty() || !fraction.is_ascii_digit() {
+        return None;
+    }
+
+    let mut tenths: i64 = 0;
+    for &digit in integer.iter().chain([fraction]) {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        tenths = tenths.checked_mul(10)?.checked_add((digit - b'0') as i64)?;
+    }
+
+    i32::try_from(if negative { -tenths } else { tenths }).ok()
+}
+
+/// An iterator over the `(station, tenths)` records of a buffer in the `<station>;<temperature>`
+/// format.
+///
+/// Empty lines are skipped, and the last line does not need to end with a newline. The iterator
+/// stops at the first malformed line, and [`Records::error`] reports where that line started.
+#[derive(Debug, Clone)]
+pub struct Records<'a> {
+    /// The buffer being scanned.
+    bytes: &'a [u8],
+    /// The offset of the next line to scan.
+    pos: usize,
+    /// The offset of the malformed line that stopped the iterator, if any.
+    error: Option<usize>,
+}
+
+impl<'a> Records<'a> {
+    /// Creates an iterator over the records of `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            error: None,
+        }
+    }
+
+    /// The byte offset of the malformed line that stopped the iterator, if there was one.
+    pub fn error(&self) -> Option<usize> {
+        self.error
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = (&'a [u8], i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.bytes[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+
+            // Whichever delimiter comes first tells us whether this line has a station at all.
+            let record = match find_either(rest, b';', b'\n') {
+                Some(0) if rest[0] == b'\n' => {
+                    self.pos += 1;
+                    continue;
+                }
+                Some(semicolon) if rest[semicolon] == b';' => {
+                    let value = &rest[semicolon + 1..];
+                    let value_len = find_byte(value, b'\n').unwrap_or(value.len());
+
+                    parse_tenths(&value[..value_len]).map(|tenths| {
+                        let line_len = semicolon + 1 + value_len + 1;
+                        (&rest[..semicolon], tenths, line_len)
+                    })
+                }
+                _ => None,
+            };
+
+            let Some((station, tenths, line_len)) = record else {
+                self.error = Some(self.pos);
+                self.pos = self.bytes.len();
+                return None;
+            };
+
+            self.pos = (self.pos + line_len).min(self.bytes.len());
+            return Some((station, tenths));
+        }
+    }
+}
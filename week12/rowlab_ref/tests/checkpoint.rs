@@ -0,0 +1,107 @@
+use rowlab::{Checkpoint, aggregate, aggregate_with_checkpoints};
+use std::path::PathBuf;
+
+/// Some sample weather stations.
+static STATIONS: [&str; 4] = ["Munich", "Amsterdam", "Pittsburgh", "Madison"];
+
+/// A deterministic stream of `rows` measurements.
+fn measurements(rows: usize) -> impl Iterator<Item = (&'static str, f64)> + Send {
+    (0..rows).map(|i| (STATIONS[i % STATIONS.len()], (i % 997) as f64 / 10.0 - 40.0))
+}
+
+/// A checkpoint path in the temporary directory that is unique to this test process.
+fn checkpoint_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rowlab-{}-{name}.checkpoint", std::process::id()))
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let mut checkpoint = Checkpoint::new();
+    checkpoint.advance(50_000, aggregate(measurements(50_000)));
+
+    let mut bytes = Vec::new();
+    checkpoint.write_to(&mut bytes).unwrap();
+    let restored = Checkpoint::read_from(&mut bytes.as_slice()).unwrap();
+
+    assert_eq!(restored.rows(), 50_000);
+    assert_eq!(
+        restored.results().to_string(),
+        checkpoint.results().to_string()
+    );
+
+    for station in STATIONS {
+        let expected = checkpoint.results().get_metrics(station).unwrap();
+        let actual = restored.results().get_metrics(station).unwrap();
+        assert_eq!(expected.min(), actual.min());
+        assert_eq!(expected.max(), actual.max());
+        assert_eq!(expected.mean(), actual.mean());
+    }
+}
+
+#[test]
+fn test_rejects_garbage() {
+    let garbage = b"definitely not a checkpoint";
+    assert!(Checkpoint::read_from(&mut garbage.as_slice()).is_err());
+}
+
+#[test]
+fn test_rejects_corrupt_lengths() {
+    let mut checkpoint = Checkpoint::new();
+    checkpoint.advance(100, aggregate(measurements(100)));
+    let mut bytes = Vec::new();
+    checkpoint.write_to(&mut bytes).unwrap();
+
+    // The magic number and row count take up 16 bytes, followed by the number of stations and the
+    // length of the first station's name. Corrupt lengths must be reported, not allocated.
+    let mut huge_count = bytes.clone();
+    huge_count[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(Checkpoint::read_from(&mut huge_count.as_slice()).is_err());
+
+    let mut huge_name = bytes.clone();
+    huge_name[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(Checkpoint::read_from(&mut huge_name.as_slice()).is_err());
+
+    let truncated = &bytes[..30];
+    assert!(Checkpoint::read_from(&mut &truncated[..]).is_err());
+}
+
+#[test]
+fn test_resume_matches_uninterrupted() {
+    let path = checkpoint_path("resume");
+
+    // Pretend the process died after only a prefix of the rows had been aggregated.
+    aggregate_with_checkpoints(measurements(70_000), Checkpoint::new(), 25_000, &path).unwrap();
+
+    let checkpoint = Checkpoint::load(&path).unwrap();
+    assert_eq!(checkpoint.rows(), 70_000);
+
+    let resumed =
+        aggregate_with_checkpoints(measurements(200_000), checkpoint, 25_000, &path).unwrap();
+    assert_eq!(Checkpoint::load(&path).unwrap().rows(), 200_000);
+    std::fs::remove_file(&path).unwrap();
+
+    let uninterrupted = aggregate(measurements(200_000));
+    assert_eq!(resumed.to_string(), uninterrupted.to_string());
+}
+
+#[test]
+fn test_save_leaves_similar_names_alone() {
+    let path = checkpoint_path("names");
+    let sibling = path.with_extension("tmp");
+    std::fs::write(&sibling, "not ours").unwrap();
+
+    let mut checkpoint = Checkpoint::new();
+    checkpoint.advance(100, aggregate(measurements(100)));
+    checkpoint.save(&path).unwrap();
+
+    assert_eq!(std::fs::read_to_string(&sibling).unwrap(), "not ours");
+    assert_eq!(Checkpoint::load(&path).unwrap().rows(), 100);
+    std::fs::remove_file(&sibling).unwrap();
+
+    // A path that already ends in `.tmp` is saved through `<name>.tmp.tmp`, not in place.
+    checkpoint.save(&sibling).unwrap();
+    assert_eq!(Checkpoint::load(&sibling).unwrap().rows(), 100);
+
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&sibling).unwrap();
+}
@@ -0,0 +1,199 @@
+use rowlab::distributed::{coordinate, coordinate_with_timeout};
+use rowlab::{FileFormat, aggregate_file, aggregate_file_range, split_file};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+/// Some sample weather stations.
+static STATIONS: [&str; 4] = ["Munich", "Amsterdam", "Pittsburgh", "Madison"];
+
+/// A worker process that is killed when dropped.
+struct Worker {
+    process: Child,
+    addr: SocketAddr,
+}
+
+impl Worker {
+    /// Spawns a `rowlab` worker process listening on a random localhost port.
+    fn spawn() -> Self {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_rowlab"))
+            .args(["--listen", "127.0.0.1:0"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("unable to spawn worker");
+
+        // The worker tells us which port it ended up listening on.
+        let mut line = String::new();
+        BufReader::new(process.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let addr = line
+            .trim()
+            .strip_prefix("Listening on ")
+            .expect("unexpected worker output")
+            .parse()
+            .unwrap();
+
+        Self { process, addr }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// Writes a measurements file with `rows` lines to the temporary directory.
+fn measurements_file(name: &str, rows: usize) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rowlab-{}-{name}.txt", std::process::id()));
+
+    let contents: String = (0..rows)
+        .map(|i| {
+            let station = STATIONS[i % STATIONS.len()];
+            format!("{station};{:.1}\n", (i % 997) as f64 / 10.0 - 40.0)
+        })
+        .collect();
+    std::fs::write(&path, contents).unwrap();
+
+    path
+}
+
+#[test]
+fn test_split_file() {
+    let path = measurements_file("split", 1000);
+    let contents = std::fs::read(&path).unwrap();
+
+    let ranges = split_file(&path, 7).unwrap();
+    assert_eq!(ranges.len(), 7);
+    assert_eq!(ranges.first().unwrap().start, 0);
+    assert_eq!(ranges.last().unwrap().end, contents.len() as u64);
+
+    for pair in ranges.windows(2) {
+        assert_eq!(pair[0].end, pair[1].start);
+        // Every range ends on a line boundary.
+        assert_eq!(contents[pair[0].end as usize - 1], b'\n');
+    }
+
+    // A tiny file can't be split into more ranges than it has lines.
+    assert_eq!(split_file(&path, 5000).unwrap().len(), 1000);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_coordinate() {
+    let path = measurements_file("coordinate", 100_000);
+    let workers: Vec<_> = (0..3).map(|_| Worker::spawn()).collect();
+    let addrs: Vec<_> = workers.iter().map(|worker| worker.addr).collect();
+
+    let res = coordinate(&path, &FileFormat::default(), &addrs, 16).unwrap();
+    let expected = aggregate_file(&path, &FileFormat::default()).unwrap();
+    assert_eq!(res.to_string(), expected.to_string());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_dead_worker_is_retried() {
+    let path = measurements_file("retry", 100_000);
+    let mut workers: Vec<_> = (0..3).map(|_| Worker::spawn()).collect();
+    let mut addrs: Vec<_> = workers.iter().map(|worker| worker.addr).collect();
+
+    // Kill one of the workers, and add the address of a worker that never existed.
+    drop(workers.remove(0));
+    let unused = TcpListener::bind("127.0.0.1:0").unwrap();
+    addrs.push(unused.local_addr().unwrap());
+    drop(unused);
+
+    let res = coordinate(&path, &FileFormat::default(), &addrs, 16).unwrap();
+    let expected = aggregate_file(&path, &FileFormat::default()).unwrap();
+    assert_eq!(res.to_string(), expected.to_string());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_no_workers_left() {
+    let path = measurements_file("no-workers", 1000);
+
+    let unused = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = unused.local_addr().unwrap();
+    drop(unused);
+
+    assert!(coordinate(&path, &FileFormat::default(), &[addr], 4).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_oversized_frame_is_rejected() {
+    let worker = Worker::spawn();
+    let mut stream = TcpStream::connect(worker.addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+
+    // Announce a 4 GiB job. The worker should hang up rather than try to allocate or read it.
+    stream.write_all(&u32::MAX.to_le_bytes()).unwrap();
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).unwrap();
+    assert!(response.is_empty());
+}
+
+#[test]
+fn test_range_error_names_range_start() {
+    let path = measurements_file("bad-range", 100);
+    let mut contents = std::fs::read_to_string(&path).unwrap();
+    contents.push_str("Munich;not a number\n");
+    std::fs::write(&path, &contents).unwrap();
+
+    let ranges = split_file(&path, 2).unwrap();
+    let last = ranges.last().unwrap().clone();
+    let lines_before = contents[..last.start as usize].lines().count();
+
+    let err = aggregate_file_range(&path, last.clone(), &FileFormat::default())
+        .unwrap_err()
+        .to_string();
+    let line = 100 - lines_before + 1;
+    assert!(err.contains(&format!("line {line} ")), "{err}");
+    assert!(err.contains(&format!("byte {}", last.start)), "{err}");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Listens on a random localhost port and accepts every connection, but never replies.
+fn hanging_worker() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    std::thread::spawn(move || {
+        let mut connections = Vec::new();
+        for stream in listener.incoming() {
+            connections.push(stream);
+        }
+    });
+
+    addr
+}
+
+#[test]
+fn test_hanging_worker_is_retried() {
+    let path = measurements_file("hanging", 10_000);
+    let worker = Worker::spawn();
+    let addrs = [hanging_worker(), worker.addr];
+    let timeout = Duration::from_millis(200);
+
+    let res = coordinate_with_timeout(&path, &FileFormat::default(), &addrs, 8, timeout).unwrap();
+    let expected = aggregate_file(&path, &FileFormat::default()).unwrap();
+    assert_eq!(res.to_string(), expected.to_string());
+
+    // With nobody else to take over, the hanging worker is eventually given up on.
+    let addrs = [hanging_worker()];
+    assert!(coordinate_with_timeout(&path, &FileFormat::default(), &addrs, 2, timeout).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,93 @@
+use rowlab::{FileFormat, aggregate_by, aggregate_file};
+
+/// A row of a made-up request log.
+struct Request {
+    endpoint: String,
+    status: u16,
+    latency_ms: f64,
+}
+
+/// A handful of made-up requests.
+fn requests() -> Vec<Request> {
+    [
+        ("/index", 200, 12.0),
+        ("/login", 500, 250.0),
+        ("/index", 200, 18.0),
+        ("/login", 200, 40.0),
+        ("/search", 404, 3.0),
+    ]
+    .into_iter()
+    .map(|(endpoint, status, latency_ms)| Request {
+        endpoint: endpoint.to_string(),
+        status,
+        latency_ms,
+    })
+    .collect()
+}
+
+#[test]
+fn test_borrowed_keys() {
+    let res = aggregate_by(
+        requests().into_iter(),
+        |request| request.endpoint.as_str(),
+        |request| request.latency_ms,
+    );
+
+    let index = res.get_metrics("/index").expect("missing key");
+    assert_eq!(index.min(), 12.0);
+    assert_eq!(index.max(), 18.0);
+    assert_eq!(index.mean(), 15.0);
+    assert!(res.get_metrics("/logout").is_none());
+
+    assert_eq!(
+        res.to_string(),
+        "{/index=12.0/15.0/18.0, /login=40.0/145.0/250.0, /search=3.0/3.0/3.0}"
+    );
+}
+
+#[test]
+fn test_owned_keys() {
+    let mut res = aggregate_by(
+        requests().into_iter(),
+        |request| &request.status,
+        |request| request.latency_ms,
+    );
+    res.merge_aggregation(aggregate_by(
+        [(200u16, 2.0)].into_iter(),
+        |(status, _)| status,
+        |(_, latency)| *latency,
+    ));
+
+    let ok = res.get_metrics(&200).expect("missing key");
+    assert_eq!(ok.min(), 2.0);
+    assert_eq!(ok.max(), 40.0);
+    assert_eq!(ok.mean(), 18.0);
+
+    // Keys are sorted by their `Ord` implementation, not lexicographically.
+    assert_eq!(
+        res.to_string(),
+        "{200=2.0/18.0/40.0, 404=3.0/3.0/3.0, 500=250.0/250.0/250.0}"
+    );
+}
+
+#[test]
+fn test_file() {
+    let path = std::env::temp_dir().join(format!("rowlab-{}-group-by.csv", std::process::id()));
+    std::fs::write(&path, "12.5,x,Munich\n-3.0,y,Pittsburgh\n\n7.5,z,Munich\n").unwrap();
+
+    let format = FileFormat {
+        delimiter: ',',
+        key_column: 2,
+        value_column: 0,
+    };
+    let res = aggregate_file(&path, &format).unwrap();
+    assert_eq!(
+        res.to_string(),
+        "{Munich=7.5/10.0/12.5, Pittsburgh=-3.0/-3.0/-3.0}"
+    );
+
+    // The default format expects semicolons, so none of these lines have a value column.
+    assert!(aggregate_file(&path, &FileFormat::default()).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
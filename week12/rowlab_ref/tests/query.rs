@@ -0,0 +1,82 @@
+use regex::Regex;
+use rowlab::{AggregationResults, Metric, Order, aggregate};
+
+/// Some sample weather stations and their measurements.
+static MEASUREMENTS: [(&str, f64); 8] = [
+    ("Munich", 10.0),
+    ("Munich", 12.0),
+    ("Madison", -5.0),
+    ("Madison", 25.0),
+    ("Pittsburgh", 8.0),
+    ("Amsterdam", 11.0),
+    ("Amsterdam", 11.5),
+    ("Mumbai", 30.0),
+];
+
+/// Aggregates [`MEASUREMENTS`].
+fn results() -> AggregationResults {
+    aggregate(MEASUREMENTS.into_iter())
+}
+
+/// The station names left in a query, in order.
+fn names<'a>(
+    query: impl Iterator<Item = (&'a String, &'a rowlab::StationAggregation)>,
+) -> Vec<&'a str> {
+    query.map(|(station, _)| station.as_str()).collect()
+}
+
+#[test]
+fn test_query_everything_matches_report() {
+    let res = results();
+    assert_eq!(res.query().to_string(), res.to_string());
+    assert_eq!(res.query().len(), 5);
+}
+
+#[test]
+fn test_top() {
+    let res = results();
+
+    let hottest = res.query().top(2, Metric::Mean, Order::Hottest);
+    assert_eq!(names(hottest.iter()), ["Mumbai", "Amsterdam"]);
+
+    let coldest = res.query().top(2, Metric::Max, Order::Coldest);
+    assert_eq!(names(coldest.iter()), ["Pittsburgh", "Amsterdam"]);
+
+    let widest = res.query().top(1, Metric::Spread, Order::Hottest);
+    assert_eq!(widest.to_string(), "{Madison=-5.0/10.0/25.0}");
+
+    // Asking for more stations than there are just ranks all of them.
+    assert_eq!(res.query().top(100, Metric::Min, Order::Coldest).len(), 5);
+}
+
+#[test]
+fn test_filters() {
+    let res = results();
+
+    let prefixed = res.query().prefix("Mu");
+    assert_eq!(names(prefixed.iter()), ["Mumbai", "Munich"]);
+
+    let regex = Regex::new("^M.*[hn]$").unwrap();
+    let matching = res.query().matching(&regex);
+    assert_eq!(names(matching.iter()), ["Madison", "Munich"]);
+
+    let mild = res.query().mean_in(10.0..=11.0);
+    assert_eq!(names(mild.iter()), ["Madison", "Munich"]);
+
+    let none = res.query().prefix("Z");
+    assert!(none.is_empty());
+    assert_eq!(none.to_string(), "{}");
+}
+
+#[test]
+fn test_chaining_order_matters() {
+    let res = results();
+
+    // The coldest station starting with `M`...
+    let filtered_first = res.query().prefix("M").top(1, Metric::Mean, Order::Coldest);
+    assert_eq!(names(filtered_first.iter()), ["Madison"]);
+
+    // ...versus the coldest station overall, if it starts with `M`.
+    let ranked_first = res.query().top(1, Metric::Mean, Order::Coldest).prefix("M");
+    assert!(ranked_first.is_empty());
+}
@@ -0,0 +1,163 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use rowlab::scan::{Records, find_byte, find_byte_scalar, find_either, parse_tenths};
+use rowlab::{FileFormat, aggregate_file};
+
+/// How many random inputs each fuzz-style test tries.
+const ITERATIONS: usize = 2_000;
+
+/// Bytes that random inputs are made of, weighted towards the ones the scanner cares about.
+const ALPHABET: &[u8] = b";;;\n\n\n--..0123456789abcXYZ\xc3\xa9\x00\xff";
+
+/// Generates a random buffer of up to `max_len` bytes from [`ALPHABET`].
+fn random_bytes(rng: &mut StdRng, max_len: usize) -> Vec<u8> {
+    let len = rng.random_range(0..=max_len);
+    (0..len).map(|_| *ALPHABET.choose(rng).unwrap()).collect()
+}
+
+/// A naive `split`-based parser for the `<station>;<temperature>` format, to check the scanner
+/// against. Returns the parsed records and the offset of the first malformed line, if any.
+fn naive_records(bytes: &[u8]) -> (Vec<(&[u8], i32)>, Option<usize>) {
+    let mut records = Vec::new();
+    let mut offset = 0;
+
+    for line in bytes.split(|&b| b == b'\n') {
+        let start = offset;
+        offset += line.len() + 1;
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut columns = line.splitn(2, |&b| b == b';');
+        let station = columns.next().unwrap();
+        let Some(tenths) = columns.next().and_then(naive_tenths) else {
+            return (records, Some(start));
+        };
+        records.push((station, tenths));
+    }
+
+    (records, None)
+}
+
+/// A naive parser for temperatures like `-12.3`, returning tenths of a degree.
+fn naive_tenths(value: &[u8]) -> Option<i32> {
+    let value = std::str::from_utf8(value).ok()?;
+    let (integer, fraction) = value.split_once('.')?;
+    let digits = integer.strip_prefix('-').unwrap_or(integer);
+
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if digits.is_empty() || !all_digits(digits) || fraction.len() != 1 || !all_digits(fraction) {
+        return None;
+    }
+
+    format!("{integer}{fraction}").parse().ok()
+}
+
+#[test]
+fn test_find_byte_matches_scalar() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..ITERATIONS {
+        let bytes = random_bytes(&mut rng, 64);
+
+        for &needle in b";\n-\xff\x00" {
+            assert_eq!(
+                find_byte(&bytes, needle),
+                find_byte_scalar(&bytes, needle),
+                "{bytes:?}"
+            );
+        }
+
+        assert_eq!(
+            find_either(&bytes, b';', b'\n'),
+            bytes.iter().position(|&b| b == b';' || b == b'\n'),
+            "{bytes:?}"
+        );
+    }
+}
+
+#[test]
+fn test_parse_tenths() {
+    assert_eq!(parse_tenths(b"0.0"), Some(0));
+    assert_eq!(parse_tenths(b"-0.1"), Some(-1));
+    assert_eq!(parse_tenths(b"12.3"), Some(123));
+    assert_eq!(parse_tenths(b"-99.9"), Some(-999));
+    assert_eq!(parse_tenths(b"-214748364.8"), Some(i32::MIN));
+
+    for invalid in [
+        &b""[..],
+        b"-",
+        b".5",
+        b"-.5",
+        b"12",
+        b"12.",
+        b"1.25",
+        b"1,5",
+        b"--1.0",
+        b" 1.0",
+        b"214748364.8",
+    ] {
+        assert_eq!(parse_tenths(invalid), None, "{invalid:?}");
+    }
+}
+
+#[test]
+fn test_records_match_naive_parser() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let stations: [&[u8]; 4] = [b"Munich", b"Pittsburgh", "S\u{e3}o Paulo".as_bytes(), b""];
+
+    // Well-formed inputs, with the occasional empty line and missing final newline.
+    for _ in 0..ITERATIONS {
+        let mut bytes = Vec::new();
+        for _ in 0..rng.random_range(0..20) {
+            if rng.random_bool(0.1) {
+                bytes.push(b'\n');
+            }
+            bytes.extend_from_slice(stations.choose(&mut rng).unwrap());
+            let tenths: i32 = rng.random_range(-999..=999);
+            let sign = if tenths < 0 { "-" } else { "" };
+            let value = format!("{sign}{}.{}\n", tenths.abs() / 10, tenths.abs() % 10);
+            bytes.extend_from_slice(value.as_bytes());
+        }
+        if rng.random_bool(0.5) {
+            bytes.pop();
+        }
+
+        let mut records = Records::new(&bytes);
+        let scanned: Vec<_> = records.by_ref().collect();
+        assert_eq!((scanned, records.error()), naive_records(&bytes));
+    }
+
+    // Garbage inputs, which must be rejected at exactly the same line.
+    for _ in 0..ITERATIONS {
+        let bytes = random_bytes(&mut rng, 64);
+
+        let mut records = Records::new(&bytes);
+        let scanned: Vec<_> = records.by_ref().collect();
+        assert_eq!(
+            (scanned, records.error()),
+            naive_records(&bytes),
+            "{bytes:?}"
+        );
+    }
+}
+
+#[test]
+fn test_file_falls_back_to_general_parser() {
+    let path = std::env::temp_dir().join(format!("rowlab-{}-scan.txt", std::process::id()));
+
+    // The scanner only understands temperatures with exactly one decimal place.
+    std::fs::write(&path, "Munich;12\nMunich;1.25\nMadison;-3.0\n").unwrap();
+    let res = aggregate_file(&path, &FileFormat::default()).unwrap();
+    assert_eq!(
+        res.to_string(),
+        "{Madison=-3.0/-3.0/-3.0, Munich=1.2/6.6/12.0}"
+    );
+
+    std::fs::write(&path, "Munich;12.0\nMunich\n").unwrap();
+    assert!(aggregate_file(&path, &FileFormat::default()).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
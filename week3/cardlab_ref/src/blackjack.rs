@@ -0,0 +1,705 @@
+//! This module contains a blackjack simulator.
+//!
+//! A round of blackjack is played between one player and the dealer, out of a [`Shoe`] of several
+//! decks shuffled together. Number cards are worth their number, face cards are worth 10, and Aces
+//! are worth 1 or 11, whichever is better (see [`card_value`] and [`Hand::total`]). The goal is to
+//! get a higher total than the dealer without going over 21.
+//!
+//! The player and the dealer are both dealt two cards, and one of the dealer's cards is dealt face
+//! up. Unless either of them has a blackjack (an Ace and a 10-valued card), the player then takes
+//! [`Action`]s until they stand or bust:
+//!
+//! - **Hit**: take another card.
+//! - **Stand**: take no more cards.
+//! - **Double**: double the bet, take exactly one more card, and stand.
+//! - **Split**: split a pair into two hands with a bet each, and play them one after the other.
+//! - **Surrender**: give up the hand as the very first action, and get half of the bet back.
+//!
+//! The dealer then draws cards until they have at least 17, and the hands are settled. The details
+//! vary from table to table, and are described by [`Rules`].
+//!
+//! A [`Strategy`] decides what the player does. [`BasicStrategy`] is the standard strategy table
+//! for multi-deck games, and [`simulate`] plays many rounds with a strategy to estimate the house
+//! edge of a set of rules.
+
+use crate::Card;
+use crate::packed::CardSet;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::fmt::Display;
+
+/// Returns the value of a card in blackjack: 2-10 for number cards, 10 for face cards, and 1 for
+/// Aces (which [`Hand::total`] counts as 11 instead when that does not bust the hand).
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::Card;
+/// # use cardlab_ref::blackjack::card_value;
+/// #
+/// assert_eq!(card_value(&Card::new("heart", 7)), 7);
+/// assert_eq!(card_value(&Card::new("heart", 12)), 10);
+/// assert_eq!(card_value(&Card::new("heart", 14)), 1);
+/// ```
+pub fn card_value(card: &Card) -> u8 {
+    match card.rank_value() {
+        14 => 1,
+        value => value.min(10),
+    }
+}
+
+/// The rules of a blackjack table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rules {
+    /// The number of decks in the shoe.
+    pub decks: usize,
+    /// The fraction of the shoe that is dealt before it is reshuffled, in the range (0, 1].
+    pub penetration: f64,
+    /// Whether the dealer hits a soft 17 (known as H17) instead of standing on it (S17).
+    pub hit_soft_17: bool,
+    /// How much a blackjack pays per unit bet, usually 1.5 (3:2) or 1.2 (6:5).
+    pub blackjack_pays: f64,
+    /// Whether hands that were split can be doubled.
+    pub double_after_split: bool,
+    /// The number of hands a player can split into, including the original hand.
+    pub max_hands: usize,
+    /// Whether the player can surrender (after the dealer checks for blackjack).
+    pub surrender: bool,
+}
+
+impl Default for Rules {
+    /// A common six-deck game: the dealer stands on soft 17, blackjack pays 3:2, doubling after
+    /// splitting is allowed, and the player can split up to four hands and surrender.
+    fn default() -> Self {
+        Self {
+            decks: 6,
+            penetration: 0.75,
+            hit_soft_17: false,
+            blackjack_pays: 1.5,
+            double_after_split: true,
+            max_hands: 4,
+            surrender: true,
+        }
+    }
+}
+
+/// A shoe of several decks shuffled together.
+///
+/// Once the cut card has been reached (see [`Rules::penetration`]), [`Shoe::needs_shuffle`]
+/// returns `true`, and the shoe should be reshuffled before the next round.
+#[derive(Debug, Clone)]
+pub struct Shoe<R> {
+    /// Every card in the shoe, in the order they are dealt.
+    cards: Vec<Card>,
+    /// The index of the next card to deal.
+    next: usize,
+    /// The index of the cut card.
+    cut: usize,
+    /// Where the randomness for shuffling comes from.
+    rng: R,
+}
+
+impl<R: Rng> Shoe<R> {
+    /// Creates a shuffled shoe of `decks` decks, which should be reshuffled once `penetration` of
+    /// its cards have been dealt.
+    ///
+    /// # Panics
+    ///
+    /// If `decks` is 0, or `penetration` is not in the range (0, 1].
+    pub fn new(decks: usize, penetration: f64, rng: R) -> Self {
+        assert!(decks > 0, "a shoe needs at least one deck");
+        assert!(
+            penetration > 0.0 && penetration <= 1.0,
+            "penetration must be in the range (0, 1]"
+        );
+
+        let deck = CardSet::full().iter().map(Card::from);
+        let cards: Vec<Card> = (0..decks).flat_map(|_| deck.clone()).collect();
+        let cut = ((cards.len() as f64 * penetration) as usize).max(1);
+
+        let mut shoe = Self {
+            cards,
+            next: 0,
+            cut,
+            rng,
+        };
+        shoe.shuffle();
+        shoe
+    }
+
+    /// Returns the number of cards left to deal.
+    pub fn remaining(&self) -> usize {
+        self.cards.len() - self.next
+    }
+
+    /// Returns whether the cut card has been reached.
+    pub fn needs_shuffle(&self) -> bool {
+        self.next >= self.cut
+    }
+
+    /// Puts every card back into the shoe and shuffles it.
+    pub fn shuffle(&mut self) {
+        self.cards.shuffle(&mut self.rng);
+        self.next = 0;
+    }
+
+    /// Deals the next card. If the shoe has run out completely (which can only happen with very
+    /// deep penetration), it is reshuffled first.
+    pub fn draw(&mut self) -> Card {
+        if self.remaining() == 0 {
+            self.shuffle();
+        }
+
+        let card = self.cards[self.next];
+        self.next += 1;
+        card
+    }
+}
+
+/// A blackjack hand.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::Card;
+/// # use cardlab_ref::blackjack::Hand;
+/// #
+/// let mut hand = Hand::from(vec![Card::new("spade", 14), Card::new("heart", 6)]);
+/// assert_eq!(hand.total(), 17);
+/// assert!(hand.is_soft());
+///
+/// // The Ace has to count as 1 now.
+/// hand.push(Card::new("club", 10));
+/// assert_eq!(hand.total(), 17);
+/// assert!(!hand.is_soft());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Hand {
+    /// The cards in the hand, in the order they were dealt.
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    /// Creates an empty hand.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a card to the hand.
+    pub fn push(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    /// Returns the cards in the hand, in the order they were dealt.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Returns the total with every Ace counted as 1.
+    fn hard_total(&self) -> u8 {
+        self.cards.iter().map(card_value).sum()
+    }
+
+    /// Returns the best total of the hand, where one Ace counts as 11 if that does not take the
+    /// total over 21.
+    pub fn total(&self) -> u8 {
+        let total = self.hard_total();
+        if self.is_soft() { total + 10 } else { total }
+    }
+
+    /// Returns whether the hand is soft, meaning that it has an Ace counted as 11.
+    pub fn is_soft(&self) -> bool {
+        self.cards.iter().any(|card| card_value(card) == 1) && self.hard_total() <= 11
+    }
+
+    /// Returns whether the hand is a blackjack: an Ace and a 10-valued card.
+    ///
+    /// Note that a hand that was split from a pair never counts as a blackjack, which is up to the
+    /// table to check.
+    pub fn is_blackjack(&self) -> bool {
+        self.cards.len() == 2 && self.total() == 21
+    }
+
+    /// Returns whether the total of the hand is over 21.
+    pub fn is_bust(&self) -> bool {
+        self.hard_total() > 21
+    }
+
+    /// Returns the value of the pair if the hand is two cards of the same value (so any two
+    /// 10-valued cards are a pair), or [`None`] otherwise.
+    pub fn pair(&self) -> Option<u8> {
+        match self.cards.as_slice() {
+            [a, b] if card_value(a) == card_value(b) => Some(card_value(a)),
+            _ => None,
+        }
+    }
+}
+
+impl From<Vec<Card>> for Hand {
+    fn from(cards: Vec<Card>) -> Self {
+        Self { cards }
+    }
+}
+
+/// The actions a player can take on a hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Hit,
+    Stand,
+    Double,
+    Split,
+    Surrender,
+}
+
+/// Everything a [`Strategy`] gets to see when deciding what to do with a hand.
+#[derive(Debug, Clone, Copy)]
+pub struct Decision<'a> {
+    /// The hand being played.
+    pub hand: &'a Hand,
+    /// The dealer's face-up card.
+    pub dealer_up: Card,
+    /// Whether the hand can be doubled.
+    pub can_double: bool,
+    /// Whether the hand can be split.
+    pub can_split: bool,
+    /// Whether the hand can be surrendered.
+    pub can_surrender: bool,
+}
+
+impl Decision<'_> {
+    /// Returns whether the action is allowed. Hitting and standing are always allowed.
+    pub fn allows(&self, action: Action) -> bool {
+        match action {
+            Action::Hit | Action::Stand => true,
+            Action::Double => self.can_double,
+            Action::Split => self.can_split,
+            Action::Surrender => self.can_surrender,
+        }
+    }
+}
+
+/// A way of playing blackjack hands.
+pub trait Strategy {
+    /// Returns the action to take on a hand. It must be allowed by the decision (see
+    /// [`Decision::allows`]).
+    fn action(&mut self, decision: &Decision) -> Action;
+}
+
+/// The basic strategy for multi-deck games, which is the best way to play every hand without
+/// keeping track of the cards that were already dealt.
+///
+/// The table depends on whether the dealer hits soft 17 and whether doubling after splitting is
+/// allowed, so it is created from the [`Rules`] of the table. Whenever the best action is not
+/// allowed (such as doubling a hand of three cards), it falls back to the next best one.
+#[derive(Debug, Clone, Copy)]
+pub struct BasicStrategy {
+    /// The rules of the table the strategy is playing at.
+    rules: Rules,
+}
+
+impl BasicStrategy {
+    /// Creates the basic strategy for a table with the given rules.
+    pub fn new(rules: Rules) -> Self {
+        Self { rules }
+    }
+
+    /// Returns whether a pair of cards with value `pair` should be split against the dealer's card
+    /// `up` (2-11, where 11 is an Ace).
+    fn split(&self, pair: u8, up: u8) -> bool {
+        let das = self.rules.double_after_split;
+        match pair {
+            1 | 8 => true,
+            9 => matches!(up, 2..=6 | 8 | 9),
+            7 => up <= 7,
+            6 => up <= 6 && (das || up >= 3),
+            4 => das && matches!(up, 5 | 6),
+            2 | 3 => up <= 7 && (das || up >= 4),
+            _ => false,
+        }
+    }
+
+    /// Returns the best action for a soft total against the dealer's card `up`, assuming that
+    /// doubling is allowed. [`Action::Double`] on soft 18 and 19 means "double if allowed,
+    /// otherwise stand".
+    fn soft(&self, total: u8, up: u8) -> Action {
+        let h17 = self.rules.hit_soft_17;
+        match total {
+            20.. => Action::Stand,
+            19 if h17 && up == 6 => Action::Double,
+            19 => Action::Stand,
+            18 if (3..=6).contains(&up) || (h17 && up == 2) => Action::Double,
+            18 if up <= 8 => Action::Stand,
+            17 if (3..=6).contains(&up) => Action::Double,
+            15 | 16 if (4..=6).contains(&up) => Action::Double,
+            13 | 14 if (5..=6).contains(&up) => Action::Double,
+            _ => Action::Hit,
+        }
+    }
+
+    /// Returns the best action for a hard total against the dealer's card `up`, assuming that
+    /// doubling and surrendering are allowed.
+    fn hard(&self, total: u8, up: u8) -> Action {
+        let h17 = self.rules.hit_soft_17;
+        match total {
+            17 if h17 && up == 11 => Action::Surrender,
+            17.. => Action::Stand,
+            16 if up >= 9 => Action::Surrender,
+            15 if up == 10 || (h17 && up == 11) => Action::Surrender,
+            13..=16 if up <= 6 => Action::Stand,
+            12 if (4..=6).contains(&up) => Action::Stand,
+            11 if up <= 10 || h17 => Action::Double,
+            10 if up <= 9 => Action::Double,
+            9 if (3..=6).contains(&up) => Action::Double,
+            _ => Action::Hit,
+        }
+    }
+}
+
+impl Strategy for BasicStrategy {
+    fn action(&mut self, decision: &Decision) -> Action {
+        let hand = decision.hand;
+        let up = match card_value(&decision.dealer_up) {
+            1 => 11,
+            value => value,
+        };
+
+        if let Some(pair) = hand.pair()
+            && decision.can_split
+            && self.split(pair, up)
+        {
+            return Action::Split;
+        }
+
+        let (total, soft) = (hand.total(), hand.is_soft());
+        let action = if soft {
+            self.soft(total, up)
+        } else {
+            self.hard(total, up)
+        };
+
+        match action {
+            Action::Double if !decision.can_double => {
+                if soft && total >= 18 {
+                    Action::Stand
+                } else {
+                    Action::Hit
+                }
+            }
+            // Without surrender, 17 stands, and 15 and 16 play like they would against a 7.
+            Action::Surrender if !decision.can_surrender => self.hard(total.min(17), 7),
+            action => action,
+        }
+    }
+}
+
+/// The error returned when a [`Strategy`] takes an action that is not allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalAction(pub Action);
+
+impl Display for IllegalAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not allowed on this hand", self.0)
+    }
+}
+
+impl std::error::Error for IllegalAction {}
+
+/// One of the player's hands at the end of a round.
+#[derive(Debug, Clone)]
+pub struct PlayedHand {
+    /// The cards in the hand.
+    pub hand: Hand,
+    /// The total bet on the hand, including doubling.
+    pub bet: f64,
+    /// How much the player won (or lost, if negative) on the hand.
+    pub net: f64,
+    /// Whether the hand was surrendered.
+    pub surrendered: bool,
+}
+
+/// The result of a round.
+#[derive(Debug, Clone)]
+pub struct RoundResult {
+    /// The player's hands, in the order they were played. There is more than one if the player
+    /// split.
+    pub hands: Vec<PlayedHand>,
+    /// The dealer's hand.
+    pub dealer: Hand,
+}
+
+impl RoundResult {
+    /// Returns how much the player won (or lost, if negative) over every hand.
+    pub fn net(&self) -> f64 {
+        self.hands.iter().map(|hand| hand.net).sum()
+    }
+
+    /// Returns how much the player bet over every hand.
+    pub fn wagered(&self) -> f64 {
+        self.hands.iter().map(|hand| hand.bet).sum()
+    }
+}
+
+/// A hand that is still being played.
+struct Seat {
+    /// The cards in the hand.
+    hand: Hand,
+    /// The total bet on the hand.
+    bet: f64,
+    /// Whether the hand was split from a pair.
+    split: bool,
+    /// Whether the hand was surrendered.
+    surrendered: bool,
+}
+
+/// A blackjack table, where rounds are played with a bet of 1 unit each.
+#[derive(Debug, Clone)]
+pub struct Table<R> {
+    /// The rules of the table.
+    rules: Rules,
+    /// The shoe the cards are dealt from.
+    shoe: Shoe<R>,
+}
+
+impl<R: Rng> Table<R> {
+    /// Creates a table with the given rules, whose shoe is shuffled with `rng`.
+    ///
+    /// # Panics
+    ///
+    /// If the rules have no decks, or a penetration that is not in the range (0, 1].
+    pub fn new(rules: Rules, rng: R) -> Self {
+        Self {
+            rules,
+            shoe: Shoe::new(rules.decks, rules.penetration, rng),
+        }
+    }
+
+    /// Returns the rules of the table.
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Plays a round with a bet of 1 unit, reshuffling the shoe first if needed.
+    ///
+    /// Returns an error if the strategy takes an action that is not allowed. The cards that were
+    /// dealt in that round are lost.
+    pub fn play_round(
+        &mut self,
+        strategy: &mut dyn Strategy,
+    ) -> Result<RoundResult, IllegalAction> {
+        if self.shoe.needs_shuffle() {
+            self.shoe.shuffle();
+        }
+
+        let mut player = Hand::new();
+        let mut dealer = Hand::new();
+        for _ in 0..2 {
+            player.push(self.shoe.draw());
+            dealer.push(self.shoe.draw());
+        }
+        let dealer_up = dealer.cards()[0];
+
+        // The dealer checks for blackjack before the player acts, so a dealer blackjack only loses
+        // the original bet.
+        if player.is_blackjack() || dealer.is_blackjack() {
+            let net = match (player.is_blackjack(), dealer.is_blackjack()) {
+                (true, true) => 0.0,
+                (true, false) => self.rules.blackjack_pays,
+                _ => -1.0,
+            };
+            let hands = vec![PlayedHand {
+                hand: player,
+                bet: 1.0,
+                net,
+                surrendered: false,
+            }];
+            return Ok(RoundResult { hands, dealer });
+        }
+
+        let mut seats = vec![Seat {
+            hand: player,
+            bet: 1.0,
+            split: false,
+            surrendered: false,
+        }];
+        let mut i = 0;
+        while i < seats.len() {
+            self.play_seat(&mut seats, i, dealer_up, strategy)?;
+            i += 1;
+        }
+
+        // The dealer only needs to draw if one of the player's hands is still alive.
+        if seats.iter().any(|s| !s.surrendered && !s.hand.is_bust()) {
+            while dealer.total() < 17
+                || (self.rules.hit_soft_17 && dealer.total() == 17 && dealer.is_soft())
+            {
+                dealer.push(self.shoe.draw());
+            }
+        }
+
+        let hands = seats
+            .into_iter()
+            .map(|seat| {
+                let net = if seat.surrendered {
+                    -seat.bet / 2.0
+                } else if seat.hand.is_bust() {
+                    -seat.bet
+                } else if dealer.is_bust() || seat.hand.total() > dealer.total() {
+                    seat.bet
+                } else if seat.hand.total() < dealer.total() {
+                    -seat.bet
+                } else {
+                    0.0
+                };
+
+                PlayedHand {
+                    hand: seat.hand,
+                    bet: seat.bet,
+                    net,
+                    surrendered: seat.surrendered,
+                }
+            })
+            .collect();
+
+        Ok(RoundResult { hands, dealer })
+    }
+
+    /// Plays the `i`th hand until it stands, busts, doubles or surrenders. Splitting adds the new
+    /// hand right after it, so that it is played next.
+    fn play_seat(
+        &mut self,
+        seats: &mut Vec<Seat>,
+        i: usize,
+        dealer_up: Card,
+        strategy: &mut dyn Strategy,
+    ) -> Result<(), IllegalAction> {
+        loop {
+            let hands = seats.len();
+            let seat = &mut seats[i];
+            // A hand that was just split only has one card.
+            if seat.hand.cards().len() == 1 {
+                seat.hand.push(self.shoe.draw());
+            }
+
+            // Split Aces only get one card each.
+            let split_aces = seat.split && card_value(&seat.hand.cards()[0]) == 1;
+            if seat.hand.total() >= 21 || split_aces {
+                return Ok(());
+            }
+
+            let two_cards = seat.hand.cards().len() == 2;
+            let decision = Decision {
+                hand: &seat.hand,
+                dealer_up,
+                can_double: two_cards && (!seat.split || self.rules.double_after_split),
+                can_split: seat.hand.pair().is_some() && hands < self.rules.max_hands,
+                can_surrender: two_cards && !seat.split && self.rules.surrender,
+            };
+
+            let action = strategy.action(&decision);
+            if !decision.allows(action) {
+                return Err(IllegalAction(action));
+            }
+
+            let seat = &mut seats[i];
+            match action {
+                Action::Hit => seat.hand.push(self.shoe.draw()),
+                Action::Stand => return Ok(()),
+                Action::Double => {
+                    seat.bet *= 2.0;
+                    seat.hand.push(self.shoe.draw());
+                    return Ok(());
+                }
+                Action::Surrender => {
+                    seat.surrendered = true;
+                    return Ok(());
+                }
+                Action::Split => {
+                    let card = seat.hand.cards.pop().expect("a pair has two cards");
+                    seat.split = true;
+                    let split = Seat {
+                        hand: Hand::from(vec![card]),
+                        bet: seat.bet,
+                        split: true,
+                        surrendered: false,
+                    };
+                    seats.insert(i + 1, split);
+                }
+            }
+        }
+    }
+}
+
+/// The results of a simulation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Report {
+    /// The number of rounds played.
+    pub rounds: u64,
+    /// The number of hands played, which is more than the number of rounds because of splits.
+    pub hands: u64,
+    /// The total amount bet, including doubles and splits.
+    pub wagered: f64,
+    /// How much the player won (or lost, if negative) in total.
+    pub net: f64,
+}
+
+impl Report {
+    /// Returns the house edge: the fraction of the initial bet the player loses per round on
+    /// average. It is negative if the player came out ahead.
+    pub fn house_edge(&self) -> f64 {
+        -self.net / self.rounds as f64
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Rounds:     {}", self.rounds)?;
+        writeln!(f, "Hands:      {}", self.hands)?;
+        writeln!(f, "Wagered:    {:.1}", self.wagered)?;
+        writeln!(f, "Net:        {:.1}", self.net)?;
+        write!(f, "House edge: {:.3}%", 100.0 * self.house_edge())
+    }
+}
+
+/// Plays `rounds` rounds at a table with the given rules, with a shoe shuffled by an RNG seeded
+/// with `seed`, and reports how the strategy did.
+///
+/// Simulating the same rules and strategy with the same seed always gives the same results, and
+/// since different rules with the same seed deal mostly the same cards, comparing them needs far
+/// fewer rounds than comparing them with different seeds.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::blackjack::{BasicStrategy, Rules, simulate};
+/// #
+/// let rules = Rules::default();
+/// let report = simulate(rules, &mut BasicStrategy::new(rules), 10_000, 42).unwrap();
+///
+/// assert_eq!(report.rounds, 10_000);
+/// assert!(report.hands >= 10_000);
+/// assert!(report.house_edge().abs() < 0.1);
+/// ```
+pub fn simulate(
+    rules: Rules,
+    strategy: &mut dyn Strategy,
+    rounds: u64,
+    seed: u64,
+) -> Result<Report, IllegalAction> {
+    let mut table = Table::new(rules, StdRng::seed_from_u64(seed));
+    let mut report = Report {
+        rounds,
+        hands: 0,
+        wagered: 0.0,
+        net: 0.0,
+    };
+
+    for _ in 0..rounds {
+        let round = table.play_round(strategy)?;
+        report.hands += round.hands.len() as u64;
+        report.wagered += round.wagered();
+        report.net += round.net();
+    }
+
+    Ok(report)
+}
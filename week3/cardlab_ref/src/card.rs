@@ -13,6 +13,9 @@
 //! If you would like to use derived traits, you are allowed to. However, we felt that it would be
 //! more instructive to manually write out the implementations in the reference solution.
 
+use std::fmt::Display;
+use std::str::FromStr;
+
 /// Represents a standard playing card with a suit and a rank.
 ///
 /// Each card consists of one of the four suits (Diamond, Club, Heart, Spade), as well as a rank
@@ -44,6 +47,7 @@
 /// // Test equality between identical cards.
 /// assert!(ace_spades == Card::new("spade", 14));
 /// ```
+#[derive(Debug, Clone, Copy)]
 pub struct Card {
     rank: Rank,
     suit: Suit,
@@ -54,12 +58,13 @@ impl Card {
     ///
     /// # Parameters
     ///
-    /// * `suit` - A string representing the suit:
+    /// * `suit` - A string representing the suit, in any of the spellings accepted by [`Suit`]'s
+    ///   [`FromStr`] implementation, such as:
     ///   * `"diamond"`
     ///   * `"club"`
     ///   * `"heart"`
     ///   * `"spade"`
-    /// * `rank` - A number from 1-13 representing the rank:
+    /// * `rank` - A number from 2-14 representing the rank:
     ///   * 2-10: Number cards
     ///   * 11: Jack
     ///   * 12: Queen
@@ -68,7 +73,8 @@ impl Card {
     ///
     /// # Panics
     ///
-    /// * If suit is not one of: `"spade"`, `"heart"`, `"club"`, `"diamond"`.
+    /// * If suit is not the name of a suit. See [`Card::try_new`] for a version that does not
+    ///   panic.
     /// * If rank is not in the range [2, 14].
     ///
     /// # Examples
@@ -81,37 +87,65 @@ impl Card {
     /// let three_of_clubs = Card::new("club", 3);
     /// ```
     pub fn new(suit: &str, rank: u8) -> Self {
-        // Convert the input `suit` integer into a `Suit` enum.
+        match Self::try_new(suit, rank) {
+            Ok(card) => card,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Creates a new Card instance from a suit name and numeric rank, just like [`Card::new`], but
+    /// returns a [`CardError`] instead of panicking if either of them is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cardlab_ref::{Card, CardError};
+    /// #
+    /// assert!(Card::try_new("spades", 14).is_ok());
+    /// assert_eq!(
+    ///     Card::try_new("swords", 14).err(),
+    ///     Some(CardError::UnknownSuit("swords".to_string()))
+    /// );
+    /// assert_eq!(Card::try_new("spade", 1).err(), Some(CardError::RankOutOfRange(1)));
+    /// ```
+    pub fn try_new(suit: &str, rank: u8) -> Result<Self, CardError> {
+        // Convert the input `suit` string into a `Suit` enum.
         // Note that this new `suit` "shadows" the input `suit`, becoming a new type.
-        let suit = match suit {
-            "diamond" => Suit::Diamond,
-            "club" => Suit::Club,
-            "heart" => Suit::Heart,
-            "spade" => Suit::Spade,
-            s => panic!("invalid suit {s}"),
-        };
+        let suit: Suit = suit.parse()?;
 
         // Convert the input `rank` integer into a `Rank` enum.
-        let rank = match rank {
-            2 => Rank::Number(Number::Two),
-            3 => Rank::Number(Number::Three),
-            4 => Rank::Number(Number::Four),
-            5 => Rank::Number(Number::Five),
-            6 => Rank::Number(Number::Six),
-            7 => Rank::Number(Number::Seven),
-            8 => Rank::Number(Number::Eight),
-            9 => Rank::Number(Number::Nine),
-            10 => Rank::Number(Number::Ten),
-            11 => Rank::Face(Face::Jack),
-            12 => Rank::Face(Face::Queen),
-            13 => Rank::Face(Face::King),
-            14 => Rank::Face(Face::Ace),
-            n => panic!("invalid rank number {n}, expected a rank in the range [2, 14]"),
-        };
+        let rank = Rank::try_from(rank)?;
 
+        Ok(Card { suit, rank })
+    }
+
+    /// Creates a new Card instance from a [`Suit`] and a [`Rank`], which can never fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cardlab_ref::{Card, Face, Number, Rank, Suit};
+    /// #
+    /// let ace_of_spades = Card::from_parts(Suit::Spade, Rank::Face(Face::Ace));
+    /// assert!(ace_of_spades == Card::new("spade", 14));
+    ///
+    /// let two_of_hearts = Card::from_parts(Suit::Heart, Rank::Number(Number::Two));
+    /// assert!(two_of_hearts == Card::new("heart", 2));
+    /// ```
+    pub fn from_parts(suit: Suit, rank: Rank) -> Self {
         Card { suit, rank }
     }
 
+    /// Returns this card's suit.
+    pub fn suit(&self) -> Suit {
+        self.suit
+    }
+
+    /// Returns this card's rank.
+    pub fn rank(&self) -> Rank {
+        self.rank
+    }
+
     /// Returns a string representation of this card's suit.
     ///
     /// # Examples
@@ -184,6 +218,91 @@ impl PartialOrd for Card {
     }
 }
 
+/// Creates a [`Card`] from a suit name and numeric rank. See [`Card::try_new`].
+impl TryFrom<(&str, u8)> for Card {
+    type Error = CardError;
+
+    fn try_from((suit, rank): (&str, u8)) -> Result<Self, Self::Error> {
+        Self::try_new(suit, rank)
+    }
+}
+
+/// Parses a [`Card`] written as `"<rank> of <suit>"`, as `"<rank> <suit>"`, or as a rank
+/// immediately followed by a suit, ignoring case. See the [`FromStr`] implementations of [`Rank`]
+/// and [`Suit`] for every way of writing them.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::Card;
+/// #
+/// let ace_of_spades = Card::new("spade", 14);
+///
+/// for s in ["Ace of Spades", "14 of spade", "A♠", "AS", "as", "a spades"] {
+///     assert!(s.parse::<Card>().unwrap() == ace_of_spades);
+/// }
+/// assert!("10♥".parse::<Card>().unwrap() == Card::new("heart", 10));
+/// assert!("Td".parse::<Card>().unwrap() == Card::new("diamond", 10));
+/// ```
+impl FromStr for Card {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || CardError::InvalidFormat(s.to_string());
+
+        // The long form, with the rank and suit separated by whitespace (and maybe "of").
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let (rank, suit) = match words[..] {
+            [rank, suit] => (rank, suit),
+            [rank, of, suit] if of.eq_ignore_ascii_case("of") => (rank, suit),
+            // The short form, where the suit is the last character.
+            [short] => {
+                let (split, _) = short.char_indices().last().ok_or_else(invalid)?;
+                if split == 0 {
+                    return Err(invalid());
+                }
+                short.split_at(split)
+            }
+            _ => return Err(invalid()),
+        };
+
+        Ok(Card {
+            rank: rank.parse()?,
+            suit: suit.parse()?,
+        })
+    }
+}
+
+/// An error that can occur when creating a [`Card`] from user input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardError {
+    /// The suit is not the name of a suit.
+    UnknownSuit(String),
+    /// The rank is a number that is not in the range [2, 14].
+    RankOutOfRange(u8),
+    /// The rank is not a number or the name of a rank.
+    UnknownRank(String),
+    /// The string is not written like a card at all.
+    InvalidFormat(String),
+}
+
+impl Display for CardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownSuit(suit) => write!(f, "invalid suit {suit}"),
+            Self::RankOutOfRange(n) => write!(
+                f,
+                "invalid rank number {n}, expected a rank in the range [2, 14]"
+            ),
+            Self::UnknownRank(rank) => write!(f, "invalid rank {rank}"),
+            Self::InvalidFormat(s) => write!(f, "invalid card {s}"),
+        }
+    }
+}
+
+impl std::error::Error for CardError {}
+
 /// Represents the four possible suits in a standard deck of playing cards.
 ///
 /// The suits are ordered in the traditional manner:
@@ -201,7 +320,8 @@ impl PartialOrd for Card {
 /// let diamond_card = Card::new("diamond", 2);
 /// assert!(spade_card > diamond_card);
 /// ```
-enum Suit {
+#[derive(Debug, Clone, Copy)]
+pub enum Suit {
     /// The Diamond suit, typically represented by a red ♦ symbol.
     Diamond,
     /// The Club suit, typically represented by a black ♣ symbol.
@@ -214,7 +334,7 @@ enum Suit {
 
 impl Suit {
     /// Gives unique values to the suits to make ordering of suits easier.
-    fn suit_value(&self) -> u8 {
+    pub(crate) fn suit_value(&self) -> u8 {
         match self {
             Self::Diamond => 0,
             Self::Club => 1,
@@ -224,6 +344,35 @@ impl Suit {
     }
 }
 
+/// Parses a suit, ignoring case. Every suit can be written as its name (`"spade"`), the plural of
+/// its name (`"spades"`), its symbol (`"♠"` or `"♤"`), or its first letter (`"S"`).
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::Suit;
+/// #
+/// for s in ["spade", "Spades", "♠", "S", "s"] {
+///     assert!(matches!(s.parse::<Suit>(), Ok(Suit::Spade)));
+/// }
+/// assert!("swords".parse::<Suit>().is_err());
+/// ```
+impl FromStr for Suit {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let suit = match s.trim().to_lowercase().as_str() {
+            "diamond" | "diamonds" | "♦" | "♢" | "d" => Self::Diamond,
+            "club" | "clubs" | "♣" | "♧" | "c" => Self::Club,
+            "heart" | "hearts" | "♥" | "♡" | "h" => Self::Heart,
+            "spade" | "spades" | "♠" | "♤" | "s" => Self::Spade,
+            _ => return Err(CardError::UnknownSuit(s.to_string())),
+        };
+
+        Ok(suit)
+    }
+}
+
 /// Represents the rank of a playing card, which can be either a number card (2-10) or a face card
 /// (Ace, King, Queen, Jack).
 ///
@@ -244,7 +393,8 @@ impl Suit {
 /// let ace_clubs = Card::new("club", 14);
 /// assert!(ace_clubs > king_diamonds);
 /// ```
-enum Rank {
+#[derive(Debug, Clone, Copy)]
+pub enum Rank {
     /// A number card (2-10).
     Number(Number),
     /// A face card (Ace, King, Queen, Jack).
@@ -253,7 +403,7 @@ enum Rank {
 
 impl Rank {
     /// Converts a rank to its numeric value (2-14), where the face cards are 11-14.
-    fn rank_value(&self) -> u8 {
+    pub(crate) fn rank_value(&self) -> u8 {
         match self {
             Self::Number(number) => number.number_value(),
             Self::Face(face) => face.face_value(),
@@ -261,6 +411,74 @@ impl Rank {
     }
 }
 
+/// Converts a numeric rank (2-10 for number cards, and 11-14 for Jack, Queen, King and Ace) into a
+/// [`Rank`].
+impl TryFrom<u8> for Rank {
+    type Error = CardError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let rank = match value {
+            2 => Self::Number(Number::Two),
+            3 => Self::Number(Number::Three),
+            4 => Self::Number(Number::Four),
+            5 => Self::Number(Number::Five),
+            6 => Self::Number(Number::Six),
+            7 => Self::Number(Number::Seven),
+            8 => Self::Number(Number::Eight),
+            9 => Self::Number(Number::Nine),
+            10 => Self::Number(Number::Ten),
+            11 => Self::Face(Face::Jack),
+            12 => Self::Face(Face::Queen),
+            13 => Self::Face(Face::King),
+            14 => Self::Face(Face::Ace),
+            n => return Err(CardError::RankOutOfRange(n)),
+        };
+
+        Ok(rank)
+    }
+}
+
+/// Parses a rank, ignoring case. Ranks can be written as their number (`"2"` to `"14"`), their
+/// name (`"two"`, `"queen"`), or their usual letter (`"T"` for Ten, `"J"`, `"Q"`, `"K"` and
+/// `"A"`).
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::{Face, Rank};
+/// #
+/// for s in ["12", "queen", "Q", "q"] {
+///     assert!(matches!(s.parse::<Rank>(), Ok(Rank::Face(Face::Queen))));
+/// }
+/// assert!("1".parse::<Rank>().is_err());
+/// ```
+impl FromStr for Rank {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = match s.trim().to_lowercase().as_str() {
+            "two" => 2,
+            "three" => 3,
+            "four" => 4,
+            "five" => 5,
+            "six" => 6,
+            "seven" => 7,
+            "eight" => 8,
+            "nine" => 9,
+            "ten" | "t" => 10,
+            "jack" | "j" => 11,
+            "queen" | "q" => 12,
+            "king" | "k" => 13,
+            "ace" | "a" => 14,
+            number => number
+                .parse()
+                .map_err(|_| CardError::UnknownRank(s.to_string()))?,
+        };
+
+        Self::try_from(value)
+    }
+}
+
 /// Represents the possible numbers for cards in a deck.
 ///
 /// # Examples
@@ -272,7 +490,8 @@ impl Rank {
 /// let seven_diamonds = Card::new("diamond", 7);
 /// assert!(two_hearts < seven_diamonds);
 /// ```
-enum Number {
+#[derive(Debug, Clone, Copy)]
+pub enum Number {
     Two,
     Three,
     Four,
@@ -319,7 +538,8 @@ impl Number {
 /// let king_diamonds = Card::new("diamond", 13);
 /// assert!(ace_spades > king_diamonds);
 /// ```
-enum Face {
+#[derive(Debug, Clone, Copy)]
+pub enum Face {
     /// The Jack card, traditionally representing a royal servant or knight.
     Jack,
     /// The Queen card, traditionally representing a female royal figure.
@@ -1,7 +1,12 @@
 #![doc = include_str!("../README.md")]
 
 mod card;
-pub use card::Card;
+pub use card::{Card, CardError, Face, Number, Rank, Suit};
+
+pub mod blackjack;
+pub mod ordering;
+pub mod packed;
+pub mod trick;
 
 #[cfg(test)]
 mod tests;
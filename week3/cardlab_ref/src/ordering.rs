@@ -0,0 +1,272 @@
+//! This module contains [`CardOrdering`], which describes a way of ordering [`Card`]s other than
+//! their [`Ord`] implementation.
+//!
+//! [`Card`]'s [`Ord`] implementation compares cards by rank, and then by suit (Diamond < Club <
+//! Heart < Spade). That is only right for some games: bridge ranks the suits differently (Club <
+//! Diamond < Heart < Spade), poker does not rank suits at all, some games treat Aces as low cards,
+//! and in trick-taking games, which card wins depends on the suit that was led and on the trump
+//! suit.
+//!
+//! Instead of picking one of these, every ordering is a value that implements [`CardOrdering`],
+//! which can then be passed to [`CardOrdering::sort`], [`CardOrdering::max`], and so on. The
+//! presets are:
+//!
+//! - [`Ranking::STANDARD`]: the same order as [`Card`]'s [`Ord`] implementation.
+//! - [`Ranking::POKER`]: by rank only.
+//! - [`Ranking::BRIDGE`]: by suit (Club < Diamond < Heart < Spade), and then by rank, which is how
+//!   a bridge hand is usually sorted.
+//! - [`Trick`]: which card wins a trick, given the suit that was led and an optional trump suit.
+//!
+//! Any closure that compares two cards is also a [`CardOrdering`].
+
+use crate::{Card, Suit};
+use std::cmp::Ordering;
+
+/// A way of ordering [`Card`]s.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::Card;
+/// # use cardlab_ref::ordering::{CardOrdering, Ranking};
+/// #
+/// let mut cards = [Card::new("spade", 2), Card::new("diamond", 14), Card::new("club", 9)];
+///
+/// // Sort by suit first, in bridge order.
+/// Ranking::BRIDGE.sort(&mut cards);
+/// assert!(cards == [Card::new("club", 9), Card::new("diamond", 14), Card::new("spade", 2)]);
+///
+/// // Aces are the lowest card.
+/// let ace_low = Ranking::STANDARD.ace_low();
+/// assert!(ace_low.min(&cards).unwrap() == &Card::new("diamond", 14));
+/// ```
+pub trait CardOrdering {
+    /// Compares two cards.
+    fn compare(&self, a: &Card, b: &Card) -> Ordering;
+
+    /// Sorts cards from lowest to highest. The sort is stable, so cards that compare as equal keep
+    /// their order.
+    fn sort(&self, cards: &mut [Card]) {
+        cards.sort_by(|a, b| self.compare(a, b));
+    }
+
+    /// Returns the highest card, or [`None`] if there are no cards. If several cards are equally
+    /// high, the last one is returned.
+    fn max<'a>(&self, cards: &'a [Card]) -> Option<&'a Card> {
+        cards.iter().max_by(|a, b| self.compare(a, b))
+    }
+
+    /// Returns the lowest card, or [`None`] if there are no cards. If several cards are equally
+    /// low, the first one is returned.
+    fn min<'a>(&self, cards: &'a [Card]) -> Option<&'a Card> {
+        cards.iter().min_by(|a, b| self.compare(a, b))
+    }
+}
+
+/// Any closure that compares two cards can be used as a [`CardOrdering`].
+impl<F> CardOrdering for F
+where
+    F: Fn(&Card, &Card) -> Ordering,
+{
+    fn compare(&self, a: &Card, b: &Card) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// Orders cards by their rank and (optionally) their suit.
+///
+/// Start from one of the presets, and adjust it with [`Ranking::ace_low`],
+/// [`Ranking::suit_first`] or [`Ranking::suits`].
+#[derive(Debug, Clone, Copy)]
+pub struct Ranking {
+    /// The suits from lowest to highest, or [`None`] if suits do not matter.
+    suits: Option<[Suit; 4]>,
+    /// Whether Aces are below Twos instead of above Kings.
+    ace_low: bool,
+    /// Whether cards are compared by suit before rank, instead of the other way around.
+    suit_first: bool,
+}
+
+impl Ranking {
+    /// Compares cards by rank, and then by suit (Diamond < Club < Heart < Spade), which is the
+    /// same as [`Card`]'s [`Ord`] implementation.
+    pub const STANDARD: Ranking = Ranking {
+        suits: Some([Suit::Diamond, Suit::Club, Suit::Heart, Suit::Spade]),
+        ace_low: false,
+        suit_first: false,
+    };
+
+    /// Compares cards by rank only, so cards of the same rank are equal.
+    pub const POKER: Ranking = Ranking {
+        suits: None,
+        ace_low: false,
+        suit_first: false,
+    };
+
+    /// Compares cards by suit (Club < Diamond < Heart < Spade), and then by rank.
+    pub const BRIDGE: Ranking = Ranking {
+        suits: Some([Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade]),
+        ace_low: false,
+        suit_first: true,
+    };
+
+    /// Returns the same ranking, but with Aces below Twos.
+    pub fn ace_low(self) -> Self {
+        Self {
+            ace_low: true,
+            ..self
+        }
+    }
+
+    /// Returns the same ranking, but comparing suits before ranks.
+    ///
+    /// If the ranking does not have a suit order, every suit is treated as equal.
+    pub fn suit_first(self) -> Self {
+        Self {
+            suit_first: true,
+            ..self
+        }
+    }
+
+    /// Returns the same ranking, but with the suits ranked from lowest to highest as given, or not
+    /// ranked at all if `suits` is [`None`].
+    ///
+    /// # Panics
+    ///
+    /// If `suits` does not contain every suit exactly once.
+    pub fn suits(self, suits: Option<[Suit; 4]>) -> Self {
+        if let Some(suits) = &suits {
+            for (i, suit) in suits.iter().enumerate() {
+                assert!(!suits[..i].contains(suit), "suit order has duplicate suits");
+            }
+        }
+
+        Self { suits, ..self }
+    }
+
+    /// Compares the ranks of two cards.
+    fn compare_ranks(&self, a: &Card, b: &Card) -> Ordering {
+        rank_value(a, self.ace_low).cmp(&rank_value(b, self.ace_low))
+    }
+
+    /// Compares the suits of two cards.
+    fn compare_suits(&self, a: &Card, b: &Card) -> Ordering {
+        match &self.suits {
+            Some(suits) => suit_index(suits, a.suit()).cmp(&suit_index(suits, b.suit())),
+            None => Ordering::Equal,
+        }
+    }
+}
+
+impl CardOrdering for Ranking {
+    fn compare(&self, a: &Card, b: &Card) -> Ordering {
+        if self.suit_first {
+            self.compare_suits(a, b)
+                .then_with(|| self.compare_ranks(a, b))
+        } else {
+            self.compare_ranks(a, b)
+                .then_with(|| self.compare_suits(a, b))
+        }
+    }
+}
+
+/// Orders the cards played to a trick, so that the highest card is the one that wins it.
+///
+/// Trumps beat every other card, and then cards of the suit that was led beat every other card.
+/// Within those two suits, the higher rank wins. Every other card can never win the trick, so they
+/// are all equal to each other (and lower than every trump or card of the led suit).
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::{Card, Suit};
+/// # use cardlab_ref::ordering::{CardOrdering, Trick};
+/// #
+/// let trick = [Card::new("heart", 4), Card::new("heart", 13), Card::new("spade", 2)];
+///
+/// // Without trumps, the highest heart wins.
+/// let no_trump = Trick::new(Suit::Heart, None);
+/// assert!(no_trump.max(&trick).unwrap() == &Card::new("heart", 13));
+///
+/// // With spades as trumps, even the Two of Spades wins.
+/// let spades = Trick::new(Suit::Heart, Some(Suit::Spade));
+/// assert!(spades.max(&trick).unwrap() == &Card::new("spade", 2));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Trick {
+    /// The suit of the first card played to the trick.
+    led: Suit,
+    /// The trump suit, if there is one.
+    trump: Option<Suit>,
+    /// Whether Aces are below Twos instead of above Kings.
+    ace_low: bool,
+}
+
+impl Trick {
+    /// Creates the ordering of a trick where `led` was the suit led, and `trump` is the trump suit
+    /// (if there is one).
+    pub fn new(led: Suit, trump: Option<Suit>) -> Self {
+        Self {
+            led,
+            trump,
+            ace_low: false,
+        }
+    }
+
+    /// Returns the same ordering, but with Aces below Twos.
+    pub fn ace_low(self) -> Self {
+        Self {
+            ace_low: true,
+            ..self
+        }
+    }
+
+    /// Returns the suit that was led.
+    pub fn led(&self) -> Suit {
+        self.led
+    }
+
+    /// Returns the trump suit, if there is one.
+    pub fn trump(&self) -> Option<Suit> {
+        self.trump
+    }
+
+    /// How strong a card is in this trick: 2 for trumps, 1 for the led suit, and 0 otherwise.
+    fn strength(&self, card: &Card) -> u8 {
+        if self.trump == Some(card.suit()) {
+            2
+        } else if card.suit() == self.led {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+impl CardOrdering for Trick {
+    fn compare(&self, a: &Card, b: &Card) -> Ordering {
+        let (strength_a, strength_b) = (self.strength(a), self.strength(b));
+        if strength_a != strength_b || strength_a == 0 {
+            return strength_a.cmp(&strength_b);
+        }
+
+        // Both cards are trumps, or both follow the led suit.
+        rank_value(a, self.ace_low).cmp(&rank_value(b, self.ace_low))
+    }
+}
+
+/// The numeric rank of a card, where Aces are 1 instead of 14 if `ace_low` is set.
+fn rank_value(card: &Card, ace_low: bool) -> u8 {
+    match card.rank_value() {
+        14 if ace_low => 1,
+        value => value,
+    }
+}
+
+/// The position of `suit` in `suits`.
+fn suit_index(suits: &[Suit; 4], suit: Suit) -> usize {
+    suits
+        .iter()
+        .position(|&s| s == suit)
+        .expect("every suit is in the suit order")
+}
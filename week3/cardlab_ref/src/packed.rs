@@ -0,0 +1,366 @@
+//! This module contains compact versions of [`Card`] and of sets of cards.
+//!
+//! [`Card`] models a card with nested enums, which makes it impossible to create an invalid card,
+//! but is not the fastest thing to work with. Once a card has been created, it can instead be
+//! stored as a [`PackedCard`], which is a single byte: the card's index from 0 to 51.
+//!
+//! Cards are numbered by rank first and then by suit (Diamond < Club < Heart < Spade), so the Two
+//! of Diamonds is 0, the Two of Clubs is 1, and the Ace of Spades is 51. This is exactly the order
+//! of [`Card`]'s [`Ord`] implementation, so comparing two indexes is the same as comparing the two
+//! cards (which is why [`PackedCard`] can simply derive its comparison traits).
+//!
+//! A [`CardSet`] is a set of cards stored as a 64-bit mask, where bit `i` is set if the card with
+//! index `i` is in the set. Inserting, removing and checking for a card, as well as taking the union
+//! or intersection of two sets, are all single bitwise operations. Since every rank takes up 4
+//! consecutive bits (one for each suit), picking out all of the cards of one rank or one suit is a
+//! single mask as well.
+
+use crate::{Card, Rank, Suit};
+use std::fmt::Debug;
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+/// The suits in the order of their indexes.
+const SUITS: [Suit; 4] = [Suit::Diamond, Suit::Club, Suit::Heart, Suit::Spade];
+
+/// A mask of all 52 cards.
+const FULL: u64 = (1 << 52) - 1;
+
+/// A mask of every Diamond. Shifting it left by a suit's index gives the mask of that suit.
+const DIAMONDS: u64 = 0x0001_1111_1111_1111;
+
+/// A [`Card`] packed into a single byte.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::Card;
+/// # use cardlab_ref::packed::PackedCard;
+/// #
+/// let ace_spades = PackedCard::from(Card::new("spade", 14));
+/// assert_eq!(ace_spades.index(), 51);
+/// assert!(Card::from(ace_spades) == Card::new("spade", 14));
+///
+/// let two_diamonds = PackedCard::from(Card::new("diamond", 2));
+/// assert!(two_diamonds < ace_spades);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackedCard(u8);
+
+impl PackedCard {
+    /// Packs a card from its suit and rank.
+    pub fn new(suit: Suit, rank: Rank) -> Self {
+        Self((rank.rank_value() - 2) * 4 + suit.suit_value())
+    }
+
+    /// Returns the card with the given index, or [`None`] if the index is not in the range
+    /// [0, 51].
+    pub fn from_index(index: u8) -> Option<Self> {
+        (index < 52).then_some(Self(index))
+    }
+
+    /// Returns the card's index, from 0 (the Two of Diamonds) to 51 (the Ace of Spades).
+    pub fn index(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the card's suit.
+    pub fn suit(self) -> Suit {
+        SUITS[(self.0 % 4) as usize]
+    }
+
+    /// Returns the card's rank.
+    pub fn rank(self) -> Rank {
+        Rank::try_from(self.rank_value()).expect("every index has a valid rank")
+    }
+
+    /// Returns the numeric rank of the card, from 2 to 14. See [`Card::rank_value`].
+    pub fn rank_value(self) -> u8 {
+        self.0 / 4 + 2
+    }
+
+    /// Returns the bit of the card in a [`CardSet`].
+    fn bit(self) -> u64 {
+        1 << self.0
+    }
+}
+
+impl From<Card> for PackedCard {
+    fn from(card: Card) -> Self {
+        Self::new(card.suit(), card.rank())
+    }
+}
+
+impl From<PackedCard> for Card {
+    fn from(card: PackedCard) -> Self {
+        Card::from_parts(card.suit(), card.rank())
+    }
+}
+
+impl Debug for PackedCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PackedCard({:?} of {:?})", self.rank(), self.suit())
+    }
+}
+
+/// A set of cards, stored as a 64-bit mask.
+///
+/// Iterating over a `CardSet` gives its cards in increasing order, which is the same order as
+/// [`Card`]'s [`Ord`] implementation.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::{Card, Suit};
+/// # use cardlab_ref::packed::CardSet;
+/// #
+/// let hand: CardSet = [
+///     Card::new("heart", 14),
+///     Card::new("spade", 14),
+///     Card::new("heart", 2),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(hand.len(), 3);
+/// assert!(hand.contains(Card::new("spade", 14).into()));
+/// assert_eq!(hand.of_suit(Suit::Heart).len(), 2);
+/// assert_eq!(hand.of_rank(14).len(), 2);
+///
+/// // The deck without the hand.
+/// let deck = CardSet::full() - hand;
+/// assert_eq!(deck.len(), 49);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Creates a set of all 52 cards.
+    pub fn full() -> Self {
+        Self(FULL)
+    }
+
+    /// Creates a set from its mask, or returns [`None`] if any bit above the 52nd is set.
+    pub fn from_bits(bits: u64) -> Option<Self> {
+        (bits & !FULL == 0).then_some(Self(bits))
+    }
+
+    /// Returns the mask of the set, where bit `i` is set if the card with index `i` is in the set.
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Adds a card to the set. Returns whether the card was not already in the set.
+    pub fn insert(&mut self, card: PackedCard) -> bool {
+        let added = self.0 & card.bit() == 0;
+        self.0 |= card.bit();
+        added
+    }
+
+    /// Removes a card from the set. Returns whether the card was in the set.
+    pub fn remove(&mut self, card: PackedCard) -> bool {
+        let removed = self.0 & card.bit() != 0;
+        self.0 &= !card.bit();
+        removed
+    }
+
+    /// Returns whether the card is in the set.
+    pub fn contains(self, card: PackedCard) -> bool {
+        self.0 & card.bit() != 0
+    }
+
+    /// Returns the number of cards in the set.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns whether the set is empty.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the cards that are in either set.
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the cards that are in both sets.
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Returns the cards that are in this set but not in `other`.
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns whether every card in this set is also in `other`.
+    pub fn is_subset(self, other: Self) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Returns the cards of the given suit.
+    pub fn of_suit(self, suit: Suit) -> Self {
+        Self(self.0 & (DIAMONDS << suit.suit_value()))
+    }
+
+    /// Returns the cards of the given numeric rank (2-14), or an empty set if `rank` is not in the
+    /// range [2, 14].
+    pub fn of_rank(self, rank: u8) -> Self {
+        match rank {
+            2..=14 => Self(self.0 & (0b1111 << ((rank - 2) * 4))),
+            _ => Self(0),
+        }
+    }
+
+    /// Returns the ranks of the cards of the given suit, as a 13-bit mask where bit `i` is set if
+    /// the card with rank `i + 2` is in the set.
+    pub fn suit_ranks(self, suit: Suit) -> u16 {
+        // Move the suit's bits down to every fourth bit, starting at bit 0, and then squeeze them
+        // together one rank at a time.
+        let spread = (self.0 >> suit.suit_value()) & DIAMONDS;
+        (0..13).fold(0, |ranks, i| {
+            ranks | (((spread >> (4 * i)) & 1) << i) as u16
+        })
+    }
+
+    /// Returns the ranks that appear in the set in any suit, in the same format as
+    /// [`CardSet::suit_ranks`].
+    pub fn ranks(self) -> u16 {
+        SUITS
+            .iter()
+            .fold(0, |ranks, &suit| ranks | self.suit_ranks(suit))
+    }
+
+    /// Returns how many cards of every rank are in the set, from Two (index 0) to Ace (index 12).
+    pub fn rank_counts(self) -> [u8; 13] {
+        std::array::from_fn(|i| ((self.0 >> (4 * i)) & 0b1111).count_ones() as u8)
+    }
+
+    /// Returns the lowest card in the set, or [`None`] if it is empty.
+    pub fn min(self) -> Option<PackedCard> {
+        (!self.is_empty()).then(|| PackedCard(self.0.trailing_zeros() as u8))
+    }
+
+    /// Returns the highest card in the set, or [`None`] if it is empty.
+    pub fn max(self) -> Option<PackedCard> {
+        (!self.is_empty()).then(|| PackedCard(63 - self.0.leading_zeros() as u8))
+    }
+
+    /// Returns an iterator over the cards in the set, from lowest to highest.
+    pub fn iter(self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+
+/// Returns every card that is not in the set.
+impl Not for CardSet {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0 & FULL)
+    }
+}
+
+impl Debug for CardSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<PackedCard> for CardSet {
+    fn from_iter<I: IntoIterator<Item = PackedCard>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        iter.into_iter().map(PackedCard::from).collect()
+    }
+}
+
+impl Extend<PackedCard> for CardSet {
+    fn extend<I: IntoIterator<Item = PackedCard>>(&mut self, iter: I) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = PackedCard;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+/// An iterator over the cards in a [`CardSet`], from lowest to highest.
+#[derive(Debug, Clone)]
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = PackedCard;
+
+    fn next(&mut self) -> Option<PackedCard> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let card = PackedCard(self.0.trailing_zeros() as u8);
+        // Clear the lowest set bit.
+        self.0 &= self.0 - 1;
+        Some(card)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    fn next_back(&mut self) -> Option<PackedCard> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let card = PackedCard(63 - self.0.leading_zeros() as u8);
+        self.0 &= !card.bit();
+        Some(card)
+    }
+}
+
+impl ExactSizeIterator for Iter {}
@@ -1,4 +1,16 @@
-use crate::Card;
+use crate::blackjack::{
+    Action, BasicStrategy, Decision, Hand, IllegalAction, Rules, Shoe, Strategy, Table, simulate,
+};
+use crate::ordering::{CardOrdering, Ranking, Trick};
+use crate::packed::{CardSet, PackedCard};
+use crate::trick::{
+    Game, PlayError, Player, RandomPlayer, Round, Scores, SpadesScore, Turn, Variant, deal,
+    hearts_points, winner,
+};
+use crate::{Card, CardError, Face, Number, Rank, Suit};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::cmp::Ordering;
 
 #[test]
 fn test_simple_card_comparisons() {
@@ -158,3 +170,1057 @@ fn test_full_deck_sorting() {
         prev_card = card;
     }
 }
+
+#[test]
+fn test_try_new() {
+    assert!(Card::try_new("spade", 14).unwrap() == Card::new("spade", 14));
+    assert!(Card::try_new("spades", 2).unwrap() == Card::new("spade", 2));
+    assert!(Card::try_new("♥", 11).unwrap() == Card::new("heart", 11));
+    assert!(Card::try_new("D", 7).unwrap() == Card::new("diamond", 7));
+
+    assert_eq!(
+        Card::try_new("swords", 14).err(),
+        Some(CardError::UnknownSuit("swords".to_string()))
+    );
+    assert_eq!(
+        Card::try_new("spade", 1).err(),
+        Some(CardError::RankOutOfRange(1))
+    );
+    assert_eq!(
+        Card::try_new("spade", 15).err(),
+        Some(CardError::RankOutOfRange(15))
+    );
+}
+
+#[test]
+fn test_try_from() {
+    assert!(Card::try_from(("club", 10)).unwrap() == Card::new("club", 10));
+    assert_eq!(
+        Card::try_from(("club", 0)).err(),
+        Some(CardError::RankOutOfRange(0))
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid suit spades!")]
+fn test_new_panics_on_unknown_suit() {
+    Card::new("spades!", 14);
+}
+
+#[test]
+#[should_panic(expected = "invalid rank number 1")]
+fn test_new_panics_on_rank_out_of_range() {
+    Card::new("spade", 1);
+}
+
+#[test]
+fn test_suit_spellings() {
+    let spellings = [
+        (Suit::Diamond, ["diamond", "diamonds", "♦", "D"]),
+        (Suit::Club, ["club", "clubs", "♣", "C"]),
+        (Suit::Heart, ["heart", "hearts", "♥", "H"]),
+        (Suit::Spade, ["spade", "spades", "♠", "S"]),
+    ];
+
+    for (suit, names) in spellings {
+        for name in names {
+            assert!(name.parse::<Suit>().unwrap() == suit);
+            assert!(name.to_uppercase().parse::<Suit>().unwrap() == suit);
+            assert!(name.to_lowercase().parse::<Suit>().unwrap() == suit);
+        }
+    }
+}
+
+#[test]
+fn test_card_from_str() {
+    let queen_hearts = Card::new("heart", 12);
+    for s in [
+        "queen of hearts",
+        "Queen of Hearts",
+        "12 of heart",
+        "Q hearts",
+        "Q♥",
+        "QH",
+        "qh",
+        "  Q♥  ",
+    ] {
+        assert!(s.parse::<Card>().unwrap() == queen_hearts, "{s}");
+    }
+
+    assert!("10♠".parse::<Card>().unwrap() == Card::new("spade", 10));
+    assert!("T♠".parse::<Card>().unwrap() == Card::new("spade", 10));
+    assert!("2c".parse::<Card>().unwrap() == Card::new("club", 2));
+}
+
+#[test]
+fn test_card_from_str_errors() {
+    let parse = |s: &str| s.parse::<Card>().err();
+
+    assert_eq!(parse(""), Some(CardError::InvalidFormat("".to_string())));
+    assert_eq!(parse("Q"), Some(CardError::InvalidFormat("Q".to_string())));
+    assert_eq!(
+        parse("queen of the hearts"),
+        Some(CardError::InvalidFormat("queen of the hearts".to_string()))
+    );
+    assert_eq!(parse("QX"), Some(CardError::UnknownSuit("X".to_string())));
+    assert_eq!(parse("1 of spades"), Some(CardError::RankOutOfRange(1)));
+    assert_eq!(
+        parse("prince of spades"),
+        Some(CardError::UnknownRank("prince".to_string()))
+    );
+}
+
+#[test]
+fn test_typed_construction() {
+    let ranks = [
+        Rank::Number(Number::Two),
+        Rank::Number(Number::Three),
+        Rank::Number(Number::Four),
+        Rank::Number(Number::Five),
+        Rank::Number(Number::Six),
+        Rank::Number(Number::Seven),
+        Rank::Number(Number::Eight),
+        Rank::Number(Number::Nine),
+        Rank::Number(Number::Ten),
+        Rank::Face(Face::Jack),
+        Rank::Face(Face::Queen),
+        Rank::Face(Face::King),
+        Rank::Face(Face::Ace),
+    ];
+
+    for (suit, name) in [
+        (Suit::Diamond, "diamond"),
+        (Suit::Club, "club"),
+        (Suit::Heart, "heart"),
+        (Suit::Spade, "spade"),
+    ] {
+        for (rank, value) in ranks.into_iter().zip(2..) {
+            let card = Card::from_parts(suit, rank);
+            assert!(card == Card::new(name, value));
+            assert!(card.suit() == suit);
+            assert!(card.rank() == rank);
+            assert!(Rank::try_from(value).unwrap() == rank);
+            assert_eq!(card.suit_name(), name);
+            assert_eq!(card.rank_value(), value);
+        }
+    }
+}
+
+/// Creates a full deck of 52 cards.
+fn full_deck() -> Vec<Card> {
+    let mut cards = Vec::new();
+    for suit in ["diamond", "club", "heart", "spade"] {
+        for rank in 2..=14 {
+            cards.push(Card::new(suit, rank));
+        }
+    }
+    cards
+}
+
+#[test]
+fn test_standard_ranking_matches_ord() {
+    let deck = full_deck();
+
+    for a in &deck {
+        for b in &deck {
+            assert_eq!(Ranking::STANDARD.compare(a, b), a.cmp(b));
+        }
+    }
+}
+
+#[test]
+fn test_poker_ranking_ignores_suits() {
+    let ace_spades = Card::new("spade", 14);
+    let ace_diamonds = Card::new("diamond", 14);
+    let king_spades = Card::new("spade", 13);
+
+    assert_eq!(
+        Ranking::POKER.compare(&ace_spades, &ace_diamonds),
+        Ordering::Equal
+    );
+    assert_eq!(
+        Ranking::POKER.compare(&ace_diamonds, &king_spades),
+        Ordering::Greater
+    );
+
+    // Sorting is stable, so equal cards keep their order.
+    let mut cards = [ace_spades, king_spades, ace_diamonds];
+    Ranking::POKER.sort(&mut cards);
+    assert!(cards == [king_spades, ace_spades, ace_diamonds]);
+}
+
+#[test]
+fn test_bridge_ranking() {
+    let mut deck = full_deck();
+    use rand::seq::SliceRandom;
+    deck.shuffle(&mut rand::thread_rng());
+
+    Ranking::BRIDGE.sort(&mut deck);
+
+    // Clubs, then Diamonds, then Hearts, then Spades, each from Two to Ace.
+    for (i, card) in deck.iter().enumerate() {
+        let suit = ["club", "diamond", "heart", "spade"][i / 13];
+        assert_eq!(card.suit_name(), suit);
+        assert_eq!(card.rank_value() as usize, i % 13 + 2);
+    }
+
+    // By rank first, but with bridge suit order.
+    let by_rank =
+        Ranking::STANDARD.suits(Some([Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade]));
+    assert_eq!(
+        by_rank.compare(&Card::new("diamond", 5), &Card::new("club", 5)),
+        Ordering::Greater
+    );
+    assert_eq!(
+        by_rank.compare(&Card::new("diamond", 2), &Card::new("club", 14)),
+        Ordering::Less
+    );
+
+    // By suit first, but with the standard suit order.
+    let by_suit = Ranking::STANDARD.suit_first();
+    assert_eq!(
+        by_suit.compare(&Card::new("club", 2), &Card::new("diamond", 14)),
+        Ordering::Greater
+    );
+}
+
+#[test]
+#[should_panic(expected = "suit order has duplicate suits")]
+fn test_ranking_with_duplicate_suits() {
+    Ranking::STANDARD.suits(Some([Suit::Club, Suit::Club, Suit::Heart, Suit::Spade]));
+}
+
+#[test]
+fn test_ace_low_ranking() {
+    let ace_low = Ranking::STANDARD.ace_low();
+    let ace_spades = Card::new("spade", 14);
+    let two_diamonds = Card::new("diamond", 2);
+    let two_spades = Card::new("spade", 2);
+
+    assert_eq!(ace_low.compare(&ace_spades, &two_diamonds), Ordering::Less);
+    assert_eq!(
+        ace_low.compare(&two_spades, &two_diamonds),
+        Ordering::Greater
+    );
+
+    let cards = full_deck();
+    assert!(ace_low.min(&cards).unwrap() == &Card::new("diamond", 14));
+    assert!(ace_low.max(&cards).unwrap() == &Card::new("spade", 13));
+}
+
+#[test]
+fn test_trick_without_trumps() {
+    let trick = Trick::new(Suit::Club, None);
+    let cards = [
+        Card::new("club", 3),
+        Card::new("spade", 14),
+        Card::new("club", 11),
+        Card::new("heart", 12),
+    ];
+
+    assert!(trick.max(&cards).unwrap() == &Card::new("club", 11));
+
+    // Neither of two off-suit cards can win, so they are equal.
+    assert_eq!(trick.compare(&cards[1], &cards[3]), Ordering::Equal);
+    assert_eq!(trick.compare(&cards[0], &cards[1]), Ordering::Greater);
+}
+
+#[test]
+fn test_trick_with_trumps() {
+    let trick = Trick::new(Suit::Club, Some(Suit::Heart));
+    let mut cards = [
+        Card::new("club", 14),
+        Card::new("heart", 2),
+        Card::new("spade", 14),
+        Card::new("heart", 10),
+        Card::new("club", 2),
+    ];
+
+    assert!(trick.max(&cards).unwrap() == &Card::new("heart", 10));
+
+    trick.sort(&mut cards);
+    let expected = [
+        Card::new("spade", 14),
+        Card::new("club", 2),
+        Card::new("club", 14),
+        Card::new("heart", 2),
+        Card::new("heart", 10),
+    ];
+    assert!(cards == expected);
+
+    // When trumps are led, they are just the led suit.
+    let trumps_led = Trick::new(Suit::Heart, Some(Suit::Heart));
+    assert!(trumps_led.max(&cards).unwrap() == &Card::new("heart", 10));
+}
+
+#[test]
+fn test_trick_ace_low() {
+    let trick = Trick::new(Suit::Diamond, None).ace_low();
+    let cards = [Card::new("diamond", 14), Card::new("diamond", 2)];
+
+    assert!(trick.max(&cards).unwrap() == &Card::new("diamond", 2));
+}
+
+#[test]
+fn test_closure_ordering() {
+    // Order by how far the rank is from Seven.
+    let distance = |a: &Card, b: &Card| {
+        let distance = |card: &Card| card.rank_value().abs_diff(7);
+        distance(a).cmp(&distance(b))
+    };
+
+    let cards = [
+        Card::new("spade", 2),
+        Card::new("heart", 8),
+        Card::new("club", 14),
+    ];
+    assert!(distance.min(&cards).unwrap() == &Card::new("heart", 8));
+    assert!(distance.max(&cards).unwrap() == &Card::new("club", 14));
+
+    // Orderings can also be used as trait objects.
+    let orderings: [&dyn CardOrdering; 3] = [&Ranking::STANDARD, &Ranking::POKER, &distance];
+    for ordering in orderings {
+        assert!(ordering.max(&cards).is_some());
+    }
+}
+
+#[test]
+fn test_packed_card_matches_card() {
+    let deck = full_deck();
+
+    for card in &deck {
+        let packed = PackedCard::from(*card);
+        assert!(Card::from(packed) == *card);
+        assert!(packed.suit() == card.suit());
+        assert!(packed.rank() == card.rank());
+        assert_eq!(packed.rank_value(), card.rank_value());
+        assert!(PackedCard::from_index(packed.index()) == Some(packed));
+        assert!(PackedCard::new(card.suit(), card.rank()) == packed);
+    }
+
+    // Packed cards are ordered exactly like cards.
+    for a in &deck {
+        for b in &deck {
+            let (packed_a, packed_b) = (PackedCard::from(*a), PackedCard::from(*b));
+            assert_eq!(packed_a.cmp(&packed_b), a.cmp(b));
+            assert_eq!(packed_a == packed_b, a == b);
+        }
+    }
+
+    // Every index is used exactly once.
+    let mut indexes: Vec<u8> = deck.iter().map(|&c| PackedCard::from(c).index()).collect();
+    indexes.sort();
+    assert_eq!(indexes, (0..52).collect::<Vec<_>>());
+    assert!(PackedCard::from_index(52).is_none());
+}
+
+#[test]
+fn test_card_set_insert_remove() {
+    let ace_spades = PackedCard::from(Card::new("spade", 14));
+    let two_clubs = PackedCard::from(Card::new("club", 2));
+
+    let mut set = CardSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert(ace_spades));
+    assert!(!set.insert(ace_spades));
+    assert!(set.insert(two_clubs));
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(ace_spades));
+
+    assert!(set.remove(ace_spades));
+    assert!(!set.remove(ace_spades));
+    assert!(!set.contains(ace_spades));
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.bits(), 1 << 1);
+}
+
+#[test]
+fn test_card_set_iterates_in_order() {
+    let mut deck = full_deck();
+    use rand::seq::SliceRandom;
+    deck.shuffle(&mut rand::thread_rng());
+
+    let set: CardSet = deck.iter().copied().collect();
+    assert!(set == CardSet::full());
+    assert_eq!(set.iter().len(), 52);
+
+    deck.sort();
+    let cards: Vec<Card> = set.iter().map(Card::from).collect();
+    assert!(cards == deck);
+
+    let reversed: Vec<Card> = set.iter().rev().map(Card::from).collect();
+    assert!(reversed.iter().rev().eq(deck.iter()));
+
+    assert!(set.min().map(Card::from) == Some(Card::new("diamond", 2)));
+    assert!(set.max().map(Card::from) == Some(Card::new("spade", 14)));
+    assert!(CardSet::new().min().is_none());
+}
+
+#[test]
+fn test_card_set_algebra() {
+    let hearts = CardSet::full().of_suit(Suit::Heart);
+    let aces = CardSet::full().of_rank(14);
+
+    assert_eq!(hearts.len(), 13);
+    assert_eq!(aces.len(), 4);
+    assert_eq!((hearts | aces).len(), 16);
+    assert_eq!(hearts.union(aces), hearts | aces);
+    assert!(
+        (hearts & aces)
+            .iter()
+            .map(Card::from)
+            .eq([Card::new("heart", 14)])
+    );
+    assert_eq!((hearts - aces).len(), 12);
+    assert_eq!((!hearts).len(), 39);
+    assert!((!hearts & hearts).is_empty());
+    assert!((hearts & aces).is_subset(hearts));
+    assert!(!hearts.is_subset(aces));
+
+    assert_eq!(CardSet::full().of_rank(1), CardSet::new());
+    assert!(CardSet::from_bits(1 << 52).is_none());
+    assert_eq!(CardSet::from_bits(0b101).map(CardSet::len), Some(2));
+}
+
+#[test]
+fn test_card_set_masks_match_cards() {
+    let deck = full_deck();
+    let suits = [Suit::Diamond, Suit::Club, Suit::Heart, Suit::Spade];
+
+    for suit in suits {
+        let set = CardSet::full().of_suit(suit);
+        let expected: Vec<&Card> = deck.iter().filter(|c| c.suit() == suit).collect();
+        assert_eq!(set.len(), expected.len());
+        assert!(set.iter().all(|card| card.suit() == suit));
+    }
+
+    for rank in 2..=14 {
+        let set = CardSet::full().of_rank(rank);
+        assert_eq!(set.len(), 4);
+        assert!(set.iter().all(|card| card.rank_value() == rank));
+    }
+}
+
+#[test]
+fn test_card_set_ranks() {
+    // (A♥ K♥ 9♥ 9♠ 2♠)
+    let hand: CardSet = ["A♥", "K♥", "9♥", "9♠", "2♠"]
+        .iter()
+        .map(|s| s.parse::<Card>().unwrap())
+        .collect();
+
+    let bit = |rank: u8| 1u16 << (rank - 2);
+    assert_eq!(hand.suit_ranks(Suit::Heart), bit(14) | bit(13) | bit(9));
+    assert_eq!(hand.suit_ranks(Suit::Spade), bit(9) | bit(2));
+    assert_eq!(hand.suit_ranks(Suit::Club), 0);
+    assert_eq!(hand.ranks(), bit(14) | bit(13) | bit(9) | bit(2));
+    assert_eq!(hand.rank_counts(), [1, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 1]);
+}
+
+/// Parses a list of cards in short form, like "Q♥".
+fn cards(cards: &[&str]) -> Vec<Card> {
+    cards.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+#[test]
+fn test_trick_winner() {
+    let trick: Vec<(usize, Card)> = cards(&["10♥", "A♥", "2♠", "A♣"])
+        .into_iter()
+        .enumerate()
+        .map(|(i, card)| ((i + 1) % 4, card))
+        .collect();
+
+    assert_eq!(winner(&trick, None), Some(2));
+    assert_eq!(winner(&trick, Some(Suit::Spade)), Some(3));
+    assert_eq!(winner(&trick, Some(Suit::Club)), Some(0));
+    assert_eq!(winner(&trick[..1], Some(Suit::Spade)), Some(1));
+    assert_eq!(winner(&[], None), None);
+}
+
+#[test]
+fn test_deal() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let hands = deal(&mut rng);
+
+    let mut all = CardSet::new();
+    for hand in &hands {
+        assert_eq!(hand.len(), 13);
+        assert!(hand.windows(2).all(|w| w[0] < w[1]));
+        all.extend(hand.iter().map(|&card| PackedCard::from(card)));
+    }
+    assert_eq!(all, CardSet::full());
+}
+
+#[test]
+fn test_hearts_first_trick_rules() {
+    let hands = [
+        cards(&[
+            "3♣", "4♣", "5♣", "6♣", "7♣", "8♣", "9♣", "10♣", "J♣", "Q♣", "K♣", "A♣", "A♦",
+        ]),
+        cards(&[
+            "2♣", "2♦", "3♦", "4♦", "5♦", "6♦", "7♦", "8♦", "9♦", "10♦", "J♦", "Q♦", "K♦",
+        ]),
+        cards(&[
+            "2♥", "3♥", "4♥", "5♥", "6♥", "7♥", "8♥", "9♥", "10♥", "J♥", "Q♥", "K♥", "Q♠",
+        ]),
+        cards(&[
+            "A♥", "2♠", "3♠", "4♠", "5♠", "6♠", "7♠", "8♠", "9♠", "10♠", "J♠", "K♠", "A♠",
+        ]),
+    ];
+    let mut round = Round::new(Variant::Hearts, hands, 0);
+
+    // The Two of Clubs leads, no matter who was supposed to lead.
+    assert_eq!(round.to_act(), 1);
+    assert!(round.legal_plays() == cards(&["2♣"]));
+    assert_eq!(
+        round.play(Card::new("diamond", 2)),
+        Err(PlayError::Illegal(Card::new("diamond", 2)))
+    );
+    assert_eq!(
+        round.play(Card::new("club", 3)),
+        Err(PlayError::NotInHand(Card::new("club", 3)))
+    );
+    assert_eq!(round.play(Card::new("club", 2)), Ok(None));
+
+    // Seat 2 can't follow suit, and only has points, so anything goes.
+    assert_eq!(round.to_act(), 2);
+    assert_eq!(round.legal_plays().len(), 13);
+    assert_eq!(round.play("Q♠".parse().unwrap()), Ok(None));
+
+    // Seat 3 can't follow suit, so they have to avoid the Ace of Hearts.
+    assert!(
+        round.legal_plays()
+            == cards(&[
+                "2♠", "3♠", "4♠", "5♠", "6♠", "7♠", "8♠", "9♠", "10♠", "J♠", "K♠", "A♠"
+            ])
+    );
+    assert_eq!(round.play("2♠".parse().unwrap()), Ok(None));
+
+    // Seat 0 has to follow suit.
+    assert_eq!(round.legal_plays().len(), 12);
+    assert_eq!(round.play("A♣".parse().unwrap()), Ok(Some(0)));
+
+    assert_eq!(round.to_act(), 0);
+    assert_eq!(hearts_points(round.taken(0)), 13);
+    assert_eq!(round.tricks_won(), [1, 0, 0, 0]);
+
+    // Hearts aren't broken, but seat 0 has none anyway.
+    assert_eq!(round.legal_plays().len(), 12);
+}
+
+#[test]
+fn test_hearts_cannot_lead_unbroken_hearts() {
+    let hands = [
+        cards(&[
+            "2♣", "2♥", "3♥", "4♥", "5♥", "6♥", "7♥", "8♥", "9♥", "10♥", "J♥", "Q♥", "K♥",
+        ]),
+        cards(&[
+            "3♣", "4♣", "5♣", "6♣", "7♣", "8♣", "9♣", "10♣", "J♣", "Q♣", "K♣", "A♣", "A♥",
+        ]),
+        cards(&[
+            "2♦", "3♦", "4♦", "5♦", "6♦", "7♦", "8♦", "9♦", "10♦", "J♦", "Q♦", "K♦", "A♦",
+        ]),
+        cards(&[
+            "2♠", "3♠", "4♠", "5♠", "6♠", "7♠", "8♠", "9♠", "10♠", "J♠", "Q♠", "K♠", "A♠",
+        ]),
+    ];
+    let mut round = Round::new(Variant::Hearts, hands, 0);
+    for card in cards(&["2♣", "A♣", "2♦", "2♠"]) {
+        round.play(card).unwrap();
+    }
+
+    // Seat 1 won the trick and can't lead their only Heart yet.
+    assert_eq!(round.to_act(), 1);
+    assert!(!round.legal_plays().contains(&Card::new("heart", 14)));
+    assert_eq!(round.legal_plays().len(), 11);
+    for card in cards(&["3♣", "3♦", "3♠", "2♥"]) {
+        round.play(card).unwrap();
+    }
+
+    // Hearts are broken now.
+    assert_eq!(round.to_act(), 1);
+    assert!(round.legal_plays().contains(&Card::new("heart", 14)));
+}
+
+#[test]
+fn test_spades_cannot_lead_unbroken_spades() {
+    let hands = [
+        cards(&[
+            "2♠", "3♠", "4♠", "5♠", "6♠", "7♠", "8♠", "9♠", "10♠", "J♠", "Q♠", "K♠", "2♣",
+        ]),
+        cards(&[
+            "3♣", "4♣", "5♣", "6♣", "7♣", "8♣", "9♣", "10♣", "J♣", "Q♣", "K♣", "A♣", "A♠",
+        ]),
+        cards(&[
+            "2♦", "3♦", "4♦", "5♦", "6♦", "7♦", "8♦", "9♦", "10♦", "J♦", "Q♦", "K♦", "A♦",
+        ]),
+        cards(&[
+            "2♥", "3♥", "4♥", "5♥", "6♥", "7♥", "8♥", "9♥", "10♥", "J♥", "Q♥", "K♥", "A♥",
+        ]),
+    ];
+    let mut round = Round::new(Variant::Spades, hands, 0);
+
+    assert_eq!(round.to_act(), 0);
+    assert!(round.legal_plays() == cards(&["2♣"]));
+    round.play(Card::new("club", 2)).unwrap();
+    round.play(Card::new("club", 3)).unwrap();
+    round.play(Card::new("diamond", 2)).unwrap();
+    assert_eq!(round.play(Card::new("heart", 2)), Ok(Some(1)));
+
+    // Seat 1 can't lead the Ace of Spades, and seat 0 is left with only Spades.
+    assert_eq!(round.legal_plays().len(), 11);
+    round.play(Card::new("club", 4)).unwrap();
+    round.play(Card::new("diamond", 3)).unwrap();
+    round.play(Card::new("heart", 3)).unwrap();
+    assert_eq!(round.play(Card::new("spade", 2)), Ok(Some(0)));
+
+    // The Two of Spades broke Spades, and the trump won the trick.
+    assert_eq!(round.tricks_won(), [1, 1, 0, 0]);
+    assert_eq!(round.legal_plays().len(), 11);
+}
+
+#[test]
+fn test_hearts_shooting_the_moon() {
+    let hands = [
+        cards(&[
+            "2♥", "3♥", "4♥", "5♥", "6♥", "7♥", "8♥", "9♥", "10♥", "J♥", "Q♥", "K♥", "A♥",
+        ]),
+        cards(&[
+            "2♣", "3♣", "4♣", "5♣", "6♣", "7♣", "8♣", "9♣", "10♣", "J♣", "Q♣", "K♣", "A♣",
+        ]),
+        cards(&[
+            "2♦", "3♦", "4♦", "5♦", "6♦", "7♦", "8♦", "9♦", "10♦", "J♦", "Q♦", "K♦", "A♦",
+        ]),
+        cards(&[
+            "2♠", "3♠", "4♠", "5♠", "6♠", "7♠", "8♠", "9♠", "10♠", "J♠", "Q♠", "K♠", "A♠",
+        ]),
+    ];
+    let mut round = Round::new(Variant::Hearts, hands, 0);
+    while !round.is_over() {
+        round.play(round.legal_plays()[0]).unwrap();
+    }
+
+    // Seat 1 takes every trick with their Clubs.
+    assert_eq!(round.tricks_won(), [0, 13, 0, 0]);
+    assert_eq!(hearts_points(round.taken(1)), 26);
+    assert_eq!(round.hearts_scores(), [26, 0, 26, 26]);
+    assert_eq!(round.play(Card::new("club", 2)), Err(PlayError::RoundOver));
+    assert!(round.legal_plays().is_empty());
+}
+
+#[test]
+fn test_spades_scoring() {
+    let mut score = SpadesScore::default();
+
+    // Made the contract exactly.
+    score.add_round([3, 4], [3, 4]);
+    assert_eq!((score.points(), score.bags()), (70, 0));
+
+    // Set: took fewer tricks than the contract.
+    score.add_round([5, 4], [2, 6]);
+    assert_eq!((score.points(), score.bags()), (-20, 0));
+
+    // 9 bags, and then 2 more make 10 and cost 100 points.
+    score.add_round([1, 1], [6, 5]);
+    assert_eq!((score.points(), score.bags()), (9, 9));
+    score.add_round([2, 1], [2, 3]);
+    assert_eq!((score.points(), score.bags()), (9 + 30 + 2 - 100, 1));
+
+    // A successful nil, and a failed one whose tricks count as bags.
+    let mut score = SpadesScore::default();
+    score.add_round([0, 4], [0, 4]);
+    assert_eq!((score.points(), score.bags()), (140, 0));
+    score.add_round([0, 4], [2, 4]);
+    assert_eq!((score.points(), score.bags()), (140 - 100 + 40 + 2, 2));
+
+    // Both partners bid nil, so there is no contract.
+    let mut score = SpadesScore::default();
+    score.add_round([0, 0], [0, 3]);
+    assert_eq!((score.points(), score.bags()), (3, 3));
+}
+
+/// Four random players with different seeds.
+fn random_players<'a>(seed: u64) -> [Box<dyn Player + 'a>; 4] {
+    std::array::from_fn(|seat| {
+        Box::new(RandomPlayer::new(StdRng::seed_from_u64(seed + seat as u64))) as _
+    })
+}
+
+#[test]
+fn test_random_hearts_games() {
+    for seed in 0..10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::new(Variant::Hearts, random_players(seed * 4));
+        let mut expected = [0; 4];
+
+        while game.winners().is_none() {
+            let round = game.play_round(&mut rng).unwrap();
+            assert!(round.is_over());
+            assert_eq!(round.completed().len(), 13);
+            assert_eq!(round.tricks_won().iter().sum::<usize>(), 13);
+            assert!((0..4).all(|seat| round.hand(seat).is_empty()));
+
+            let scores = round.hearts_scores();
+            let total: u32 = scores.iter().sum();
+            assert!(total == 26 || (total == 78 && scores.contains(&0)));
+            for (expected, score) in expected.iter_mut().zip(scores) {
+                *expected += score;
+            }
+            assert_eq!(game.scores(), Scores::Hearts(expected));
+        }
+
+        let winners = game.winners().unwrap();
+        assert!(expected.iter().any(|&score| score >= 100));
+        let lowest = *expected.iter().min().unwrap();
+        assert!(winners.iter().all(|&seat| expected[seat] == lowest));
+    }
+}
+
+#[test]
+fn test_random_spades_games() {
+    for seed in 0..10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::new(Variant::Spades, random_players(seed * 4));
+        let winners = game.play(&mut rng).unwrap();
+
+        let Scores::Spades(teams) = game.scores() else {
+            panic!("a game of Spades has Spades scores");
+        };
+        let team = winners[0];
+        assert!(winners == [team, team + 2]);
+        assert!(teams[team].points() >= 500 || teams[1 - team].points() <= -200);
+        assert!(teams[team].points() > teams[1 - team].points());
+        assert!(teams.iter().all(|score| score.bags() < 10));
+        assert!(game.rounds() > 0);
+    }
+}
+
+/// Plays randomly, but always bids every trick, so their team can never make its contract.
+struct Overbidder(RandomPlayer<StdRng>);
+
+impl Player for Overbidder {
+    fn bid(&mut self, _seat: usize, _hand: &[Card]) -> u8 {
+        13
+    }
+
+    fn play(&mut self, turn: &Turn) -> Card {
+        self.0.play(turn)
+    }
+}
+
+#[test]
+fn test_spades_games_always_end() {
+    let overbidder = |seed| Box::new(Overbidder(RandomPlayer::new(StdRng::seed_from_u64(seed))));
+    let mut rng = StdRng::seed_from_u64(0);
+
+    // One team overbids and falls below the losing score after a single round.
+    let players: [Box<dyn Player>; 4] = [
+        overbidder(0),
+        Box::new(RandomPlayer::new(StdRng::seed_from_u64(1))),
+        overbidder(2),
+        Box::new(RandomPlayer::new(StdRng::seed_from_u64(3))),
+    ];
+    let mut game = Game::new(Variant::Spades, players);
+    assert_eq!(game.play(&mut rng).unwrap(), [1, 3]);
+    assert_eq!(game.rounds(), 1);
+
+    // Both teams are set by the same amount every round, so they stay tied until the round limit.
+    let players: [Box<dyn Player>; 4] = std::array::from_fn(|seat| overbidder(seat as u64) as _);
+    let mut game = Game::new(Variant::Spades, players);
+    assert_eq!(game.play(&mut rng).unwrap(), [0, 1, 2, 3]);
+    assert_eq!(game.rounds(), Game::MAX_ROUNDS);
+    let Scores::Spades(teams) = game.scores() else {
+        panic!("a game of Spades has Spades scores");
+    };
+    assert!(teams.iter().all(|score| score.points() == -260 * 100));
+}
+
+#[test]
+fn test_illegal_player_stops_the_round() {
+    /// Always plays the last card in their hand, legal or not.
+    struct Cheater;
+
+    impl Player for Cheater {
+        fn play(&mut self, turn: &Turn) -> Card {
+            *turn.hand.last().unwrap()
+        }
+    }
+
+    let players: [Box<dyn Player>; 4] = std::array::from_fn(|_| Box::new(Cheater) as _);
+    let mut game = Game::new(Variant::Hearts, players);
+    let mut rng = StdRng::seed_from_u64(0);
+
+    assert!(matches!(
+        game.play_round(&mut rng),
+        Err(PlayError::Illegal(_))
+    ));
+    assert_eq!(game.scores(), Scores::Hearts([0; 4]));
+    assert_eq!(game.rounds(), 0);
+}
+
+/// A blackjack hand of cards in short form.
+fn blackjack_hand(hand: &[&str]) -> Hand {
+    Hand::from(cards(hand))
+}
+
+#[test]
+fn test_blackjack_totals() {
+    let hand = blackjack_hand(&["K♠", "Q♥"]);
+    assert_eq!((hand.total(), hand.is_soft()), (20, false));
+    assert_eq!(hand.pair(), Some(10));
+
+    let hand = blackjack_hand(&["A♠", "A♥"]);
+    assert_eq!((hand.total(), hand.is_soft()), (12, true));
+    assert_eq!(hand.pair(), Some(1));
+
+    let hand = blackjack_hand(&["A♠", "A♥", "9♦"]);
+    assert_eq!((hand.total(), hand.is_soft()), (21, true));
+    assert!(!hand.is_blackjack());
+    assert_eq!(hand.pair(), None);
+
+    let hand = blackjack_hand(&["A♠", "5♥", "6♦", "A♣"]);
+    assert_eq!((hand.total(), hand.is_soft()), (13, false));
+
+    let hand = blackjack_hand(&["J♠", "A♥"]);
+    assert!(hand.is_blackjack());
+    assert!(!hand.is_bust());
+
+    let hand = blackjack_hand(&["J♠", "6♥", "6♦"]);
+    assert_eq!(hand.total(), 22);
+    assert!(hand.is_bust());
+}
+
+#[test]
+fn test_shoe() {
+    let mut shoe = Shoe::new(2, 0.5, StdRng::seed_from_u64(3));
+    assert_eq!(shoe.remaining(), 104);
+
+    let mut dealt: Vec<Card> = (0..52).map(|_| shoe.draw()).collect();
+    assert!(shoe.needs_shuffle());
+    dealt.extend((0..52).map(|_| shoe.draw()));
+    assert_eq!(shoe.remaining(), 0);
+
+    // Every card appears exactly twice.
+    let full_deck = full_deck();
+    assert!(
+        full_deck
+            .iter()
+            .all(|card| dealt.iter().filter(|c| *c == card).count() == 2)
+    );
+
+    // An empty shoe reshuffles itself.
+    shoe.draw();
+    assert_eq!(shoe.remaining(), 103);
+    assert!(!shoe.needs_shuffle());
+}
+
+#[test]
+#[should_panic(expected = "penetration must be in the range (0, 1]")]
+fn test_shoe_invalid_penetration() {
+    Shoe::new(6, 1.5, StdRng::seed_from_u64(0));
+}
+
+#[test]
+fn test_basic_strategy() {
+    let rules = Rules::default();
+    let mut strategy = BasicStrategy::new(rules);
+
+    let mut action =
+        |hand: &[&str], up: &str, can_double: bool, can_split: bool, can_surrender: bool| {
+            strategy.action(&Decision {
+                hand: &blackjack_hand(hand),
+                dealer_up: up.parse().unwrap(),
+                can_double,
+                can_split,
+                can_surrender,
+            })
+        };
+
+    assert_eq!(
+        action(&["10♠", "6♥"], "10♦", true, false, true),
+        Action::Surrender
+    );
+    assert_eq!(
+        action(&["10♠", "6♥"], "10♦", true, false, false),
+        Action::Hit
+    );
+    assert_eq!(
+        action(&["10♠", "6♥"], "6♦", true, false, true),
+        Action::Stand
+    );
+    assert_eq!(action(&["10♠", "2♥"], "3♦", true, false, true), Action::Hit);
+    assert_eq!(
+        action(&["10♠", "7♥"], "A♦", true, false, true),
+        Action::Stand
+    );
+    assert_eq!(
+        action(&["6♠", "5♥"], "10♦", true, false, true),
+        Action::Double
+    );
+    assert_eq!(action(&["6♠", "5♥"], "A♦", true, false, true), Action::Hit);
+    assert_eq!(
+        action(&["3♠", "2♥", "6♣"], "10♦", false, false, false),
+        Action::Hit
+    );
+
+    assert_eq!(
+        action(&["A♠", "7♥"], "4♦", true, false, true),
+        Action::Double
+    );
+    assert_eq!(
+        action(&["A♠", "7♥"], "4♦", false, false, false),
+        Action::Stand
+    );
+    assert_eq!(action(&["A♠", "7♥"], "9♦", true, false, true), Action::Hit);
+    assert_eq!(
+        action(&["A♠", "2♥"], "5♦", true, false, true),
+        Action::Double
+    );
+    assert_eq!(
+        action(&["A♠", "2♥"], "5♦", false, false, false),
+        Action::Hit
+    );
+
+    assert_eq!(
+        action(&["8♠", "8♥"], "10♦", true, true, true),
+        Action::Split
+    );
+    assert_eq!(action(&["A♠", "A♥"], "A♦", true, true, true), Action::Split);
+    assert_eq!(
+        action(&["A♠", "A♥"], "A♦", false, false, false),
+        Action::Hit
+    );
+    assert_eq!(action(&["K♠", "Q♥"], "6♦", true, true, true), Action::Stand);
+    assert_eq!(action(&["9♠", "9♥"], "7♦", true, true, true), Action::Stand);
+    assert_eq!(
+        action(&["5♠", "5♥"], "9♦", true, true, true),
+        Action::Double
+    );
+    assert_eq!(action(&["4♠", "4♥"], "5♦", true, true, true), Action::Split);
+
+    // The table changes with the rules.
+    let rules = Rules {
+        hit_soft_17: true,
+        double_after_split: false,
+        ..rules
+    };
+    let mut strategy = BasicStrategy::new(rules);
+    let mut action = |hand: &[&str], up: &str| {
+        strategy.action(&Decision {
+            hand: &blackjack_hand(hand),
+            dealer_up: up.parse().unwrap(),
+            can_double: true,
+            can_split: true,
+            can_surrender: true,
+        })
+    };
+
+    assert_eq!(action(&["6♠", "5♥"], "A♦"), Action::Double);
+    assert_eq!(action(&["10♠", "7♥"], "A♦"), Action::Surrender);
+    assert_eq!(action(&["4♠", "4♥"], "5♦"), Action::Hit);
+}
+
+/// Plays a fixed list of actions, in order.
+struct Scripted(Vec<Action>);
+
+impl Strategy for Scripted {
+    fn action(&mut self, _: &Decision) -> Action {
+        self.0.remove(0)
+    }
+}
+
+#[test]
+fn test_blackjack_illegal_action() {
+    let mut table = Table::new(Rules::default(), StdRng::seed_from_u64(5));
+
+    // Surrendering after hitting is never allowed, unless the hand was already over.
+    loop {
+        match table.play_round(&mut Scripted(vec![Action::Hit, Action::Surrender])) {
+            Err(error) => {
+                assert_eq!(error, IllegalAction(Action::Surrender));
+                break;
+            }
+            Ok(round) => {
+                assert!(round.hands[0].hand.total() >= 21 || round.hands[0].hand.cards().len() == 2)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_blackjack_rounds() {
+    let rules = Rules {
+        max_hands: 3,
+        ..Rules::default()
+    };
+    let mut table = Table::new(rules, StdRng::seed_from_u64(11));
+    let mut strategy = BasicStrategy::new(rules);
+    let mut splits = 0;
+
+    for _ in 0..2000 {
+        let round = table.play_round(&mut strategy).unwrap();
+        assert!(!round.hands.is_empty() && round.hands.len() <= 3);
+        splits += round.hands.len() - 1;
+
+        let dealer = &round.dealer;
+        let alive = round
+            .hands
+            .iter()
+            .any(|h| !h.surrendered && !h.hand.is_bust());
+        let natural = round.hands.len() == 1 && round.hands[0].hand.is_blackjack();
+        if alive && !natural && !dealer.is_blackjack() {
+            assert!(dealer.total() >= 17);
+        }
+
+        for hand in &round.hands {
+            assert!(hand.bet == 1.0 || hand.bet == 2.0);
+            if hand.surrendered {
+                assert_eq!(hand.net, -0.5);
+                assert_eq!(hand.hand.cards().len(), 2);
+            } else if hand.hand.is_bust() {
+                assert_eq!(hand.net, -hand.bet);
+            } else if hand.bet == 2.0 {
+                assert_eq!(hand.hand.cards().len(), 3);
+            }
+        }
+
+        if natural && !dealer.is_blackjack() {
+            assert_eq!(round.net(), 1.5);
+        }
+    }
+
+    assert!(splits > 0);
+}
+
+#[test]
+fn test_blackjack_simulation() {
+    let rules = Rules::default();
+    let report = simulate(rules, &mut BasicStrategy::new(rules), 100_000, 1).unwrap();
+
+    // The same seed gives the same results.
+    assert_eq!(
+        report,
+        simulate(rules, &mut BasicStrategy::new(rules), 100_000, 1).unwrap()
+    );
+    assert_eq!(report.rounds, 100_000);
+    assert!(report.hands > report.rounds);
+    assert!(report.wagered > report.hands as f64);
+    assert!(report.house_edge() > -0.01 && report.house_edge() < 0.015);
+    assert!(
+        report
+            .to_string()
+            .lines()
+            .last()
+            .unwrap()
+            .starts_with("House edge:")
+    );
+
+    // Paying 6:5 for blackjack costs the player about 1.4% of their bets.
+    let six_five = Rules {
+        blackjack_pays: 1.2,
+        ..rules
+    };
+    let worse = simulate(six_five, &mut BasicStrategy::new(six_five), 100_000, 1).unwrap();
+    let difference = worse.house_edge() - report.house_edge();
+    assert!(difference > 0.012 && difference < 0.016);
+}
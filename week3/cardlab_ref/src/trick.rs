@@ -0,0 +1,682 @@
+//! This module contains an engine for four-player trick-taking games, namely Hearts and Spades.
+//!
+//! In both games, every player is dealt 13 cards, and the game is played in 13 tricks. The player
+//! who leads a trick may play any card (with some exceptions), and every other player must then
+//! follow the suit that was led if they can. The highest card of the led suit wins the trick,
+//! unless a trump was played, in which case the highest trump wins. The winner leads the next trick.
+//!
+//! - **Hearts** has no trumps. Every Heart taken is worth 1 point and the Queen of Spades is worth
+//!   13, and the goal is to take as _few_ points as possible. The Two of Clubs leads the first
+//!   trick, nobody may play points on the first trick (unless they have nothing else), and Hearts
+//!   can't be led until a Heart has been played. A player who takes all 26 points "shoots the
+//!   moon": they score nothing, and everyone else scores 26. The game ends once somebody has 100
+//!   points, and the lowest score wins.
+//! - **Spades** always has Spades as trumps, and is played by two teams of partners sitting across
+//!   from each other (seats 0 and 2 against seats 1 and 3). Before playing, every player bids how
+//!   many tricks they expect to take. See [`SpadesScore`] for how rounds are scored. Spades can't be
+//!   led until a Spade has been played. The game ends once a team has 500 points.
+//!
+//! A [`Round`] enforces the rules of a single round, and a [`Game`] deals rounds and asks each
+//! seat's [`Player`] what to bid and play until the game is over. [`RandomPlayer`] plays random
+//! legal cards, which is useful for simulating games.
+
+use crate::Card;
+use crate::Suit;
+use crate::ordering::{CardOrdering, Trick};
+use crate::packed::CardSet;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::fmt::Display;
+
+/// The number of players at the table.
+pub const SEATS: usize = 4;
+
+/// The number of tricks in a round, which is also the number of cards dealt to every player.
+pub const TRICKS: usize = 13;
+
+/// The trick-taking games this module can play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    Hearts,
+    Spades,
+}
+
+impl Variant {
+    /// Returns the trump suit of the game, if it has one.
+    pub fn trump(self) -> Option<Suit> {
+        match self {
+            Variant::Hearts => None,
+            Variant::Spades => Some(Suit::Spade),
+        }
+    }
+
+    /// Returns the suit that can't be led until it has been "broken" (played on an earlier trick).
+    fn restricted_suit(self) -> Suit {
+        match self {
+            Variant::Hearts => Suit::Heart,
+            Variant::Spades => Suit::Spade,
+        }
+    }
+}
+
+/// Shuffles a standard deck and deals 13 cards to each of the four seats. Every hand is sorted.
+pub fn deal<R: Rng + ?Sized>(rng: &mut R) -> [Vec<Card>; SEATS] {
+    let mut deck: Vec<Card> = CardSet::full().iter().map(Card::from).collect();
+    deck.shuffle(rng);
+
+    let mut hands: [Vec<Card>; SEATS] = Default::default();
+    for (i, card) in deck.into_iter().enumerate() {
+        hands[i % SEATS].push(card);
+    }
+    for hand in &mut hands {
+        hand.sort();
+    }
+
+    hands
+}
+
+/// Returns the seat that played the winning card of a trick, given every card played to it in
+/// order (starting with the card that was led), and the trump suit if there is one.
+///
+/// Returns [`None`] if the trick is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::{Card, Suit};
+/// # use cardlab_ref::trick::winner;
+/// #
+/// let trick = [
+///     (2, Card::new("heart", 10)),
+///     (3, Card::new("heart", 14)),
+///     (0, Card::new("spade", 2)),
+///     (1, Card::new("club", 14)),
+/// ];
+///
+/// assert_eq!(winner(&trick, None), Some(3));
+/// assert_eq!(winner(&trick, Some(Suit::Spade)), Some(0));
+/// ```
+pub fn winner(trick: &[(usize, Card)], trump: Option<Suit>) -> Option<usize> {
+    let &(_, led) = trick.first()?;
+    let ordering = Trick::new(led.suit(), trump);
+
+    // Every card that can win the trick is different, so there is exactly one highest card.
+    trick
+        .iter()
+        .max_by(|(_, a), (_, b)| ordering.compare(a, b))
+        .map(|&(seat, _)| seat)
+}
+
+/// Returns how many points a set of cards is worth in Hearts: 1 for every Heart, and 13 for the
+/// Queen of Spades.
+pub fn hearts_points(cards: &[Card]) -> u32 {
+    cards.iter().map(|card| card_points(*card)).sum()
+}
+
+/// The number of points a single card is worth in Hearts.
+fn card_points(card: Card) -> u32 {
+    if card.suit() == Suit::Heart {
+        1
+    } else if card == Card::new("spade", 12) {
+        13
+    } else {
+        0
+    }
+}
+
+/// The reasons a card can't be played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayError {
+    /// Every trick of the round has already been played.
+    RoundOver,
+    /// The card is not in the hand of the player whose turn it is.
+    NotInHand(Card),
+    /// The card is in the player's hand, but the rules do not allow playing it right now.
+    Illegal(Card),
+}
+
+impl Display for PlayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RoundOver => write!(f, "the round is over"),
+            Self::NotInHand(card) => write!(f, "{card:?} is not in the player's hand"),
+            Self::Illegal(card) => write!(f, "{card:?} can't be played right now"),
+        }
+    }
+}
+
+impl std::error::Error for PlayError {}
+
+/// A single round of Hearts or Spades: 13 tricks, played out of the hands that were dealt.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::trick::{Round, Variant, deal};
+/// # use rand::SeedableRng;
+/// # use rand::rngs::StdRng;
+/// #
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let mut round = Round::new(Variant::Hearts, deal(&mut rng), 0);
+///
+/// // Play the round out by always playing the lowest legal card.
+/// while !round.is_over() {
+///     let card = round.legal_plays()[0];
+///     round.play(card).unwrap();
+/// }
+///
+/// let total: u32 = round.hearts_scores().iter().sum();
+/// assert!(total == 26 || total == 78);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Round {
+    /// Which game is being played.
+    variant: Variant,
+    /// The cards left in every seat's hand.
+    hands: [Vec<Card>; SEATS],
+    /// The cards played to the current trick so far, along with the seats that played them.
+    trick: Vec<(usize, Card)>,
+    /// Every trick that has been completed, in order.
+    completed: Vec<Vec<(usize, Card)>>,
+    /// The cards taken by every seat.
+    taken: [Vec<Card>; SEATS],
+    /// The number of tricks taken by every seat.
+    tricks_won: [usize; SEATS],
+    /// Whether a card of the restricted suit (see [`Variant::restricted_suit`]) has been played.
+    broken: bool,
+    /// The seat whose turn it is.
+    to_act: usize,
+}
+
+impl Round {
+    /// Starts a round with the hands that were dealt to every seat.
+    ///
+    /// In Hearts, the seat holding the Two of Clubs leads the first trick. In Spades, `leader`
+    /// leads the first trick (usually the seat to the left of the dealer).
+    pub fn new(variant: Variant, hands: [Vec<Card>; SEATS], leader: usize) -> Self {
+        let two_of_clubs = Card::new("club", 2);
+        let to_act = match variant {
+            Variant::Hearts => hands
+                .iter()
+                .position(|hand| hand.contains(&two_of_clubs))
+                .unwrap_or(leader),
+            Variant::Spades => leader,
+        };
+
+        Self {
+            variant,
+            hands,
+            trick: Vec::with_capacity(SEATS),
+            completed: Vec::with_capacity(TRICKS),
+            taken: Default::default(),
+            tricks_won: [0; SEATS],
+            broken: false,
+            to_act: to_act % SEATS,
+        }
+    }
+
+    /// Returns which game is being played.
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Returns the seat whose turn it is.
+    pub fn to_act(&self) -> usize {
+        self.to_act
+    }
+
+    /// Returns the cards left in a seat's hand.
+    pub fn hand(&self, seat: usize) -> &[Card] {
+        &self.hands[seat]
+    }
+
+    /// Returns the cards played to the current trick so far, along with the seats that played them.
+    pub fn trick(&self) -> &[(usize, Card)] {
+        &self.trick
+    }
+
+    /// Returns every trick that has been completed, in order.
+    pub fn completed(&self) -> &[Vec<(usize, Card)>] {
+        &self.completed
+    }
+
+    /// Returns the cards taken by a seat.
+    pub fn taken(&self, seat: usize) -> &[Card] {
+        &self.taken[seat]
+    }
+
+    /// Returns the number of tricks taken by every seat.
+    pub fn tricks_won(&self) -> [usize; SEATS] {
+        self.tricks_won
+    }
+
+    /// Returns whether every trick has been played.
+    pub fn is_over(&self) -> bool {
+        self.completed.len() == TRICKS
+    }
+
+    /// Returns every card the seat whose turn it is may play, in the order of their hand.
+    pub fn legal_plays(&self) -> Vec<Card> {
+        if self.is_over() {
+            return Vec::new();
+        }
+
+        let hand = &self.hands[self.to_act];
+        let first_trick = self.completed.is_empty();
+
+        let legal: Vec<Card> = match self.trick.first() {
+            // The Two of Clubs always leads the first trick of Hearts.
+            None if first_trick && self.variant == Variant::Hearts => {
+                let two_of_clubs = Card::new("club", 2);
+                self.unless_none(hand, |&card| card == two_of_clubs)
+            }
+            // The restricted suit can't be led until it is broken, unless there is no choice.
+            None => {
+                let restricted = self.variant.restricted_suit();
+                self.unless_none(hand, |card| self.broken || card.suit() != restricted)
+            }
+            // Follow suit if possible.
+            Some(&(_, led)) => self.unless_none(hand, |card| card.suit() == led.suit()),
+        };
+
+        // Nobody may play points on the first trick of Hearts, unless they have no choice.
+        if first_trick && self.variant == Variant::Hearts {
+            self.unless_none(&legal, |card| card_points(*card) == 0)
+        } else {
+            legal
+        }
+    }
+
+    /// Returns the cards that match `keep`, or all of them if none do.
+    fn unless_none(&self, cards: &[Card], keep: impl Fn(&Card) -> bool) -> Vec<Card> {
+        let kept: Vec<Card> = cards.iter().copied().filter(|card| keep(card)).collect();
+        if kept.is_empty() {
+            cards.to_vec()
+        } else {
+            kept
+        }
+    }
+
+    /// Plays a card from the hand of the seat whose turn it is.
+    ///
+    /// If this completes a trick, the trick goes to its winner, who leads the next one, and their
+    /// seat is returned.
+    pub fn play(&mut self, card: Card) -> Result<Option<usize>, PlayError> {
+        if self.is_over() {
+            return Err(PlayError::RoundOver);
+        }
+
+        let hand = &self.hands[self.to_act];
+        let Some(position) = hand.iter().position(|&c| c == card) else {
+            return Err(PlayError::NotInHand(card));
+        };
+        if !self.legal_plays().contains(&card) {
+            return Err(PlayError::Illegal(card));
+        }
+
+        self.hands[self.to_act].remove(position);
+        self.trick.push((self.to_act, card));
+        if card.suit() == self.variant.restricted_suit() {
+            self.broken = true;
+        }
+
+        if self.trick.len() < SEATS {
+            self.to_act = (self.to_act + 1) % SEATS;
+            return Ok(None);
+        }
+
+        let winner = winner(&self.trick, self.variant.trump()).expect("the trick is not empty");
+        self.taken[winner].extend(self.trick.iter().map(|&(_, card)| card));
+        self.tricks_won[winner] += 1;
+        self.completed.push(std::mem::take(&mut self.trick));
+        self.to_act = winner;
+
+        Ok(Some(winner))
+    }
+
+    /// Returns the Hearts points every seat took this round, after checking whether anyone shot
+    /// the moon (in which case they score 0, and everyone else scores 26).
+    pub fn hearts_scores(&self) -> [u32; SEATS] {
+        let points = self.taken.each_ref().map(|taken| hearts_points(taken));
+
+        if points.contains(&26) {
+            points.map(|points| if points == 26 { 0 } else { 26 })
+        } else {
+            points
+        }
+    }
+}
+
+/// A team's score in Spades.
+///
+/// Every round, a team's contract is the total bid of its two players. If the team takes at least
+/// that many tricks, it scores 10 points for every trick it bid, plus 1 point for every extra trick
+/// (known as a "bag"). Otherwise, it loses 10 points for every trick it bid. Every time a team
+/// collects 10 bags, it loses 100 points.
+///
+/// A bid of 0 is a "nil" bid, which is worth 100 points if that player takes no tricks at all, and
+/// loses 100 points otherwise. The tricks a nil bidder takes do not count towards their partner's
+/// contract, but they do count as bags.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::trick::SpadesScore;
+/// #
+/// let mut score = SpadesScore::default();
+///
+/// // Bid 4 and 3, and took 5 and 3 tricks.
+/// score.add_round([4, 3], [5, 3]);
+/// assert_eq!(score.points(), 71);
+/// assert_eq!(score.bags(), 1);
+///
+/// // Bid nil and 5, took 1 and 6 tricks.
+/// score.add_round([0, 5], [1, 6]);
+/// assert_eq!(score.points(), 71 - 100 + 50 + 2);
+/// assert_eq!(score.bags(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpadesScore {
+    /// The team's total points.
+    points: i32,
+    /// The number of bags the team has collected since it last lost 100 points for them.
+    bags: u32,
+}
+
+impl SpadesScore {
+    /// The number of bags that costs a team 100 points.
+    pub const BAG_LIMIT: u32 = 10;
+
+    /// Returns the team's total points.
+    pub fn points(&self) -> i32 {
+        self.points
+    }
+
+    /// Returns the number of bags the team has collected since it last lost 100 points for them.
+    pub fn bags(&self) -> u32 {
+        self.bags
+    }
+
+    /// Adds the result of a round, given the bids and the tricks taken by both partners.
+    pub fn add_round(&mut self, bids: [u8; 2], tricks: [usize; 2]) {
+        let mut contract = 0;
+        let mut made = 0;
+        let mut bags = 0;
+
+        for (bid, tricks) in bids.into_iter().zip(tricks) {
+            let tricks = tricks as u32;
+            if bid == 0 {
+                self.points += if tricks == 0 { 100 } else { -100 };
+                bags += tricks;
+            } else {
+                contract += u32::from(bid);
+                made += tricks;
+            }
+        }
+
+        if contract > 0 {
+            if made >= contract {
+                self.points += 10 * contract as i32;
+                bags += made - contract;
+            } else {
+                self.points -= 10 * contract as i32;
+            }
+        }
+
+        self.points += bags as i32;
+        self.bags += bags;
+        while self.bags >= Self::BAG_LIMIT {
+            self.bags -= Self::BAG_LIMIT;
+            self.points -= 100;
+        }
+    }
+}
+
+/// What a [`Player`] can see when it is their turn to play a card.
+#[derive(Debug, Clone, Copy)]
+pub struct Turn<'a> {
+    /// Which game is being played.
+    pub variant: Variant,
+    /// The player's seat.
+    pub seat: usize,
+    /// The cards in the player's hand.
+    pub hand: &'a [Card],
+    /// The cards the player may play, which is never empty.
+    pub legal: &'a [Card],
+    /// The cards played to the current trick so far, along with the seats that played them.
+    pub trick: &'a [(usize, Card)],
+    /// Every trick that has been completed this round, in order.
+    pub completed: &'a [Vec<(usize, Card)>],
+}
+
+/// A strategy for playing Hearts or Spades.
+pub trait Player {
+    /// Returns how many tricks the player expects to take in a round of Spades, given their seat
+    /// and their hand. A bid of 0 is a nil bid (see [`SpadesScore`]).
+    ///
+    /// By default, bids one trick for every Ace and King, and one for every Spade beyond the
+    /// third, but always at least one.
+    fn bid(&mut self, seat: usize, hand: &[Card]) -> u8 {
+        let _ = seat;
+        let high_cards = hand.iter().filter(|card| card.rank_value() >= 13).count();
+        let spades = hand
+            .iter()
+            .filter(|card| card.suit() == Suit::Spade)
+            .count();
+
+        (high_cards + spades.saturating_sub(3)).clamp(1, TRICKS) as u8
+    }
+
+    /// Returns the card to play, which must be one of `turn.legal`.
+    fn play(&mut self, turn: &Turn) -> Card;
+}
+
+/// A [`Player`] that plays a random legal card every time.
+#[derive(Debug, Clone)]
+pub struct RandomPlayer<R> {
+    /// Where the randomness comes from.
+    rng: R,
+}
+
+impl<R: Rng> RandomPlayer<R> {
+    /// Creates a player that makes its choices with `rng`.
+    pub fn new(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<R: Rng> Player for RandomPlayer<R> {
+    fn play(&mut self, turn: &Turn) -> Card {
+        *turn
+            .legal
+            .choose(&mut self.rng)
+            .expect("there is always a legal card")
+    }
+}
+
+/// The scores of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scores {
+    /// The Hearts points of every seat. Lower is better.
+    Hearts([u32; SEATS]),
+    /// The score of each team in Spades: seats 0 and 2, and seats 1 and 3.
+    Spades([SpadesScore; 2]),
+}
+
+/// A game of Hearts or Spades between four [`Player`]s.
+///
+/// # Examples
+///
+/// ```
+/// # use cardlab_ref::trick::{Game, Player, RandomPlayer, Variant};
+/// # use rand::SeedableRng;
+/// # use rand::rngs::StdRng;
+/// #
+/// let players: [Box<dyn Player>; 4] =
+///     std::array::from_fn(|seat| Box::new(RandomPlayer::new(StdRng::seed_from_u64(seat as u64))) as _);
+///
+/// let mut game = Game::new(Variant::Spades, players);
+/// let winners = game.play(&mut StdRng::seed_from_u64(42)).unwrap();
+///
+/// // In Spades, the partners sitting across from each other win together.
+/// assert_eq!(winners.len(), 2);
+/// assert_eq!(winners[1], winners[0] + 2);
+/// ```
+pub struct Game<'a> {
+    /// Which game is being played.
+    variant: Variant,
+    /// The strategy of every seat.
+    players: [Box<dyn Player + 'a>; SEATS],
+    /// The seat that deals the next round.
+    dealer: usize,
+    /// The number of rounds played so far.
+    rounds: usize,
+    /// The scores so far.
+    scores: Scores,
+}
+
+impl<'a> Game<'a> {
+    /// The score at which a game of Hearts ends.
+    pub const HEARTS_LIMIT: u32 = 100;
+
+    /// The score at which a game of Spades ends.
+    pub const SPADES_TARGET: i32 = 500;
+
+    /// The score at which a team loses a game of Spades, no matter how the other team is doing.
+    pub const SPADES_LOSING_SCORE: i32 = -200;
+
+    /// The number of rounds after which a game ends, even if no score limit has been reached. Only
+    /// a game of Spades can take this long, since its scores can go down as well as up.
+    pub const MAX_ROUNDS: usize = 100;
+
+    /// Creates a game with the strategy of every seat. Seat 0 deals the first round.
+    pub fn new(variant: Variant, players: [Box<dyn Player + 'a>; SEATS]) -> Self {
+        let scores = match variant {
+            Variant::Hearts => Scores::Hearts([0; SEATS]),
+            Variant::Spades => Scores::Spades([SpadesScore::default(); 2]),
+        };
+
+        Self {
+            variant,
+            players,
+            dealer: 0,
+            rounds: 0,
+            scores,
+        }
+    }
+
+    /// Returns the scores so far.
+    pub fn scores(&self) -> Scores {
+        self.scores
+    }
+
+    /// Returns the number of rounds played so far.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Returns the seats that won the game (more than one if there is a tie for the lowest score
+    /// in Hearts, or both partners in Spades), or [`None`] if the game is not over yet.
+    ///
+    /// A game of Spades ends when a team reaches [`Game::SPADES_TARGET`] or falls to
+    /// [`Game::SPADES_LOSING_SCORE`], and the team with the higher score wins. A tie is played out,
+    /// unless the game has already lasted [`Game::MAX_ROUNDS`] rounds, in which case all four
+    /// seats win.
+    pub fn winners(&self) -> Option<Vec<usize>> {
+        let out_of_rounds = self.rounds >= Self::MAX_ROUNDS;
+
+        match self.scores {
+            Scores::Hearts(points) => {
+                if points.iter().all(|&p| p < Self::HEARTS_LIMIT) && !out_of_rounds {
+                    return None;
+                }
+                let lowest = *points.iter().min().expect("there are four seats");
+                Some((0..SEATS).filter(|&seat| points[seat] == lowest).collect())
+            }
+            Scores::Spades([first, second]) => {
+                let (first, second) = (first.points(), second.points());
+                let reached_limit = first.max(second) >= Self::SPADES_TARGET
+                    || first.min(second) <= Self::SPADES_LOSING_SCORE;
+                if !reached_limit && !out_of_rounds {
+                    return None;
+                }
+
+                if first == second {
+                    return out_of_rounds.then(|| (0..SEATS).collect());
+                }
+                let team = usize::from(second > first);
+                Some(vec![team, team + 2])
+            }
+        }
+    }
+
+    /// Deals a round with `rng` and plays it, adding its result to the scores. Returns the round
+    /// once it has been played.
+    ///
+    /// Returns an error (and leaves the scores unchanged) if a player plays an illegal card.
+    pub fn play_round<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<Round, PlayError> {
+        self.play_hands(deal(rng))
+    }
+
+    /// Plays a round out of hands that were already dealt, adding its result to the scores.
+    ///
+    /// Returns an error (and leaves the scores unchanged) if a player plays an illegal card.
+    pub fn play_hands(&mut self, hands: [Vec<Card>; SEATS]) -> Result<Round, PlayError> {
+        let leader = (self.dealer + 1) % SEATS;
+
+        let bids: [u8; SEATS] = match self.variant {
+            Variant::Hearts => [0; SEATS],
+            Variant::Spades => std::array::from_fn(|seat| {
+                self.players[seat].bid(seat, &hands[seat]).min(TRICKS as u8)
+            }),
+        };
+
+        let mut round = Round::new(self.variant, hands, leader);
+        while !round.is_over() {
+            let seat = round.to_act();
+            let legal = round.legal_plays();
+            let turn = Turn {
+                variant: self.variant,
+                seat,
+                hand: round.hand(seat),
+                legal: &legal,
+                trick: round.trick(),
+                completed: round.completed(),
+            };
+
+            let card = self.players[seat].play(&turn);
+            round.play(card)?;
+        }
+
+        match &mut self.scores {
+            Scores::Hearts(points) => {
+                for (total, points) in points.iter_mut().zip(round.hearts_scores()) {
+                    *total += points;
+                }
+            }
+            Scores::Spades(teams) => {
+                let tricks = round.tricks_won();
+                for (team, score) in teams.iter_mut().enumerate() {
+                    score.add_round(
+                        [bids[team], bids[team + 2]],
+                        [tricks[team], tricks[team + 2]],
+                    );
+                }
+            }
+        }
+
+        self.dealer = (self.dealer + 1) % SEATS;
+        self.rounds += 1;
+        Ok(round)
+    }
+
+    /// Plays rounds until the game is over, and returns the winners (see [`Game::winners`]).
+    pub fn play<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<Vec<usize>, PlayError> {
+        loop {
+            if let Some(winners) = self.winners() {
+                return Ok(winners);
+            }
+            self.play_round(rng)?;
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::multimap::SetMultiMap;
+use crate::values::ValueSet;
+use std::hash::Hash;
+
+/// A multimap that can also be looked up in reverse, from a value to the keys associated with it.
+///
+/// This is useful as an inverted index: for example, mapping documents to the words they contain
+/// also answers which documents contain a given word.
+///
+/// It is implemented as two [`MultiMap`](crate::multimap::MultiMap)s, one from keys to values and
+/// one from values to keys, which every method keeps consistent with each other: a key is
+/// associated with a value in the forward map exactly when the value is associated with the key in
+/// the reverse map.
+///
+/// Each key-value pair is stored at most once (both maps are [`SetMultiMap`]s), so inserting the
+/// same pair twice does nothing the second time. Since every key and value is stored in both maps,
+/// they need to be [`Clone`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use multilab_ref::bimultimap::BiMultiMap;
+/// #
+/// let mut index = BiMultiMap::new();
+/// index.insert("doc1", "rust");
+/// index.insert("doc1", "lab");
+/// index.insert("doc2", "rust");
+///
+/// assert_eq!(index.get_values(&"doc1").unwrap(), &["rust", "lab"]);
+/// assert_eq!(index.get_keys(&"rust").unwrap(), &["doc1", "doc2"]);
+///
+/// index.remove_key(&"doc1");
+/// assert_eq!(index.get_keys(&"rust").unwrap(), &["doc2"]);
+/// assert!(index.get_keys(&"lab").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BiMultiMap<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Hash + Eq + Clone,
+{
+    /// The values associated with every key.
+    forward: SetMultiMap<K, V>,
+    /// The keys associated with every value.
+    reverse: SetMultiMap<V, K>,
+}
+
+impl<K, V> BiMultiMap<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Hash + Eq + Clone,
+{
+    /// Creates a new, empty [`BiMultiMap`].
+    pub fn new() -> Self {
+        Self {
+            forward: SetMultiMap::default(),
+            reverse: SetMultiMap::default(),
+        }
+    }
+
+    /// Associates a key with a value. Returns `false` if they were already associated.
+    pub fn insert(&mut self, key: K, value: V) -> bool {
+        if !self.forward.insert(key.clone(), value.clone()) {
+            return false;
+        }
+
+        self.reverse.insert(value, key);
+        true
+    }
+
+    /// Removes the association between a key and a value. Returns `false` if they were not
+    /// associated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::bimultimap::BiMultiMap;
+    /// #
+    /// let mut index = BiMultiMap::new();
+    /// index.insert(1, 'a');
+    /// index.insert(2, 'a');
+    ///
+    /// assert!(index.remove_value(&1, &'a'));
+    /// assert!(!index.remove_value(&1, &'a'));
+    /// assert_eq!(index.get_keys(&'a').unwrap(), &[2]);
+    /// assert!(!index.contains_key(&1));
+    /// ```
+    pub fn remove_value(&mut self, key: &K, value: &V) -> bool {
+        if !self.forward.remove_value(key, value) {
+            return false;
+        }
+
+        let removed = self.reverse.remove_value(value, key);
+        debug_assert!(removed, "the reverse map is missing a pair");
+        true
+    }
+
+    /// Removes a key and every value associated with it, and returns those values.
+    ///
+    /// Returns `None` if the key was not found.
+    pub fn remove_key(&mut self, key: &K) -> Option<ValueSet<V>> {
+        let values = self.forward.remove_key(key)?;
+        for value in values.iter() {
+            self.reverse.remove_value(value, key);
+        }
+        Some(values)
+    }
+
+    /// Removes a value and every key associated with it, and returns those keys.
+    ///
+    /// Returns `None` if the value was not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::bimultimap::BiMultiMap;
+    /// #
+    /// let mut index = BiMultiMap::new();
+    /// index.insert(1, 'a');
+    /// index.insert(2, 'a');
+    /// index.insert(2, 'b');
+    ///
+    /// assert_eq!(index.remove_keys(&'a').unwrap().len(), 2);
+    /// assert_eq!(index.get_values(&2).unwrap(), &['b']);
+    /// assert!(index.get_values(&1).is_none());
+    /// ```
+    pub fn remove_keys(&mut self, value: &V) -> Option<ValueSet<K>> {
+        let keys = self.reverse.remove_key(value)?;
+        for key in keys.iter() {
+            self.forward.remove_value(key, value);
+        }
+        Some(keys)
+    }
+
+    /// Gets the values associated with a key, if any.
+    pub fn get_values(&self, key: &K) -> Option<&[V]> {
+        self.forward.get_values(key)
+    }
+
+    /// Gets the keys associated with a value, if any.
+    pub fn get_keys(&self, value: &V) -> Option<&[K]> {
+        self.reverse.get_values(value)
+    }
+
+    /// Checks if a key is associated with a value.
+    pub fn contains(&self, key: &K, value: &V) -> bool {
+        self.forward
+            .get(key)
+            .is_some_and(|values| values.contains(value))
+    }
+
+    /// Checks if a key is associated with any values.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.forward.contains(key)
+    }
+
+    /// Checks if a value is associated with any keys.
+    pub fn contains_value(&self, value: &V) -> bool {
+        self.reverse.contains(value)
+    }
+
+    /// Counts the key-value pairs.
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    /// Checks if there are no key-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.forward.is_empty()
+    }
+
+    /// Returns an iterator over the keys that have values, in an arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.forward.keys()
+    }
+
+    /// Returns an iterator over the values that have keys, in an arbitrary order. Unlike
+    /// [`MultiMap::values`](crate::multimap::MultiMap::values), each value only appears once.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.reverse.keys()
+    }
+
+    /// Returns an iterator over every key-value pair, in an arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.forward.iter()
+    }
+
+    /// Returns an iterator over every value-key pair, in an arbitrary order.
+    pub fn iter_reverse(&self) -> impl Iterator<Item = (&V, &K)> {
+        self.reverse.iter()
+    }
+}
+
+impl<K, V> Default for BiMultiMap<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for BiMultiMap<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Hash + Eq + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut bimultimap = Self::new();
+        bimultimap.extend(iter);
+        bimultimap
+    }
+}
+
+impl<K, V> Extend<(K, V)> for BiMultiMap<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Hash + Eq + Clone,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
@@ -1,10 +1,18 @@
 #![doc = include_str!("../README.md")]
 
-/// This module contains a multiset type that can store elements of a generic type.
+/// This module contains multiset types that can store elements of a generic type, either hashed or
+/// sorted.
 pub mod multiset;
 
-/// This module contains a multimap type that associates keys with multiple values.
+/// This module contains multimap types that associate keys with multiple values, either hashed or
+/// sorted.
 pub mod multimap;
 
+/// This module contains the containers a multimap can store the values of each key in.
+pub mod values;
+
+/// This module contains a multimap that can also be looked up from values to keys.
+pub mod bimultimap;
+
 #[cfg(test)]
 mod tests;
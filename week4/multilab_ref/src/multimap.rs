@@ -1,5 +1,76 @@
-use std::collections::HashMap;
+use crate::values::{ValueContainer, ValueSet};
+use std::collections::{BTreeMap, HashMap, hash_map};
 use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut, Index, RangeBounds};
+use std::slice;
+
+/// The operations shared by [`MultiMap`] and [`BTreeMultiMap`].
+///
+/// Both types also have all of these as inherent methods, so this trait only needs to be imported
+/// to write code that works with either of them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use multilab_ref::multimap::{BTreeMultiMap, MultiMap, MultiMapOps};
+/// #
+/// fn tag_all<M: MultiMapOps<&'static str, u32>>(map: &mut M) {
+///     for (i, word) in ["a", "b", "a"].into_iter().enumerate() {
+///         map.insert(word, i as u32);
+///     }
+/// }
+///
+/// let mut hashed = MultiMap::new();
+/// let mut sorted = BTreeMultiMap::new();
+/// tag_all(&mut hashed);
+/// tag_all(&mut sorted);
+///
+/// assert_eq!(hashed.get_values(&"a"), sorted.get_values(&"a"));
+/// assert_eq!(hashed.len(), 3);
+/// assert_eq!(sorted.count(&"a"), 2);
+/// ```
+pub trait MultiMapOps<K, V> {
+    /// The container the values of each key are stored in.
+    type Values: ValueContainer<V>;
+
+    /// The mutable reference to a key's values returned by [`MultiMapOps::get_values_mut`].
+    type ValuesMut<'a>: DerefMut<Target = Self::Values>
+    where
+        Self: 'a,
+        K: 'a;
+
+    /// Inserts a key-value pair. See [`MultiMap::insert`].
+    fn insert(&mut self, key: K, value: V);
+
+    /// Removes a specific value from the values associated with a key. See
+    /// [`MultiMap::remove_value`].
+    fn remove_value(&mut self, key: &K, value: &V) -> bool;
+
+    /// Removes all values associated with a key and returns them. See [`MultiMap::remove_key`].
+    fn remove_key(&mut self, key: &K) -> Option<Self::Values>;
+
+    /// Gets the values associated with a key, if any. See [`MultiMap::get_values`].
+    fn get_values(&self, key: &K) -> Option<&[V]>;
+
+    /// Gets a mutable reference to the values associated with a key, if any. See
+    /// [`MultiMap::get_values_mut`].
+    fn get_values_mut<'a>(&'a mut self, key: &'a K) -> Option<Self::ValuesMut<'a>>;
+
+    /// Counts the values associated with a key. See [`MultiMap::count`].
+    fn count(&self, key: &K) -> usize;
+
+    /// Counts all of the values, across every key. See [`MultiMap::len`].
+    fn len(&self) -> usize;
+
+    /// Checks if there are no values at all.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if a key has any values associated with it. See [`MultiMap::contains`].
+    fn contains(&self, key: &K) -> bool;
+}
 
 /// A multimap that associates keys with multiple values.
 ///
@@ -25,33 +96,62 @@ use std::hash::Hash;
 /// ### `Eq + Hash`
 /// See the note in the documentation for [`MultiSet`](crate::multiset::MultiSet).
 ///
-/// ### Public Field
+/// ### Private Field
 ///
-/// Note that unlike [`MultiSet`](crate::multiset::MultiSet), it is fine to expose the
-/// inner hash table here because we are not storing any other metadata
-/// (in the previous section, you probably had more than one field tracking data).
-/// This means that an outsider can't mess up our data structure by breaking any invariants.
-/// So this type is really just a wrapper around the inner data structure
-/// that provides some useful methods.
+/// This type is really just a wrapper around an inner hash table that provides some useful
+/// methods, but the hash table is still private. Exposing it would let an outsider leave a key
+/// mapped to an empty [`Vec`] behind, which [`MultiMap::remove_value`] is careful never to do.
+/// Instead, the contents of the map are available through iterators (see [`MultiMap::iter`] and
+/// [`MultiMap::iter_all`]) and the [`MultiMap::entry`] API.
 ///
-/// Also, we wanted to expose it to help with our test cases 😊.
-#[derive(Debug)]
-pub struct MultiMap<K: Hash + Eq, V: Eq> {
+/// ### Value Containers
+///
+/// By default, the values of each key are stored in a [`Vec`], so inserting the same key-value
+/// pair twice stores the value twice. The third generic parameter picks a different
+/// [`ValueContainer`], such as a [`ValueSet`], which only stores each value once per key and can
+/// look up values in constant time. [`SetMultiMap`] is a shorthand for that.
+///
+/// ```rust
+/// # use multilab_ref::multimap::SetMultiMap;
+/// #
+/// let mut index = SetMultiMap::default();
+/// assert!(index.insert("rust", 1));
+/// assert!(index.insert("rust", 2));
+/// assert!(!index.insert("rust", 1));
+///
+/// assert_eq!(index.get_values(&"rust").unwrap(), &[1, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiMap<K: Hash + Eq, V: Eq, C: ValueContainer<V> = Vec<V>> {
     /// The internal [`HashMap`] storing the key-value associations.
-    pub inner: HashMap<K, Vec<V>>,
+    ///
+    /// Every key is mapped to a non-empty container. Whenever a container is emptied, whether by
+    /// [`MultiMap::remove_value`], [`ValuesMut`] or an [`Entry`], its key is removed as well.
+    inner: HashMap<K, C>,
+    /// Marks that the map stores values of type `V`, which only appears inside `C`.
+    values: PhantomData<V>,
 }
 
+/// A [`MultiMap`] that stores each value at most once per key, in a [`ValueSet`].
+pub type SetMultiMap<K, V> = MultiMap<K, V, ValueSet<V>>;
+
 impl<K: Hash + Eq, V: Eq> MultiMap<K, V> {
     /// Creates a new, empty [`MultiMap`].
+    ///
+    /// This always creates a map whose values are stored in a [`Vec`]. Use [`Default::default`]
+    /// to create a map with a different [`ValueContainer`].
     pub fn new() -> Self {
-        Self {
-            inner: HashMap::new(),
-        }
+        Self::default()
     }
+}
 
+impl<K: Hash + Eq, V: Eq, C: ValueContainer<V>> MultiMap<K, V, C> {
     /// Inserts a key-value pair into the [`MultiMap`].
     ///
-    /// If the key already exists, the value is added to the existing vector of values.
+    /// If the key already exists, the value is added to the existing container of values.
+    ///
+    /// Returns whether the value was added, which is always `true` unless the container does not
+    /// allow duplicates (such as a [`ValueSet`]).
     ///
     /// # Examples
     ///
@@ -64,8 +164,8 @@ impl<K: Hash + Eq, V: Eq> MultiMap<K, V> {
     ///
     /// assert_eq!(multimap.get_values(&1).unwrap(), &["hello", "world"]);
     /// ```
-    pub fn insert(&mut self, key: K, value: V) {
-        self.inner.entry(key).or_default().push(value);
+    pub fn insert(&mut self, key: K, value: V) -> bool {
+        self.inner.entry(key).or_default().insert(value)
     }
 
     /// Removes a specific value from the values associated with a key.
@@ -73,7 +173,7 @@ impl<K: Hash + Eq, V: Eq> MultiMap<K, V> {
     /// Returns `true` if the value was removed, `false` if the key or value was not found.
     ///
     /// Note that removing all values of a key will remove the key itself and the
-    /// `Vec<V>` mapped to it.
+    /// container mapped to it.
     ///
     /// # Examples
     ///
@@ -87,31 +187,16 @@ impl<K: Hash + Eq, V: Eq> MultiMap<K, V> {
     /// assert!(multimap.remove_value(&1, &"hello"));
     /// assert_eq!(multimap.get_values(&1).unwrap(), &["world"]);
     /// ```
-    #[allow(clippy::needless_range_loop)]
     pub fn remove_value(&mut self, key: &K, value: &V) -> bool {
         // Check if the key exists first.
         let Some(values) = self.inner.get_mut(key) else {
             return false;
         };
 
-        // Note that we can do the below much more succinctly with
-        // `values.iter().position(|x| x == value)`, but since we haven't talked about iterators
-        // yet, you don't have to worry about that now.
-
-        let mut found_index = None;
-        for i in 0..values.len() {
-            if values[i] == *value {
-                found_index = Some(i);
-                break;
-            }
-        }
-
-        let Some(i) = found_index else {
+        // The container knows how to find and remove the value (see `ValueContainer::remove`).
+        if !values.remove(value) {
             return false;
-        };
-
-        // Since we've found the index of the value, we can remove it.
-        values.remove(i);
+        }
 
         // Technically we don't need to remove the key if the value list is empty. This is a space
         // optimization (as opposed to a time optimization).
@@ -138,13 +223,13 @@ impl<K: Hash + Eq, V: Eq> MultiMap<K, V> {
     /// assert_eq!(multimap.remove_key(&1).unwrap(), &["hello", "world"]);
     /// assert!(multimap.get_values(&1).is_none());
     /// ```
-    pub fn remove_key(&mut self, key: &K) -> Option<Vec<V>> {
+    pub fn remove_key(&mut self, key: &K) -> Option<C> {
         self.inner.remove(key)
     }
 
     /// Gets the values associated with a key, if any.
     ///
-    /// Returns a reference to the vector of values if the key exists, otherwise `None`.
+    /// Returns a slice of the values if the key exists, otherwise `None`.
     /// The returned reference is immutable and can't be modified.
     ///
     /// # Examples
@@ -166,9 +251,31 @@ impl<K: Hash + Eq, V: Eq> MultiMap<K, V> {
         self.inner.get(key).map(|v| v.as_slice())
     }
 
+    /// Gets the container of values associated with a key, if any.
+    ///
+    /// This is like [`MultiMap::get_values`], but gives access to the container's own methods,
+    /// such as [`ValueSet::contains`], which takes constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::SetMultiMap;
+    /// #
+    /// let mut multimap = SetMultiMap::default();
+    /// multimap.insert(1, "hello");
+    ///
+    /// assert!(multimap.get(&1).unwrap().contains(&"hello"));
+    /// assert!(multimap.get(&2).is_none());
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&C> {
+        self.inner.get(key)
+    }
+
     /// Gets a mutable reference to the values associated with a key, if any.
     ///
-    /// Returns a mutable reference to the vector of values if the key exists, otherwise `None`.
+    /// Returns a [`ValuesMut`], which can be used like a mutable reference to the container of
+    /// values, if the key exists, otherwise `None`. If every value is removed through it, the key
+    /// is removed from the map once it is dropped.
     ///
     /// # Examples
     ///
@@ -178,23 +285,750 @@ impl<K: Hash + Eq, V: Eq> MultiMap<K, V> {
     /// let mut multimap = MultiMap::new();
     /// multimap.insert(1, "hello");
     ///
-    /// if let Some(values) = multimap.get_values_mut(&1) {
+    /// if let Some(mut values) = multimap.get_values_mut(&1) {
     ///     values.push("world");
     /// }
-    ///
     /// assert_eq!(multimap.get_values(&1).unwrap(), &["hello", "world"]);
+    ///
+    /// multimap.get_values_mut(&1).unwrap().clear();
+    /// assert!(!multimap.contains(&1));
+    /// ```
+    pub fn get_values_mut<'a>(&'a mut self, key: &'a K) -> Option<ValuesMut<'a, K, V, C>> {
+        if !self.inner.contains_key(key) {
+            return None;
+        }
+
+        Some(ValuesMut {
+            map: &mut self.inner,
+            key,
+            values: PhantomData,
+        })
+    }
+
+    /// Counts the values associated with a key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// multimap.insert(1, "hello");
+    /// multimap.insert(1, "world");
+    ///
+    /// assert_eq!(multimap.count(&1), 2);
+    /// assert_eq!(multimap.count(&2), 0);
+    /// ```
+    pub fn count(&self, key: &K) -> usize {
+        self.inner.get(key).map_or(0, |values| values.len())
+    }
+
+    /// Counts all of the values, across every key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// multimap.insert(1, "hello");
+    /// multimap.insert(1, "world");
+    /// multimap.insert(2, "foo");
+    ///
+    /// assert_eq!(multimap.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.inner.values().map(|values| values.len()).sum()
+    }
+
+    /// Checks if there are no values at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if a key has any values associated with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// multimap.insert(1, "hello");
+    ///
+    /// assert!(multimap.contains(&1));
+    /// assert!(!multimap.contains(&2));
     /// ```
-    pub fn get_values_mut(&mut self, key: &K) -> Option<&mut Vec<V>> {
-        self.inner.get_mut(key)
+    pub fn contains(&self, key: &K) -> bool {
+        self.count(key) > 0
+    }
+
+    /// Returns an iterator over every key-value pair, in an arbitrary order. Each key appears once
+    /// for every value associated with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// multimap.insert(1, "hello");
+    /// multimap.insert(1, "world");
+    ///
+    /// let mut pairs: Vec<_> = multimap.iter().collect();
+    /// pairs.sort();
+    /// assert_eq!(pairs, [(&1, &"hello"), (&1, &"world")]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V, C> {
+        Iter {
+            keys: self.inner.iter(),
+            current: None,
+        }
+    }
+
+    /// Returns an iterator over every key and all of its values, in an arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// multimap.insert(1, "hello");
+    /// multimap.insert(1, "world");
+    ///
+    /// let all: Vec<_> = multimap.iter_all().collect();
+    /// assert_eq!(all, [(&1, &["hello", "world"][..])]);
+    /// ```
+    pub fn iter_all(&self) -> impl Iterator<Item = (&K, &[V])> {
+        self.inner
+            .iter()
+            .map(|(key, values)| (key, values.as_slice()))
+    }
+
+    /// Returns an iterator over the keys that have values, in an arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter_all().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over every value, in an arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.inner.values().flat_map(|values| values.as_slice())
+    }
+
+    /// Removes every key from the map, and returns an iterator over the keys and their values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// multimap.insert(1, "hello");
+    /// multimap.insert(1, "world");
+    ///
+    /// let drained: Vec<_> = multimap.drain().collect();
+    /// assert_eq!(drained, [(1, vec!["hello", "world"])]);
+    /// assert!(multimap.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> impl Iterator<Item = (K, C)> {
+        self.inner.drain()
+    }
+
+    /// Turns the map into an iterator over every key and all of its values, in an arbitrary
+    /// order. Unlike [`MultiMap::into_iter`](IntoIterator::into_iter), this yields each key once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// multimap.insert(1, "hello");
+    /// multimap.insert(1, "world");
+    ///
+    /// let groups: Vec<_> = multimap.into_groups().collect();
+    /// assert_eq!(groups, [(1, vec!["hello", "world"])]);
+    /// ```
+    pub fn into_groups(self) -> impl Iterator<Item = (K, C)> {
+        self.inner.into_iter()
+    }
+
+    /// Keeps only the key-value pairs for which `f` returns `true`. Keys that are left without any
+    /// values are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// for i in 0..10 {
+    ///     multimap.insert(i % 3, i);
+    /// }
+    ///
+    /// multimap.retain(|_, value| value % 2 == 0);
+    /// assert_eq!(multimap.get_values(&0).unwrap(), &[0, 6]);
+    /// assert_eq!(multimap.get_values(&1).unwrap(), &[4]);
+    /// assert_eq!(multimap.get_values(&2).unwrap(), &[2, 8]);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.inner.retain(|key, values| {
+            values.retain(|value| f(key, value));
+            !values.is_empty()
+        });
+    }
+
+    /// Gets the entry for a key, to look at or change its values in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::MultiMap;
+    /// #
+    /// let mut multimap = MultiMap::new();
+    /// multimap.entry("odd").or_insert(1);
+    /// multimap.entry("odd").or_insert(3); // "odd" already has a value.
+    /// multimap.entry("odd").insert(5);
+    /// multimap.entry("even").and_modify(|values| values.clear());
+    ///
+    /// assert_eq!(multimap.get_values(&"odd").unwrap(), &[1, 5]);
+    /// assert!(!multimap.contains(&"even"));
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, C> {
+        Entry {
+            map: &mut self.inner,
+            key,
+            values: PhantomData,
+        }
+    }
+}
+
+/// A mutable reference to the values of a key in a [`MultiMap`], which is returned by
+/// [`MultiMap::get_values_mut`].
+///
+/// It can be used just like a `&mut C`. If every value is removed through it, the key is removed
+/// from the map when it is dropped, so that no key is ever left without values.
+#[derive(Debug)]
+pub struct ValuesMut<'a, K: Hash + Eq, V, C: ValueContainer<V> = Vec<V>> {
+    /// The internal [`HashMap`] of the map.
+    map: &'a mut HashMap<K, C>,
+    /// The key whose values are borrowed, which stays in `map` until this is dropped.
+    key: &'a K,
+    /// Marks that the values are of type `V`, which only appears inside `C`.
+    values: PhantomData<V>,
+}
+
+impl<K: Hash + Eq, V, C: ValueContainer<V>> Deref for ValuesMut<'_, K, V, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.map.get(self.key).expect("borrowed key is in the map")
+    }
+}
+
+impl<K: Hash + Eq, V, C: ValueContainer<V>> DerefMut for ValuesMut<'_, K, V, C> {
+    fn deref_mut(&mut self) -> &mut C {
+        self.map
+            .get_mut(self.key)
+            .expect("borrowed key is in the map")
+    }
+}
+
+impl<K: Hash + Eq, V, C: ValueContainer<V>> Drop for ValuesMut<'_, K, V, C> {
+    fn drop(&mut self) {
+        if self.is_empty() {
+            self.map.remove(self.key);
+        }
+    }
+}
+
+/// An entry of a [`MultiMap`], which is returned by [`MultiMap::entry`].
+#[derive(Debug)]
+pub struct Entry<'a, K, V, C = Vec<V>> {
+    /// The internal [`HashMap`] of the map.
+    map: &'a mut HashMap<K, C>,
+    /// The entry's key, which may or may not be in `map`.
+    key: K,
+    /// Marks that the entry holds values of type `V`, which only appears inside `C`.
+    values: PhantomData<V>,
+}
+
+impl<'a, K: Hash + Eq, V: Eq, C: ValueContainer<V>> Entry<'a, K, V, C> {
+    /// Returns the entry's key.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Adds a value to the key, and returns all of its values.
+    pub fn insert(self, value: V) -> &'a [V] {
+        let values = self.map.entry(self.key).or_default();
+        values.insert(value);
+        Self::as_slice(values)
+    }
+
+    /// Adds a value to the key if it does not have any values yet, and returns all of its values.
+    pub fn or_insert(self, value: V) -> &'a [V] {
+        self.or_insert_with(|| value)
+    }
+
+    /// Adds the value returned by `f` to the key if it does not have any values yet, and returns
+    /// all of its values.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a [V] {
+        let values = self.map.entry(self.key).or_default();
+        if values.is_empty() {
+            values.insert(f());
+        }
+        Self::as_slice(values)
+    }
+
+    /// Calls `f` with the key's values, if it has any. If `f` removes every value, the key is
+    /// removed from the map.
+    pub fn and_modify<F: FnOnce(&mut C)>(self, f: F) -> Self {
+        if let Some(values) = self.map.get_mut(&self.key) {
+            f(values);
+            if values.is_empty() {
+                self.map.remove(&self.key);
+            }
+        }
+        self
+    }
+
+    /// Gives up mutable access to the values, so that they can be returned for all of `'a`.
+    fn as_slice(values: &'a mut C) -> &'a [V] {
+        let values: &'a C = values;
+        values.as_slice()
+    }
+}
+
+/// An iterator over the key-value pairs of a [`MultiMap`], which is returned by
+/// [`MultiMap::iter`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a, K, V, C = Vec<V>> {
+    /// The keys that have not been visited yet.
+    keys: hash_map::Iter<'a, K, C>,
+    /// The key being visited, and its values that have not been visited yet.
+    current: Option<(&'a K, slice::Iter<'a, V>)>,
+}
+
+impl<'a, K, V, C: ValueContainer<V>> Iterator for Iter<'a, K, V, C> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, values)) = &mut self.current
+                && let Some(value) = values.next()
+            {
+                return Some((key, value));
+            }
+
+            let (key, values) = self.keys.next()?;
+            self.current = Some((key, values.as_slice().iter()));
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, C: ValueContainer<V>> FromIterator<(K, V)> for MultiMap<K, V, C> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut multimap = Self::default();
+        multimap.extend(iter);
+        multimap
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, C: ValueContainer<V>> Extend<(K, V)> for MultiMap<K, V, C> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
     }
 }
 
-impl<K, V> Default for MultiMap<K, V>
+/// Iterates over every key-value pair, in an arbitrary order. Each key is cloned for every value
+/// associated with it. See [`MultiMap::into_groups`] to get each key once with all of its values.
+///
+/// # Examples
+///
+/// ```rust
+/// # use multilab_ref::multimap::MultiMap;
+/// #
+/// let multimap: MultiMap<_, _> = [(1, "hello"), (1, "world")].into_iter().collect();
+///
+/// let mut pairs: Vec<_> = multimap.into_iter().collect();
+/// pairs.sort();
+/// assert_eq!(pairs, [(1, "hello"), (1, "world")]);
+/// ```
+impl<K, V, C> IntoIterator for MultiMap<K, V, C>
+where
+    K: Hash + Eq + Clone,
+    V: Eq,
+    C: ValueContainer<V> + IntoIterator<Item = V>,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, C>;
+
+    fn into_iter(self) -> IntoIter<K, V, C> {
+        IntoIter {
+            groups: self.inner.into_iter(),
+            current: None,
+        }
+    }
+}
+
+/// Iterates over every key-value pair, in an arbitrary order. See [`MultiMap::iter`].
+impl<'a, K: Hash + Eq, V: Eq, C: ValueContainer<V>> IntoIterator for &'a MultiMap<K, V, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, C>;
+
+    fn into_iter(self) -> Iter<'a, K, V, C> {
+        self.iter()
+    }
+}
+
+/// An owning iterator over the key-value pairs of a [`MultiMap`], which is returned by
+/// [`MultiMap::into_iter`](IntoIterator::into_iter).
+#[derive(Debug)]
+pub struct IntoIter<K, V, C: IntoIterator<Item = V> = Vec<V>> {
+    /// The keys that have not been visited yet, and their values.
+    groups: hash_map::IntoIter<K, C>,
+    /// The key being visited, and its values that have not been visited yet.
+    current: Option<(K, C::IntoIter)>,
+}
+
+impl<K: Clone, V, C: IntoIterator<Item = V>> Iterator for IntoIter<K, V, C> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, values)) = &mut self.current
+                && let Some(value) = values.next()
+            {
+                return Some((key.clone(), value));
+            }
+
+            let (key, values) = self.groups.next()?;
+            self.current = Some((key, values.into_iter()));
+        }
+    }
+}
+
+/// Two [`MultiMap`]s are equal if they have the same keys, and every key has equal containers of
+/// values. For [`Vec`]s, that means the same values in the same order, while for [`ValueSet`]s,
+/// the order does not matter.
+impl<K: Hash + Eq, V: Eq, C: ValueContainer<V> + PartialEq> PartialEq for MultiMap<K, V, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, C: ValueContainer<V> + Eq> Eq for MultiMap<K, V, C> {}
+
+/// Gets the values associated with a key.
+///
+/// # Panics
+///
+/// Panics if the key has no values.
+///
+/// # Examples
+///
+/// ```rust
+/// # use multilab_ref::multimap::MultiMap;
+/// #
+/// let multimap: MultiMap<_, _> = [(1, "hello"), (1, "world")].into_iter().collect();
+/// assert_eq!(&multimap[&1], &["hello", "world"]);
+/// ```
+impl<K: Hash + Eq, V: Eq, C: ValueContainer<V>> Index<&K> for MultiMap<K, V, C> {
+    type Output = [V];
+
+    fn index(&self, key: &K) -> &[V] {
+        self.get_values(key).expect("key not found in MultiMap")
+    }
+}
+
+impl<K, V, C> Default for MultiMap<K, V, C>
 where
     K: Eq + Hash,
     V: Eq,
+    C: ValueContainer<V>,
 {
+    fn default() -> Self {
+        Self {
+            inner: HashMap::new(),
+            values: PhantomData,
+        }
+    }
+}
+
+/// Implements [`MultiMapOps`] by forwarding to the inherent methods.
+macro_rules! impl_multimap_ops {
+    ([$($generics:tt)+] $map:ident<$($params:ty),+>, $values:ty, $values_mut:ty) => {
+        impl<$($generics)+> MultiMapOps<K, V> for $map<$($params),+> {
+            type Values = $values;
+            type ValuesMut<'a> = $values_mut where Self: 'a, K: 'a;
+
+            fn insert(&mut self, key: K, value: V) {
+                $map::insert(self, key, value);
+            }
+
+            fn remove_value(&mut self, key: &K, value: &V) -> bool {
+                $map::remove_value(self, key, value)
+            }
+
+            fn remove_key(&mut self, key: &K) -> Option<$values> {
+                $map::remove_key(self, key)
+            }
+
+            fn get_values(&self, key: &K) -> Option<&[V]> {
+                $map::get_values(self, key)
+            }
+
+            fn get_values_mut<'a>(&'a mut self, key: &'a K) -> Option<$values_mut> {
+                $map::get_values_mut(self, key)
+            }
+
+            fn count(&self, key: &K) -> usize {
+                $map::count(self, key)
+            }
+
+            fn len(&self) -> usize {
+                $map::len(self)
+            }
+
+            fn contains(&self, key: &K) -> bool {
+                $map::contains(self, key)
+            }
+        }
+    };
+}
+
+impl_multimap_ops!(
+    [K: Hash + Eq, V: Eq, C: ValueContainer<V>] MultiMap<K, V, C>,
+    C,
+    ValuesMut<'a, K, V, C>
+);
+impl_multimap_ops!([K: Ord, V: Eq] BTreeMultiMap<K, V>, Vec<V>, BTreeValuesMut<'a, K, V>);
+
+/// A multimap that keeps its keys sorted.
+///
+/// This is the same as [`MultiMap`], but it is implemented using a [`BTreeMap`] instead of a
+/// [`HashMap`]. Keys need to be [`Ord`] instead of `Hash + Eq`, and in return, iterating over the
+/// map visits the keys in order, and it is possible to look up a range of keys.
+///
+/// The values of each key are still kept in the order they were inserted.
+///
+/// # Examples
+///
+/// ```rust
+/// # use multilab_ref::multimap::BTreeMultiMap;
+/// #
+/// let mut multimap = BTreeMultiMap::new();
+/// multimap.insert(3, "three");
+/// multimap.insert(1, "one");
+/// multimap.insert(2, "two");
+/// multimap.insert(1, "uno");
+///
+/// let keys: Vec<_> = multimap.keys().collect();
+/// assert_eq!(keys, [&1, &2, &3]);
+///
+/// let (key, values) = multimap.first().unwrap();
+/// assert_eq!((key, values), (&1, &["one", "uno"][..]));
+///
+/// let middle: Vec<_> = multimap.range(2..).map(|(key, _)| key).collect();
+/// assert_eq!(middle, [&2, &3]);
+/// ```
+#[derive(Debug)]
+pub struct BTreeMultiMap<K: Ord, V: Eq> {
+    /// The internal [`BTreeMap`] storing the key-value associations.
+    ///
+    /// Like in a [`MultiMap`], every key is mapped to a non-empty [`Vec`].
+    inner: BTreeMap<K, Vec<V>>,
+}
+
+impl<K: Ord, V: Eq> BTreeMultiMap<K, V> {
+    /// Creates a new, empty [`BTreeMultiMap`].
+    pub fn new() -> Self {
+        Self {
+            inner: BTreeMap::new(),
+        }
+    }
+
+    /// Inserts a key-value pair. See [`MultiMap::insert`].
+    pub fn insert(&mut self, key: K, value: V) {
+        self.inner.entry(key).or_default().push(value);
+    }
+
+    /// Removes a specific value from the values associated with a key. See
+    /// [`MultiMap::remove_value`].
+    pub fn remove_value(&mut self, key: &K, value: &V) -> bool {
+        let Some(values) = self.inner.get_mut(key) else {
+            return false;
+        };
+        let Some(i) = values.iter().position(|x| x == value) else {
+            return false;
+        };
+
+        values.remove(i);
+        if values.is_empty() {
+            self.inner.remove(key);
+        }
+
+        true
+    }
+
+    /// Removes all values associated with a key and returns them. See [`MultiMap::remove_key`].
+    pub fn remove_key(&mut self, key: &K) -> Option<Vec<V>> {
+        self.inner.remove(key)
+    }
+
+    /// Gets the values associated with a key, if any. See [`MultiMap::get_values`].
+    pub fn get_values(&self, key: &K) -> Option<&[V]> {
+        self.inner.get(key).map(|v| v.as_slice())
+    }
+
+    /// Gets a mutable reference to the values associated with a key, if any. See
+    /// [`MultiMap::get_values_mut`].
+    pub fn get_values_mut<'a>(&'a mut self, key: &'a K) -> Option<BTreeValuesMut<'a, K, V>> {
+        if !self.inner.contains_key(key) {
+            return None;
+        }
+
+        Some(BTreeValuesMut {
+            map: &mut self.inner,
+            key,
+        })
+    }
+
+    /// Counts the values associated with a key.
+    pub fn count(&self, key: &K) -> usize {
+        self.inner.get(key).map_or(0, |values| values.len())
+    }
+
+    /// Counts all of the values, across every key.
+    pub fn len(&self) -> usize {
+        self.inner.values().map(|values| values.len()).sum()
+    }
+
+    /// Checks if there are no values at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if a key has any values associated with it.
+    pub fn contains(&self, key: &K) -> bool {
+        self.count(key) > 0
+    }
+
+    /// Returns the smallest key and its values, or `None` if the map is empty.
+    pub fn first(&self) -> Option<(&K, &[V])> {
+        self.iter_all().next()
+    }
+
+    /// Returns the largest key and its values, or `None` if the map is empty.
+    pub fn last(&self) -> Option<(&K, &[V])> {
+        self.iter_all().next_back()
+    }
+
+    /// Returns an iterator over the keys in `range` and their values, in order of the keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`BTreeMap::range`], such as when the start of the range is
+    /// after its end.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::BTreeMultiMap;
+    /// #
+    /// let mut multimap = BTreeMultiMap::new();
+    /// for i in 0..10 {
+    ///     multimap.insert(i % 5, i);
+    /// }
+    ///
+    /// let range: Vec<_> = multimap.range(1..3).collect();
+    /// assert_eq!(range, [(&1, &[1, 6][..]), (&2, &[2, 7][..])]);
+    /// ```
+    pub fn range<R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (&K, &[V])> {
+        self.inner
+            .range(range)
+            .map(|(key, values)| (key, values.as_slice()))
+    }
+
+    /// Returns an iterator over the keys that have values, in order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter_all().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over every key and all of its values, in order of the keys.
+    pub fn iter_all(&self) -> impl DoubleEndedIterator<Item = (&K, &[V])> {
+        self.range(..)
+    }
+
+    /// Returns an iterator over every key-value pair, in order of the keys. Each key appears once
+    /// for every value associated with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use multilab_ref::multimap::BTreeMultiMap;
+    /// #
+    /// let mut multimap = BTreeMultiMap::new();
+    /// multimap.insert("b", 2);
+    /// multimap.insert("a", 1);
+    /// multimap.insert("b", 3);
+    ///
+    /// let pairs: Vec<_> = multimap.iter().collect();
+    /// assert_eq!(pairs, [(&"a", &1), (&"b", &2), (&"b", &3)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.inner
+            .iter()
+            .flat_map(|(key, values)| values.iter().map(move |value| (key, value)))
+    }
+}
+
+impl<K: Ord, V: Eq> Default for BTreeMultiMap<K, V> {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// A mutable reference to the values of a key in a [`BTreeMultiMap`], which is returned by
+/// [`BTreeMultiMap::get_values_mut`]. See [`ValuesMut`].
+#[derive(Debug)]
+pub struct BTreeValuesMut<'a, K: Ord, V> {
+    /// The internal [`BTreeMap`] of the map.
+    map: &'a mut BTreeMap<K, Vec<V>>,
+    /// The key whose values are borrowed, which stays in `map` until this is dropped.
+    key: &'a K,
+}
+
+impl<K: Ord, V> Deref for BTreeValuesMut<'_, K, V> {
+    type Target = Vec<V>;
+
+    fn deref(&self) -> &Vec<V> {
+        self.map.get(self.key).expect("borrowed key is in the map")
+    }
+}
+
+impl<K: Ord, V> DerefMut for BTreeValuesMut<'_, K, V> {
+    fn deref_mut(&mut self) -> &mut Vec<V> {
+        self.map
+            .get_mut(self.key)
+            .expect("borrowed key is in the map")
+    }
+}
+
+impl<K: Ord, V> Drop for BTreeValuesMut<'_, K, V> {
+    fn drop(&mut self) {
+        if self.is_empty() {
+            self.map.remove(self.key);
+        }
+    }
+}
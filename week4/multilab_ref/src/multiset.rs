@@ -1,5 +1,53 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
+use std::ops::RangeBounds;
+
+/// The operations shared by [`MultiSet`] and [`BTreeMultiSet`].
+///
+/// Both types also have all of these as inherent methods, so this trait only needs to be imported
+/// to write code that works with either of them.
+///
+/// # Examples
+///
+/// ```
+/// # use multilab_ref::multiset::{BTreeMultiSet, MultiSet, MultiSetOps};
+/// #
+/// fn count_words<S: MultiSetOps<&'static str>>(words: &mut S, text: &'static str) {
+///     for word in text.split_whitespace() {
+///         words.insert(word);
+///     }
+/// }
+///
+/// let mut hashed = MultiSet::new();
+/// let mut sorted = BTreeMultiSet::new();
+/// count_words(&mut hashed, "the cat and the hat");
+/// count_words(&mut sorted, "the cat and the hat");
+///
+/// assert_eq!(hashed.count(&"the"), 2);
+/// assert_eq!(sorted.count(&"the"), 2);
+/// assert_eq!(hashed.len(), sorted.len());
+/// ```
+pub trait MultiSetOps<K> {
+    /// Inserts an element. See [`MultiSet::insert`].
+    fn insert(&mut self, value: K);
+
+    /// Removes one occurrence of an element. See [`MultiSet::remove`].
+    fn remove(&mut self, value: &K) -> bool;
+
+    /// Counts the occurrences of `value`. See [`MultiSet::count`].
+    fn count(&self, value: &K) -> usize;
+
+    /// Counts all the elements, including each duplicate. See [`MultiSet::len`].
+    fn len(&self) -> usize;
+
+    /// Checks if the multiset is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if a given value is in the multiset. See [`MultiSet::contains`].
+    fn contains(&self, value: &K) -> bool;
+}
 
 /// A multiset that can store elements of type `K`.
 ///
@@ -34,6 +82,7 @@ use std::hash::Hash;
 ///
 /// This shouldn't have any effect your implementation,
 /// but if you run into trouble with this, please let us know!
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MultiSet<K: Eq + Hash> {
     inner: HashMap<K, usize>,
     size: usize,
@@ -177,6 +226,295 @@ where
     pub fn count(&self, value: &K) -> usize {
         self.inner.get(value).map_or(0, |x| *x)
     }
+
+    /// Returns an iterator over every element, in an arbitrary order. Each element appears as many
+    /// times as it occurs, and all of its occurrences are next to each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut multiset = MultiSet::new();
+    /// multiset.insert('a');
+    /// multiset.insert('b');
+    /// multiset.insert('a');
+    ///
+    /// let mut elements: Vec<_> = multiset.iter().collect();
+    /// elements.sort();
+    /// assert_eq!(elements, [&'a', &'a', &'b']);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
+        self.iter_distinct()
+            .flat_map(|(value, count)| std::iter::repeat_n(value, count))
+    }
+
+    /// Returns an iterator over every distinct element and its number of occurrences, in an
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut multiset = MultiSet::new();
+    /// multiset.insert('a');
+    /// multiset.insert('b');
+    /// multiset.insert('a');
+    ///
+    /// let mut counts: Vec<_> = multiset.iter_distinct().collect();
+    /// counts.sort();
+    /// assert_eq!(counts, [(&'a', 2), (&'b', 1)]);
+    /// ```
+    pub fn iter_distinct(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.inner.iter().map(|(value, &count)| (value, count))
+    }
+
+    /// Inserts `n` occurrences of an element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut multiset = MultiSet::new();
+    /// multiset.insert_n('a', 3);
+    /// multiset.insert_n('b', 0);
+    ///
+    /// assert_eq!(multiset.count(&'a'), 3);
+    /// assert!(!multiset.contains(&'b'));
+    /// assert_eq!(multiset.len(), 3);
+    /// ```
+    pub fn insert_n(&mut self, value: K, n: usize) {
+        if n == 0 {
+            return;
+        }
+        self.size += n;
+        *self.inner.entry(value).or_insert(0) += n;
+    }
+
+    /// Removes up to `n` occurrences of an element, and returns how many were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut multiset = MultiSet::new();
+    /// multiset.insert_n('a', 3);
+    ///
+    /// assert_eq!(multiset.remove_n(&'a', 2), 2);
+    /// assert_eq!(multiset.remove_n(&'a', 2), 1);
+    /// assert_eq!(multiset.remove_n(&'a', 2), 0);
+    /// assert!(multiset.is_empty());
+    /// ```
+    pub fn remove_n(&mut self, value: &K, n: usize) -> usize {
+        let Some(count) = self.inner.get_mut(value) else {
+            return 0;
+        };
+
+        let removed = n.min(*count);
+        *count -= removed;
+        if *count == 0 {
+            self.inner.remove(value);
+        }
+        self.size -= removed;
+        removed
+    }
+
+    /// Returns the `n` elements that occur the most, along with their number of occurrences, from
+    /// most to least common. Elements that occur equally often are in an arbitrary order.
+    ///
+    /// If there are fewer than `n` distinct elements, all of them are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut letters = MultiSet::new();
+    /// for letter in "banana".chars() {
+    ///     letters.insert(letter);
+    /// }
+    ///
+    /// assert_eq!(letters.most_common(1), [(&'a', 3)]);
+    /// assert_eq!(letters.most_common(3), [(&'a', 3), (&'n', 2), (&'b', 1)]);
+    /// assert_eq!(letters.most_common(10).len(), 3);
+    /// ```
+    pub fn most_common(&self, n: usize) -> Vec<(&K, usize)> {
+        let mut counts: Vec<_> = self.iter_distinct().collect();
+        counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+        counts.truncate(n);
+        counts
+    }
+
+    /// Checks if every element occurs in `other` at least as many times as it occurs in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut a = MultiSet::new();
+    /// a.insert_n('x', 2);
+    /// let mut b = MultiSet::new();
+    /// b.insert_n('x', 3);
+    /// b.insert('y');
+    ///
+    /// assert!(a.is_subset(&b));
+    /// assert!(!b.is_subset(&a));
+    ///
+    /// // Multiplicities matter.
+    /// a.insert_n('x', 2);
+    /// assert!(!a.is_subset(&b));
+    /// ```
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.len() <= other.len()
+            && self
+                .iter_distinct()
+                .all(|(value, count)| count <= other.count(value))
+    }
+
+    /// Checks if every element occurs in `self` at least as many times as it occurs in `other`.
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+}
+
+impl<K> MultiSet<K>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Creates a multiset of every element in either `self` or `other`, where the number of
+    /// occurrences of each element is given by `f` of its counts in `self` and in `other`.
+    fn combine(&self, other: &Self, f: impl Fn(usize, usize) -> usize) -> Self {
+        let mut result = Self::new();
+        for (value, count) in self.iter_distinct() {
+            result.insert_n(value.clone(), f(count, other.count(value)));
+        }
+        for (value, count) in other.iter_distinct() {
+            if !self.contains(value) {
+                result.insert_n(value.clone(), f(0, count));
+            }
+        }
+        result
+    }
+
+    /// Returns the union of two multisets, where each element occurs as many times as it does in
+    /// whichever multiset has more of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut a = MultiSet::new();
+    /// a.insert_n('x', 2);
+    /// a.insert('y');
+    /// let mut b = MultiSet::new();
+    /// b.insert_n('x', 1);
+    /// b.insert_n('z', 3);
+    ///
+    /// let union = a.union(&b);
+    /// assert_eq!(union.count(&'x'), 2);
+    /// assert_eq!(union.count(&'y'), 1);
+    /// assert_eq!(union.count(&'z'), 3);
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, usize::max)
+    }
+
+    /// Returns the intersection of two multisets, where each element occurs as many times as it
+    /// does in whichever multiset has less of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut a = MultiSet::new();
+    /// a.insert_n('x', 2);
+    /// a.insert('y');
+    /// let mut b = MultiSet::new();
+    /// b.insert_n('x', 1);
+    /// b.insert_n('z', 3);
+    ///
+    /// let intersection = a.intersection(&b);
+    /// assert_eq!(intersection.count(&'x'), 1);
+    /// assert!(!intersection.contains(&'y'));
+    /// assert_eq!(intersection.len(), 1);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, usize::min)
+    }
+
+    /// Returns the difference of two multisets, where each occurrence of an element in `other`
+    /// cancels out one occurrence of it in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut a = MultiSet::new();
+    /// a.insert_n('x', 2);
+    /// a.insert('y');
+    /// let mut b = MultiSet::new();
+    /// b.insert_n('x', 1);
+    /// b.insert_n('y', 3);
+    ///
+    /// let difference = a.difference(&b);
+    /// assert_eq!(difference.count(&'x'), 1);
+    /// assert_eq!(difference.len(), 1);
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, usize::saturating_sub)
+    }
+
+    /// Returns the sum of two multisets, where each element occurs as many times as it does in
+    /// both multisets together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut a = MultiSet::new();
+    /// a.insert_n('x', 2);
+    /// let mut b = MultiSet::new();
+    /// b.insert_n('x', 1);
+    /// b.insert('y');
+    ///
+    /// let sum = a.sum(&b);
+    /// assert_eq!(sum.count(&'x'), 3);
+    /// assert_eq!(sum.len(), a.len() + b.len());
+    /// ```
+    pub fn sum(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a + b)
+    }
+
+    /// Returns the symmetric difference of two multisets, where each element occurs as many times
+    /// as the difference between its counts in the two multisets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use multilab_ref::multiset::MultiSet;
+    /// #
+    /// let mut a = MultiSet::new();
+    /// a.insert_n('x', 2);
+    /// a.insert('y');
+    /// let mut b = MultiSet::new();
+    /// b.insert_n('x', 5);
+    /// b.insert('y');
+    ///
+    /// let symmetric_difference = a.symmetric_difference(&b);
+    /// assert_eq!(symmetric_difference.count(&'x'), 3);
+    /// assert!(!symmetric_difference.contains(&'y'));
+    /// ```
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.combine(other, usize::abs_diff)
+    }
 }
 
 impl<K> Default for MultiSet<K>
@@ -187,3 +525,167 @@ where
         Self::new()
     }
 }
+
+/// Implements [`MultiSetOps`] by forwarding to the inherent methods.
+macro_rules! impl_multiset_ops {
+    ($set:ident, $($bound:tt)+) => {
+        impl<K: $($bound)+> MultiSetOps<K> for $set<K> {
+            fn insert(&mut self, value: K) {
+                $set::insert(self, value)
+            }
+
+            fn remove(&mut self, value: &K) -> bool {
+                $set::remove(self, value)
+            }
+
+            fn count(&self, value: &K) -> usize {
+                $set::count(self, value)
+            }
+
+            fn len(&self) -> usize {
+                $set::len(self)
+            }
+
+            fn contains(&self, value: &K) -> bool {
+                $set::contains(self, value)
+            }
+        }
+    };
+}
+
+impl_multiset_ops!(MultiSet, Eq + Hash);
+impl_multiset_ops!(BTreeMultiSet, Ord);
+
+/// A multiset that keeps its elements sorted.
+///
+/// This is the same as [`MultiSet`], but it is implemented using a [`BTreeMap`] instead of a
+/// [`HashMap`]. Elements need to be [`Ord`] instead of `Eq + Hash`, and in return, iterating over
+/// the multiset visits the elements in order, and it is possible to look up a range of elements.
+///
+/// # Examples
+///
+/// ```
+/// # use multilab_ref::multiset::BTreeMultiSet;
+/// #
+/// let mut scores = BTreeMultiSet::new();
+/// for score in [70, 95, 70, 82, 95, 95] {
+///     scores.insert(score);
+/// }
+///
+/// assert_eq!(scores.first(), Some((&70, 2)));
+/// assert_eq!(scores.last(), Some((&95, 3)));
+///
+/// let passing: usize = scores.range(80..).map(|(_, count)| count).sum();
+/// assert_eq!(passing, 4);
+///
+/// let sorted: Vec<_> = scores.iter().collect();
+/// assert_eq!(sorted, [&70, &70, &82, &95, &95, &95]);
+/// ```
+#[derive(Debug)]
+pub struct BTreeMultiSet<K: Ord> {
+    /// The number of occurrences of every element, which is never 0.
+    inner: BTreeMap<K, usize>,
+    /// The total number of elements, including each duplicate.
+    size: usize,
+}
+
+impl<K: Ord> BTreeMultiSet<K> {
+    /// Creates a new empty [`BTreeMultiSet`].
+    pub fn new() -> Self {
+        Self {
+            inner: BTreeMap::new(),
+            size: 0,
+        }
+    }
+
+    /// Checks if the multiset is empty.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Counts all the elements, including each duplicate.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Checks if a given value is in the multiset.
+    pub fn contains(&self, value: &K) -> bool {
+        self.inner.contains_key(value)
+    }
+
+    /// Inserts an element.
+    pub fn insert(&mut self, value: K) {
+        self.size += 1;
+        *self.inner.entry(value).or_insert(0) += 1;
+    }
+
+    /// Removes one occurrence of an element. Returns false if the element was not in the
+    /// multiset.
+    pub fn remove(&mut self, value: &K) -> bool {
+        match self.inner.get_mut(value) {
+            None => false,
+            Some(count) => {
+                debug_assert!(*count > 0);
+                if *count == 1 {
+                    self.inner.remove(value);
+                } else {
+                    *count -= 1;
+                }
+                self.size -= 1;
+                true
+            }
+        }
+    }
+
+    /// Counts the occurrences of `value`.
+    pub fn count(&self, value: &K) -> usize {
+        self.inner.get(value).map_or(0, |x| *x)
+    }
+
+    /// Returns the smallest element and its number of occurrences, or `None` if the multiset is
+    /// empty.
+    pub fn first(&self) -> Option<(&K, usize)> {
+        self.inner
+            .first_key_value()
+            .map(|(value, &count)| (value, count))
+    }
+
+    /// Returns the largest element and its number of occurrences, or `None` if the multiset is
+    /// empty.
+    pub fn last(&self) -> Option<(&K, usize)> {
+        self.inner
+            .last_key_value()
+            .map(|(value, &count)| (value, count))
+    }
+
+    /// Returns an iterator over the distinct elements in `range` and their number of occurrences,
+    /// in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`BTreeMap::range`], such as when the start of the range is
+    /// after its end.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> impl Iterator<Item = (&K, usize)> {
+        self.inner
+            .range(range)
+            .map(|(value, &count)| (value, count))
+    }
+
+    /// Returns an iterator over every distinct element and its number of occurrences, in order.
+    pub fn iter_distinct(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.range(..)
+    }
+
+    /// Returns an iterator over every element in order, where each element appears as many times
+    /// as it occurs.
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
+        self.iter_distinct()
+            .flat_map(|(value, count)| std::iter::repeat_n(value, count))
+    }
+}
+
+impl<K: Ord> Default for BTreeMultiSet<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,192 +1,873 @@
-mod multiset_tests {
-    use crate::multiset::MultiSet;
+/// Runs the same tests against a multiset type, through the [`MultiSetOps`] trait.
+///
+/// [`MultiSetOps`]: crate::multiset::MultiSetOps
+macro_rules! multiset_tests {
+    ($name:ident, $multiset:ident) => {
+        mod $name {
+            use crate::multiset::{MultiSetOps, $multiset as MultiSet};
+
+            /// Creates an empty multiset, which can only be used through the [`MultiSetOps`] trait.
+            fn new<K: Ord + std::hash::Hash>() -> impl MultiSetOps<K> {
+                MultiSet::new()
+            }
+
+            #[test]
+            fn test_insert_remove_basic() {
+                let mut multiset = new::<char>();
+                assert_eq!(0, multiset.count(&'a'));
+                assert!(!multiset.remove(&'a'));
+
+                let multiset = new::<String>();
+                assert_eq!(0, multiset.count(&"hello".to_string()));
+
+                let mut multiset = new::<i32>();
+                multiset.insert(5);
+                multiset.insert(5);
+                assert_eq!(2, multiset.len());
+                assert_eq!(2, multiset.count(&5));
+
+                let mut multiset = new::<i32>();
+                multiset.insert(5);
+                multiset.insert(3);
+                multiset.insert(5);
+                assert_eq!(2, multiset.count(&5));
+                assert_eq!(1, multiset.count(&3));
+                assert_eq!(0, multiset.count(&10));
+            }
+
+            #[test]
+            fn test_insert_remove_more() {
+                let mut multiset = new::<i32>();
+                multiset.insert(5);
+                assert_eq!(1, multiset.len());
+                assert!(multiset.contains(&5));
+                assert!(multiset.remove(&5));
+                assert!(multiset.is_empty());
+
+                let mut multiset = new::<i32>();
+                multiset.insert(5);
+                multiset.insert(5);
+                assert_eq!(2, multiset.len());
+                assert!(multiset.remove(&5));
+                assert_eq!(1, multiset.len());
+                assert_eq!(1, multiset.count(&5));
+
+                let mut multiset = new::<i32>();
+                multiset.insert(5);
+                multiset.insert(3);
+                multiset.insert(5);
+                assert_eq!(3, multiset.len());
+                assert!(multiset.remove(&5));
+                assert_eq!(2, multiset.len());
+                assert_eq!(1, multiset.count(&5));
+                assert!(multiset.remove(&3));
+                assert_eq!(1, multiset.len());
+                assert!(multiset.remove(&5));
+                assert!(multiset.is_empty());
+            }
+
+            #[test]
+            fn test_multiple_insertions_and_removals() {
+                let mut multiset = new::<String>();
+
+                // Insert a variety of elements, some duplicates
+                multiset.insert("apple".to_string());
+                multiset.insert("banana".to_string());
+                multiset.insert("apple".to_string());
+                multiset.insert("cherry".to_string());
+                multiset.insert("banana".to_string());
+
+                assert_eq!(5, multiset.len());
+                assert_eq!(2, multiset.count(&"apple".to_string()));
+                assert_eq!(2, multiset.count(&"banana".to_string()));
+                assert_eq!(1, multiset.count(&"cherry".to_string()));
+
+                // Remove some elements, including duplicates
+                assert!(multiset.remove(&"apple".to_string()));
+                assert!(multiset.remove(&"banana".to_string()));
+                assert!(multiset.remove(&"cherry".to_string()));
+
+                assert_eq!(2, multiset.len());
+                assert_eq!(1, multiset.count(&"apple".to_string()));
+                assert_eq!(1, multiset.count(&"banana".to_string()));
+
+                // Remove the remaining elements
+                assert!(multiset.remove(&"apple".to_string()));
+                assert!(multiset.remove(&"banana".to_string()));
+
+                assert!(multiset.is_empty());
+            }
+
+            #[test]
+            fn test_interleaved_insert_remove() {
+                let mut multiset = new::<i32>();
+
+                let mut removal_counter = 0;
+
+                for i in 0..10_000 {
+                    multiset.insert(i % 100);
+
+                    if i % 2 == 0 {
+                        multiset.remove(&(removal_counter % 100));
+                        removal_counter += 1;
+                    }
+                }
+
+                assert_eq!(5_000, multiset.len());
+                assert_eq!(50, multiset.count(&0));
+            }
+        }
+    };
+}
+
+multiset_tests!(multiset_tests, MultiSet);
+multiset_tests!(btree_multiset_tests, BTreeMultiSet);
+
+/// Runs the same tests against a multimap type, through the [`MultiMapOps`] trait.
+///
+/// [`MultiMapOps`]: crate::multimap::MultiMapOps
+macro_rules! multimap_tests {
+    ($name:ident, $multimap:ident) => {
+        mod $name {
+            use crate::multimap::{MultiMapOps, $multimap as MultiMap};
+
+            /// Creates an empty multimap, which can only be used through the [`MultiMapOps`] trait.
+            fn new<K: Ord + std::hash::Hash, V: Eq>() -> impl MultiMapOps<K, V, Values = Vec<V>> {
+                MultiMap::new()
+            }
+
+            #[test]
+            fn test_insert_duplicate_values() {
+                let mut multimap = new::<i32, &str>();
+                multimap.insert(1, "hello");
+                multimap.insert(1, "hello");
+
+                assert_eq!(multimap.get_values(&1).unwrap(), &["hello", "hello"]);
+            }
+
+            #[test]
+            fn test_insert_many_values() {
+                let mut multimap = new::<i32, i32>();
+                for i in 0..1000 {
+                    multimap.insert(1, i);
+                }
+
+                assert_eq!(multimap.get_values(&1).unwrap().len(), 1000);
+            }
+
+            #[test]
+            fn test_remove_nonexistent_key() {
+                let mut multimap = new::<i32, Option<()>>();
+                assert_eq!(multimap.get_values(&1), None);
+                assert_eq!(multimap.remove_key(&1), None);
+            }
+
+            #[test]
+            fn test_remove_value_from_multiple() {
+                let mut multimap = new::<i32, &str>();
+
+                multimap.insert(1, "hello");
+                assert!(multimap.remove_value(&1, &"hello"));
+                assert!(multimap.get_values(&1).is_none());
+
+                multimap.insert(1, "hello");
+                multimap.insert(1, "world");
+                assert!(multimap.remove_value(&1, &"hello"));
+                assert_eq!(multimap.get_values(&1).unwrap(), &["world"]);
+            }
+
+            #[test]
+            fn test_large_insert_remove() {
+                let mut multimap = new::<i32, i32>();
+
+                // Insert 100 values for 1000 keys
+                for i in 0..1000 {
+                    for j in 0..100 {
+                        multimap.insert(i, j);
+                    }
+                }
+
+                // Assert the total number of key-value pairs
+                assert_eq!(multimap.len(), 100000);
+
+                // Remove half of the values for each key using indices
+                for i in 0..1000 {
+                    if let Some(mut values) = multimap.get_values_mut(&i) {
+                        let mut remaining_count = values.len();
+                        for _ in 0..(remaining_count / 2) {
+                            let index_to_remove = 0; // Remove from the beginning for simplicity
+                            values.remove(index_to_remove);
+                            remaining_count -= 1;
+                        }
+                        assert_eq!(remaining_count, 50);
+                    }
+                }
+
+                // Assert the remaining number of key-value pairs
+                assert_eq!(multimap.len(), 50000);
+            }
+
+            #[test]
+            fn test_emptied_key() {
+                let mut multimap = new::<i32, &str>();
+                multimap.insert(1, "hello");
+                multimap.insert(2, "world");
+
+                // Emptying a key's values by hand removes the key.
+                multimap.get_values_mut(&1).unwrap().clear();
+                assert_eq!(multimap.get_values(&1), None);
+                assert!(multimap.get_values_mut(&1).is_none());
+                assert!(!multimap.contains(&1));
+                assert_eq!(multimap.count(&1), 0);
+                assert_eq!(multimap.len(), 1);
+                assert_eq!(multimap.remove_key(&1), None);
+                assert!(!multimap.remove_value(&1, &"hello"));
+
+                multimap.get_values_mut(&2).unwrap().clear();
+                assert!(multimap.is_empty());
+                assert_eq!(multimap.remove_key(&2), None);
+
+                multimap.insert(1, "again");
+                assert_eq!(multimap.get_values(&1).unwrap(), &["again"]);
+            }
+        }
+    };
+}
+
+multimap_tests!(multimap_tests, MultiMap);
+multimap_tests!(btree_multimap_tests, BTreeMultiMap);
+
+mod btree_tests {
+    use crate::multimap::BTreeMultiMap;
+    use crate::multiset::BTreeMultiSet;
 
     #[test]
-    fn test_insert_remove_basic() {
-        let mut multiset: MultiSet<char> = MultiSet::new();
-        assert_eq!(0, multiset.count(&'a'));
-        assert!(!multiset.remove(&'a'));
+    fn test_multiset_ordered() {
+        let mut multiset = BTreeMultiSet::new();
+        assert_eq!(multiset.first(), None);
+        assert_eq!(multiset.last(), None);
+
+        for i in (0..100).rev() {
+            multiset.insert(i % 10);
+        }
 
-        let multiset: MultiSet<String> = MultiSet::new();
-        assert_eq!(0, multiset.count(&"hello".to_string()));
+        assert_eq!(multiset.first(), Some((&0, 10)));
+        assert_eq!(multiset.last(), Some((&9, 10)));
 
-        let mut multiset: MultiSet<i32> = MultiSet::new();
-        multiset.insert(5);
-        multiset.insert(5);
-        assert_eq!(2, multiset.len());
-        assert_eq!(2, multiset.count(&5));
+        let elements: Vec<_> = multiset.iter().copied().collect();
+        assert_eq!(elements.len(), 100);
+        assert!(elements.is_sorted());
 
-        let mut multiset: MultiSet<i32> = MultiSet::new();
-        multiset.insert(5);
-        multiset.insert(3);
-        multiset.insert(5);
-        assert_eq!(2, multiset.count(&5));
-        assert_eq!(1, multiset.count(&3));
-        assert_eq!(0, multiset.count(&10));
+        let range: Vec<_> = multiset.range(3..=5).collect();
+        assert_eq!(range, [(&3, 10), (&4, 10), (&5, 10)]);
+
+        for _ in 0..10 {
+            assert!(multiset.remove(&0));
+        }
+        assert_eq!(multiset.first(), Some((&1, 10)));
+        assert_eq!(multiset.range(..1).count(), 0);
+        assert_eq!(multiset.iter_distinct().count(), 9);
     }
 
     #[test]
-    fn test_insert_remove_more() {
-        let mut multiset: MultiSet<i32> = MultiSet::new();
-        multiset.insert(5);
-        assert_eq!(1, multiset.len());
-        assert!(multiset.contains(&5));
-        assert!(multiset.remove(&5));
-        assert!(multiset.is_empty());
+    fn test_multimap_ordered() {
+        let mut multimap = BTreeMultiMap::new();
+        assert_eq!(multimap.first(), None);
 
-        let mut multiset: MultiSet<i32> = MultiSet::new();
-        multiset.insert(5);
-        multiset.insert(5);
-        assert_eq!(2, multiset.len());
-        assert!(multiset.remove(&5));
-        assert_eq!(1, multiset.len());
-        assert_eq!(1, multiset.count(&5));
+        for word in ["delta", "alpha", "charlie", "bravo", "alpha"] {
+            multimap.insert(word.len(), word);
+        }
 
-        let mut multiset: MultiSet<i32> = MultiSet::new();
-        multiset.insert(5);
-        multiset.insert(3);
-        multiset.insert(5);
-        assert_eq!(3, multiset.len());
-        assert!(multiset.remove(&5));
-        assert_eq!(2, multiset.len());
-        assert_eq!(1, multiset.count(&5));
-        assert!(multiset.remove(&3));
-        assert_eq!(1, multiset.len());
-        assert!(multiset.remove(&5));
-        assert!(multiset.is_empty());
+        assert_eq!(
+            multimap.first(),
+            Some((&5, &["delta", "alpha", "bravo", "alpha"][..]))
+        );
+        assert_eq!(multimap.last(), Some((&7, &["charlie"][..])));
+        assert_eq!(multimap.keys().collect::<Vec<_>>(), [&5, &7]);
+        assert_eq!(multimap.range(6..).count(), 1);
+        assert_eq!(multimap.iter().count(), 5);
+
+        assert!(multimap.remove_value(&7, &"charlie"));
+        assert!(!multimap.contains(&7));
+        assert_eq!(multimap.last(), multimap.first());
+        assert_eq!(multimap.iter_all().count(), 1);
     }
 
     #[test]
-    fn test_multiple_insertions_and_removals() {
-        let mut multiset: MultiSet<String> = MultiSet::new();
+    fn test_multimap_ordered_skips_emptied_keys() {
+        let mut multimap = BTreeMultiMap::new();
+        for i in 1..=3 {
+            multimap.insert(i, i * 10);
+        }
 
-        // Insert a variety of elements, some duplicates
-        multiset.insert("apple".to_string());
-        multiset.insert("banana".to_string());
-        multiset.insert("apple".to_string());
-        multiset.insert("cherry".to_string());
-        multiset.insert("banana".to_string());
+        multimap.get_values_mut(&1).unwrap().clear();
+        multimap.get_values_mut(&3).unwrap().clear();
+
+        assert_eq!(multimap.first(), Some((&2, &[20][..])));
+        assert_eq!(multimap.last(), Some((&2, &[20][..])));
+        assert_eq!(multimap.keys().collect::<Vec<_>>(), [&2]);
+        assert_eq!(multimap.range(..).count(), 1);
+        assert_eq!(multimap.iter().count(), 1);
+    }
+}
+
+mod collection_tests {
+    use crate::multimap::MultiMap;
+    use crate::multiset::MultiSet;
+
+    /// A multimap from every number in `0..n` modulo 3 to the number.
+    fn mod_three(n: i32) -> MultiMap<i32, i32> {
+        (0..n).map(|i| (i % 3, i)).collect()
+    }
+
+    #[test]
+    fn test_multimap_iterators() {
+        let multimap = mod_three(7);
+
+        let mut pairs: Vec<_> = multimap.iter().map(|(&k, &v)| (k, v)).collect();
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            [(0, 0), (0, 3), (0, 6), (1, 1), (1, 4), (2, 2), (2, 5)]
+        );
+
+        let mut borrowed = Vec::new();
+        for (&key, &value) in &multimap {
+            borrowed.push((key, value));
+        }
+        borrowed.sort();
+        assert_eq!(borrowed, pairs);
+
+        let mut keys: Vec<_> = multimap.keys().copied().collect();
+        keys.sort();
+        assert_eq!(keys, [0, 1, 2]);
+
+        let mut values: Vec<_> = multimap.values().copied().collect();
+        values.sort();
+        assert_eq!(values, [0, 1, 2, 3, 4, 5, 6]);
+
+        let mut all: Vec<_> = multimap.iter_all().collect();
+        all.sort();
+        assert_eq!(
+            all,
+            [(&0, &[0, 3, 6][..]), (&1, &[1, 4][..]), (&2, &[2, 5][..])]
+        );
+
+        let mut groups: Vec<_> = multimap.clone().into_groups().collect();
+        groups.sort();
+        assert_eq!(
+            groups,
+            [(0, vec![0, 3, 6]), (1, vec![1, 4]), (2, vec![2, 5])]
+        );
+
+        let mut owned: Vec<_> = multimap.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, pairs);
+    }
 
-        assert_eq!(5, multiset.len());
-        assert_eq!(2, multiset.count(&"apple".to_string()));
-        assert_eq!(2, multiset.count(&"banana".to_string()));
-        assert_eq!(1, multiset.count(&"cherry".to_string()));
+    #[test]
+    fn test_multimap_emptied_key() {
+        let mut multimap = mod_three(6);
+        multimap.get_values_mut(&1).unwrap().clear();
 
-        // Remove some elements, including duplicates
-        assert!(multiset.remove(&"apple".to_string()));
-        assert!(multiset.remove(&"banana".to_string()));
-        assert!(multiset.remove(&"cherry".to_string()));
+        // The key is removed along with its last value.
+        assert!(!multimap.contains(&1));
+        assert_eq!(multimap.get_values(&1), None);
+        assert_eq!(multimap.keys().count(), 2);
+        assert_eq!(multimap.iter_all().count(), 2);
+        assert_eq!(multimap.iter().count(), 4);
+        assert_eq!(multimap.clone().into_groups().count(), 2);
+        assert_eq!(multimap.into_iter().count(), 4);
+
+        let mut multimap = mod_three(6);
+        multimap.get_values_mut(&1).unwrap().clear();
+        assert_eq!(multimap.drain().count(), 2);
+        assert!(multimap.is_empty());
+    }
 
-        assert_eq!(2, multiset.len());
-        assert_eq!(1, multiset.count(&"apple".to_string()));
-        assert_eq!(1, multiset.count(&"banana".to_string()));
+    #[test]
+    fn test_multimap_drain_retain() {
+        let mut multimap = mod_three(12);
+
+        multimap.retain(|&key, &value| key != 1 && value < 9);
+        assert!(!multimap.contains(&1));
+        assert_eq!(multimap.get_values(&0).unwrap(), &[0, 3, 6]);
+        assert_eq!(multimap.get_values(&2).unwrap(), &[2, 5, 8]);
+        assert_eq!(multimap.len(), 6);
+
+        let mut drained: Vec<_> = multimap.drain().collect();
+        drained.sort();
+        assert_eq!(drained, [(0, vec![0, 3, 6]), (2, vec![2, 5, 8])]);
+        assert!(multimap.is_empty());
+        assert_eq!(multimap.keys().count(), 0);
+    }
 
-        // Remove the remaining elements
-        assert!(multiset.remove(&"apple".to_string()));
-        assert!(multiset.remove(&"banana".to_string()));
+    #[test]
+    fn test_multimap_entry() {
+        let mut multimap = MultiMap::new();
 
-        assert!(multiset.is_empty());
+        assert_eq!(multimap.entry("a").or_insert(1), &[1]);
+        assert_eq!(multimap.entry("a").or_insert(2), &[1]);
+        assert_eq!(multimap.entry("a").insert(3), &[1, 3]);
+        assert_eq!(multimap.entry("b").or_insert_with(|| 4), &[4]);
+        assert_eq!(multimap.entry("b").key(), &"b");
+
+        multimap
+            .entry("a")
+            .and_modify(|values| values.retain(|&v| v > 1))
+            .insert(5);
+        assert_eq!(multimap.get_values(&"a").unwrap(), &[3, 5]);
+
+        // Modifying a key that does not exist does nothing.
+        multimap.entry("c").and_modify(|values| values.push(6));
+        assert!(!multimap.contains(&"c"));
+
+        // An emptied key counts as a key without values.
+        multimap.entry("b").and_modify(|values| values.clear());
+        assert_eq!(multimap.entry("b").or_insert(7), &[7]);
     }
 
     #[test]
-    fn test_interleaved_insert_remove() {
-        let mut multiset: MultiSet<i32> = MultiSet::new();
+    fn test_multimap_traits() {
+        let multimap = mod_three(6);
+        let mut copy = multimap.clone();
+        assert_eq!(multimap, copy);
+
+        copy.extend([(3, 6)]);
+        assert_ne!(multimap, copy);
+        assert!(copy.remove_value(&3, &6));
+        assert_eq!(multimap, copy);
+
+        // Values are compared in order.
+        let reversed: MultiMap<_, _> = (0..6).rev().map(|i| (i % 3, i)).collect();
+        assert_ne!(multimap, reversed);
+
+        // Emptied keys do not matter.
+        copy.insert(4, 4);
+        copy.get_values_mut(&4).unwrap().clear();
+        assert_eq!(multimap, copy);
+
+        assert_eq!(&multimap[&0], &[0, 3]);
+        assert_eq!(multimap[&2].len(), 2);
+    }
 
-        let mut removal_counter = 0;
+    #[test]
+    #[should_panic(expected = "key not found in MultiMap")]
+    fn test_multimap_index_missing() {
+        let multimap = mod_three(3);
+        let _ = &multimap[&3];
+    }
 
-        for i in 0..10_000 {
-            multiset.insert(i % 100);
+    #[test]
+    fn test_multiset_iterators() {
+        let mut multiset = MultiSet::new();
+        for word in "the cat and the hat and the bat".split_whitespace() {
+            multiset.insert(word);
+        }
 
-            if i % 2 == 0 {
-                multiset.remove(&(removal_counter % 100));
-                removal_counter += 1;
-            }
+        let elements: Vec<_> = multiset.iter().collect();
+        assert_eq!(elements.len(), multiset.len());
+        for word in &elements {
+            assert_eq!(
+                elements.iter().filter(|w| *w == word).count(),
+                multiset.count(word)
+            );
         }
 
-        assert_eq!(5_000, multiset.len());
-        assert_eq!(50, multiset.count(&0));
+        let mut distinct: Vec<_> = multiset.iter_distinct().collect();
+        distinct.sort();
+        assert_eq!(
+            distinct,
+            [
+                (&"and", 2),
+                (&"bat", 1),
+                (&"cat", 1),
+                (&"hat", 1),
+                (&"the", 3)
+            ]
+        );
+
+        let empty: MultiSet<i32> = MultiSet::new();
+        assert_eq!(empty.iter().count(), 0);
+        assert_eq!(empty.iter_distinct().count(), 0);
     }
 }
 
-mod multimap_tests {
-    use crate::multimap::MultiMap;
+mod multiset_algebra_tests {
+    use crate::multiset::MultiSet;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// The number of random cases every law is checked on.
+    const CASES: usize = 500;
+
+    /// Creates a random multiset of small numbers, so that different multisets overlap a lot.
+    fn random_multiset(rng: &mut StdRng) -> MultiSet<u8> {
+        let mut multiset = MultiSet::new();
+        for value in 0..rng.gen_range(0..8) {
+            multiset.insert_n(value, rng.gen_range(0..5));
+        }
+        multiset
+    }
+
+    /// Checks a law on `CASES` random triples of multisets.
+    fn check(law: impl Fn(&MultiSet<u8>, &MultiSet<u8>, &MultiSet<u8>)) {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..CASES {
+            let a = random_multiset(&mut rng);
+            let b = random_multiset(&mut rng);
+            let c = random_multiset(&mut rng);
+            law(&a, &b, &c);
+        }
+    }
 
     #[test]
-    fn test_insert_duplicate_values() {
-        let mut multimap = MultiMap::new();
-        multimap.insert(1, "hello");
-        multimap.insert(1, "hello");
+    fn test_counts() {
+        check(|a, b, _| {
+            for value in 0..8 {
+                let (x, y) = (a.count(&value), b.count(&value));
+                assert_eq!(a.union(b).count(&value), x.max(y));
+                assert_eq!(a.intersection(b).count(&value), x.min(y));
+                assert_eq!(a.difference(b).count(&value), x.saturating_sub(y));
+                assert_eq!(a.sum(b).count(&value), x + y);
+                assert_eq!(a.symmetric_difference(b).count(&value), x.abs_diff(y));
+            }
+        });
+    }
 
-        assert_eq!(multimap.get_values(&1).unwrap(), &["hello", "hello"]);
+    #[test]
+    fn test_sizes() {
+        check(|a, b, _| {
+            assert_eq!(
+                a.union(b).len() + a.intersection(b).len(),
+                a.len() + b.len()
+            );
+            assert_eq!(a.sum(b).len(), a.len() + b.len());
+            assert_eq!(
+                a.symmetric_difference(b).len(),
+                a.difference(b).len() + b.difference(a).len()
+            );
+            assert_eq!(a.difference(b).len() + a.intersection(b).len(), a.len());
+        });
     }
 
     #[test]
-    fn test_insert_many_values() {
-        let mut multimap = MultiMap::new();
-        for i in 0..1000 {
-            multimap.insert(1, i);
-        }
+    fn test_commutative_and_associative() {
+        check(|a, b, c| {
+            assert_eq!(a.union(b), b.union(a));
+            assert_eq!(a.intersection(b), b.intersection(a));
+            assert_eq!(a.sum(b), b.sum(a));
+            assert_eq!(a.symmetric_difference(b), b.symmetric_difference(a));
+
+            assert_eq!(a.union(b).union(c), a.union(&b.union(c)));
+            assert_eq!(
+                a.intersection(b).intersection(c),
+                a.intersection(&b.intersection(c))
+            );
+            assert_eq!(a.sum(b).sum(c), a.sum(&b.sum(c)));
+        });
+    }
 
-        assert_eq!(multimap.get_values(&1).unwrap().len(), 1000);
+    #[test]
+    fn test_absorption_and_distribution() {
+        check(|a, b, c| {
+            assert_eq!(a.union(&a.intersection(b)), *a);
+            assert_eq!(a.intersection(&a.union(b)), *a);
+            assert_eq!(
+                a.intersection(&b.union(c)),
+                a.intersection(b).union(&a.intersection(c))
+            );
+            assert_eq!(
+                a.union(&b.intersection(c)),
+                a.union(b).intersection(&a.union(c))
+            );
+            assert_eq!(a.difference(b).sum(&a.intersection(b)), *a);
+            assert_eq!(
+                a.symmetric_difference(b),
+                a.difference(b).sum(&b.difference(a))
+            );
+        });
     }
 
     #[test]
-    fn test_remove_nonexistent_key() {
-        let mut multimap: MultiMap<i32, Option<()>> = MultiMap::new();
-        assert_eq!(multimap.get_values(&1), None);
-        assert_eq!(multimap.remove_key(&1), None);
+    fn test_subsets() {
+        check(|a, b, _| {
+            let union = a.union(b);
+            let intersection = a.intersection(b);
+            assert!(intersection.is_subset(a) && a.is_subset(&union));
+            assert!(union.is_superset(b) && b.is_superset(&intersection));
+            assert!(a.is_subset(a) && a.is_superset(a));
+
+            // Each of these is another way of saying that `a` is a subset of `b`.
+            let subset = a.is_subset(b);
+            assert_eq!(subset, a.difference(b).is_empty());
+            assert_eq!(subset, union == *b);
+            assert_eq!(subset, intersection == *a);
+            assert_eq!(subset, b.is_superset(a));
+        });
     }
 
     #[test]
-    fn test_remove_value_from_multiple() {
-        let mut multimap = MultiMap::new();
+    fn test_insert_remove_n() {
+        check(|a, b, _| {
+            let mut multiset = a.clone();
+            for (value, count) in b.iter_distinct() {
+                multiset.insert_n(*value, count);
+            }
+            assert_eq!(multiset, a.sum(b));
 
-        multimap.insert(1, "hello");
-        assert!(multimap.remove_value(&1, &"hello"));
-        assert!(multimap.get_values(&1).is_none());
+            for (value, count) in b.iter_distinct() {
+                assert_eq!(multiset.remove_n(value, count), count);
+            }
+            assert_eq!(multiset, *a);
 
-        multimap.insert(1, "hello");
-        multimap.insert(1, "world");
-        assert!(multimap.remove_value(&1, &"hello"));
-        assert_eq!(multimap.get_values(&1).unwrap(), &["world"]);
+            for (value, count) in b.iter_distinct() {
+                let expected = count.min(multiset.count(value));
+                assert_eq!(multiset.remove_n(value, count), expected);
+            }
+            assert_eq!(multiset, a.difference(b));
+            assert_eq!(multiset.len(), multiset.iter().count());
+        });
     }
 
     #[test]
-    fn test_large_insert_remove() {
-        let mut multimap = MultiMap::new();
+    fn test_most_common() {
+        check(|a, _, _| {
+            let distinct = a.iter_distinct().count();
+            for n in 0..=distinct + 1 {
+                let most_common = a.most_common(n);
+                assert_eq!(most_common.len(), n.min(distinct));
+                assert!(most_common.windows(2).all(|w| w[0].1 >= w[1].1));
+                assert!(
+                    most_common
+                        .iter()
+                        .all(|&(value, count)| a.count(value) == count)
+                );
+
+                // Nothing that was left out is more common than what was kept.
+                if let Some(&(_, least)) = most_common.last() {
+                    let kept = |value: &u8| most_common.iter().any(|(v, _)| *v == value);
+                    assert!(
+                        a.iter_distinct()
+                            .all(|(value, count)| kept(value) || count <= least)
+                    );
+                }
+            }
+        });
+    }
+}
+
+mod value_container_tests {
+    use crate::multimap::{MultiMap, SetMultiMap};
+    use crate::values::{ValueContainer, ValueSet};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    /// Inserts the same pairs into a multimap with any container, and returns how many were added.
+    fn insert_twice<C: ValueContainer<i32>>(multimap: &mut MultiMap<&str, i32, C>) -> usize {
+        let pairs = [("a", 1), ("a", 2), ("b", 3)];
+        pairs
+            .iter()
+            .chain(&pairs)
+            .filter(|&&(key, value)| multimap.insert(key, value))
+            .count()
+    }
 
-        // Insert 100 values for 1000 keys
-        for i in 0..1000 {
-            for j in 0..100 {
-                multimap.insert(i, j);
+    #[test]
+    fn test_vec_and_set_containers() {
+        let mut vec_map = MultiMap::new();
+        assert_eq!(insert_twice(&mut vec_map), 6);
+        assert_eq!(vec_map.get_values(&"a").unwrap(), &[1, 2, 1, 2]);
+        assert_eq!(vec_map.len(), 6);
+
+        let mut set_map = SetMultiMap::default();
+        assert_eq!(insert_twice(&mut set_map), 3);
+        assert_eq!(set_map.get_values(&"a").unwrap(), &[1, 2]);
+        assert_eq!(set_map.len(), 3);
+
+        assert!(set_map.remove_value(&"a", &1));
+        assert!(!set_map.remove_value(&"a", &1));
+        assert_eq!(set_map.count(&"a"), 1);
+        assert!(set_map.remove_value(&"b", &3));
+        assert!(!set_map.contains(&"b"));
+
+        let values = set_map.remove_key(&"a").unwrap();
+        assert!(values.contains(&2));
+        assert!(set_map.is_empty());
+    }
+
+    #[test]
+    fn test_set_multimap_collection_traits() {
+        let a: SetMultiMap<_, _> = [(1, "x"), (1, "y"), (2, "z"), (1, "x")]
+            .into_iter()
+            .collect();
+        let b: SetMultiMap<_, _> = [(2, "z"), (1, "y"), (1, "x")].into_iter().collect();
+
+        // The order of the values does not matter for sets.
+        assert_eq!(a.get_values(&1).unwrap(), &["x", "y"]);
+        assert_eq!(b.get_values(&1).unwrap(), &["y", "x"]);
+        assert_eq!(a, b);
+        assert_eq!(&a[&2], &["z"]);
+        assert_eq!(a.iter().count(), 3);
+
+        let mut c = a.clone();
+        c.entry(1).insert("x");
+        c.entry(3).or_insert("w");
+        assert_eq!(c.len(), 4);
+        c.retain(|_, &value| value != "x");
+        assert_eq!(c.get_values(&1).unwrap(), &["y"]);
+        assert_ne!(a, c);
+
+        let mut groups: Vec<_> = c
+            .clone()
+            .into_groups()
+            .map(|(key, values)| (key, values.len()))
+            .collect();
+        groups.sort();
+        assert_eq!(groups, [(1, 1), (2, 1), (3, 1)]);
+
+        let mut owned: Vec<_> = c.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, [(1, "y"), (2, "z"), (3, "w")]);
+    }
+
+    #[test]
+    fn test_value_set_matches_hash_set() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut set = ValueSet::new();
+        let mut expected = HashSet::new();
+        // The values in the order they were inserted in.
+        let mut order = Vec::new();
+
+        for _ in 0..10_000 {
+            let value = rng.gen_range(0..50);
+            match rng.gen_range(0..3) {
+                0 => {
+                    assert_eq!(set.insert(value), expected.insert(value));
+                    if !order.contains(&value) {
+                        order.push(value);
+                    }
+                }
+                1 => {
+                    assert_eq!(set.remove(&value), expected.remove(&value));
+                    order.retain(|&v| v != value);
+                }
+                _ => {
+                    set.retain(|v| v % 7 != value % 7);
+                    expected.retain(|v| v % 7 != value % 7);
+                    order.retain(|v| v % 7 != value % 7);
+                }
             }
+
+            assert_eq!(set.len(), expected.len());
+            assert_eq!(set.contains(&value), expected.contains(&value));
+            assert_eq!(set.as_slice(), order);
         }
 
-        // Assert the total number of key-value pairs (indirectly)
-        let mut total_count = 0;
-        for (_, values) in multimap.inner.iter() {
-            total_count += values.len();
+        let values: HashSet<_> = set.iter().copied().collect();
+        assert_eq!(values, expected);
+        assert_eq!(set.as_slice().len(), expected.len());
+    }
+}
+
+mod bimultimap_tests {
+    use crate::bimultimap::BiMultiMap;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    /// Checks that the forward and reverse indexes agree with each other and with `expected`.
+    fn check_invariants(map: &BiMultiMap<u8, u8>, expected: &HashSet<(u8, u8)>) {
+        let forward: HashSet<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        let reverse: HashSet<_> = map.iter_reverse().map(|(&v, &k)| (k, v)).collect();
+        assert_eq!(&forward, expected);
+        assert_eq!(&reverse, expected);
+        assert_eq!(map.len(), expected.len());
+        assert_eq!(map.is_empty(), expected.is_empty());
+        assert!(expected.iter().all(|(k, v)| map.contains(k, v)));
+
+        for &key in map.keys() {
+            let values = map.get_values(&key).unwrap();
+            assert!(!values.is_empty());
+            for value in values {
+                assert!(map.get_keys(value).unwrap().contains(&key));
+            }
         }
-        assert_eq!(total_count, 100000);
-
-        // Remove half of the values for each key using indices
-        for i in 0..1000 {
-            if let Some(values) = multimap.get_values_mut(&i) {
-                let mut remaining_count = values.len();
-                for _ in 0..(remaining_count / 2) {
-                    let index_to_remove = 0; // Remove from the beginning for simplicity
-                    values.remove(index_to_remove);
-                    remaining_count -= 1;
-                }
-                assert_eq!(remaining_count, 50);
+        for &value in map.values() {
+            let keys = map.get_keys(&value).unwrap();
+            assert!(!keys.is_empty());
+            for key in keys {
+                assert!(map.get_values(key).unwrap().contains(&value));
             }
         }
+    }
+
+    #[test]
+    fn test_lookups_both_ways() {
+        let mut map = BiMultiMap::new();
+        assert!(map.insert("a", 1));
+        assert!(map.insert("a", 2));
+        assert!(map.insert("b", 2));
+        assert!(!map.insert("a", 1));
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get_values(&"a").unwrap(), &[1, 2]);
+        assert_eq!(map.get_keys(&2).unwrap(), &["a", "b"]);
+        assert!(map.contains(&"b", &2));
+        assert!(!map.contains(&"b", &1));
+        assert!(map.get_keys(&3).is_none());
+
+        assert_eq!(map.remove_key(&"a").unwrap().len(), 2);
+        assert!(map.get_keys(&1).is_none());
+        assert!(!map.contains_value(&1));
+        assert_eq!(map.get_keys(&2).unwrap(), &["b"]);
+        assert!(map.remove_key(&"a").is_none());
+
+        assert!(map.remove_value(&"b", &2));
+        assert!(map.is_empty());
+        assert!(!map.contains_key(&"b"));
+    }
+
+    #[test]
+    fn test_random_operations_keep_indexes_consistent() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let mut map = BiMultiMap::new();
+            let mut expected = HashSet::new();
+
+            for _ in 0..200 {
+                let key = rng.gen_range(0..10);
+                let value = rng.gen_range(0..10);
+                match rng.gen_range(0..10) {
+                    0..5 => assert_eq!(map.insert(key, value), expected.insert((key, value))),
+                    5..8 => assert_eq!(
+                        map.remove_value(&key, &value),
+                        expected.remove(&(key, value))
+                    ),
+                    8 => {
+                        let removed = map.remove_key(&key);
+                        let count = expected.iter().filter(|&&(k, _)| k == key).count();
+                        assert_eq!(
+                            removed.map(|values| values.len()),
+                            (count > 0).then_some(count)
+                        );
+                        expected.retain(|&(k, _)| k != key);
+                    }
+                    _ => {
+                        let removed = map.remove_keys(&value);
+                        let count = expected.iter().filter(|&&(_, v)| v == value).count();
+                        assert_eq!(removed.map(|keys| keys.len()), (count > 0).then_some(count));
+                        expected.retain(|&(_, v)| v != value);
+                    }
+                }
+
+                check_invariants(&map, &expected);
+            }
 
-        // Assert the remaining number of key-value pairs (indirectly)
-        total_count = 0;
-        for (_, values) in multimap.inner.iter() {
-            total_count += values.len();
+            let rebuilt: BiMultiMap<_, _> = expected.iter().copied().collect();
+            assert_eq!(rebuilt, map);
         }
-        assert_eq!(total_count, 50000);
     }
 }
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A container for the values associated with one key of a [`MultiMap`](crate::multimap::MultiMap).
+///
+/// The container decides what happens when the same value is inserted twice:
+///
+/// - A [`Vec`] keeps every copy, in the order they were inserted. Removing a value has to search
+///   for it, which takes linear time.
+/// - A [`ValueSet`] only keeps one copy of each value, also in the order they were inserted. It
+///   can insert and look up values in constant time, but removing a value still takes linear time.
+///
+/// Either way, the values can be viewed as a slice with [`ValueContainer::as_slice`].
+pub trait ValueContainer<V>: Default {
+    /// Adds a value. Returns `false` if the container does not allow duplicates and already
+    /// contained the value, and `true` otherwise.
+    fn insert(&mut self, value: V) -> bool;
+
+    /// Removes one copy of a value. Returns `true` if the value was removed, `false` if it was not
+    /// found.
+    fn remove(&mut self, value: &V) -> bool;
+
+    /// Keeps only the values for which `f` returns `true`.
+    fn retain<F: FnMut(&V) -> bool>(&mut self, f: F);
+
+    /// Returns the values as a slice.
+    fn as_slice(&self) -> &[V];
+
+    /// Counts the values.
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Checks if there are no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<V: Eq> ValueContainer<V> for Vec<V> {
+    fn insert(&mut self, value: V) -> bool {
+        self.push(value);
+        true
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    fn remove(&mut self, value: &V) -> bool {
+        // Note that we can do the below much more succinctly with
+        // `self.iter().position(|x| x == value)`, but since we haven't talked about iterators
+        // yet, you don't have to worry about that now.
+
+        let mut found_index = None;
+        for i in 0..self.len() {
+            if self[i] == *value {
+                found_index = Some(i);
+                break;
+            }
+        }
+
+        let Some(i) = found_index else {
+            return false;
+        };
+
+        // Since we've found the index of the value, we can remove it.
+        Vec::remove(self, i);
+        true
+    }
+
+    fn retain<F: FnMut(&V) -> bool>(&mut self, f: F) {
+        Vec::retain(self, f);
+    }
+
+    fn as_slice(&self) -> &[V] {
+        self
+    }
+}
+
+/// A set of values that remembers the order they were inserted in.
+///
+/// Every value is stored in a [`Vec`], and a [`HashMap`] keeps track of where each value is in the
+/// [`Vec`], so inserting and looking up a value take constant time. Removing a value shifts every
+/// value after it down by one to keep them in order, so it takes linear time, like it does for a
+/// [`Vec`].
+///
+/// Since each value is stored twice, values need to be [`Clone`].
+///
+/// # Examples
+///
+/// ```
+/// # use multilab_ref::values::ValueSet;
+/// #
+/// let mut set = ValueSet::new();
+/// assert!(set.insert("a"));
+/// assert!(set.insert("b"));
+/// assert!(set.insert("c"));
+/// assert!(!set.insert("a"));
+/// assert_eq!(set.as_slice(), &["a", "b", "c"]);
+///
+/// assert!(set.remove(&"a"));
+/// assert_eq!(set.as_slice(), &["b", "c"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValueSet<V> {
+    /// The values, in insertion order.
+    values: Vec<V>,
+    /// The index of every value in `values`.
+    positions: HashMap<V, usize>,
+}
+
+impl<V: Hash + Eq + Clone> ValueSet<V> {
+    /// Creates a new, empty [`ValueSet`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value. Returns `false` if the value was already in the set.
+    pub fn insert(&mut self, value: V) -> bool {
+        if self.positions.contains_key(&value) {
+            return false;
+        }
+
+        self.positions.insert(value.clone(), self.values.len());
+        self.values.push(value);
+        true
+    }
+
+    /// Removes a value. Returns `false` if the value was not in the set.
+    ///
+    /// The values after the removed value keep their order, and each move down by one.
+    pub fn remove(&mut self, value: &V) -> bool {
+        let Some(i) = self.positions.remove(value) else {
+            return false;
+        };
+
+        self.values.remove(i);
+        for moved in &self.values[i..] {
+            *self
+                .positions
+                .get_mut(moved)
+                .expect("every value has a position") -= 1;
+        }
+        true
+    }
+
+    /// Checks if a value is in the set.
+    pub fn contains(&self, value: &V) -> bool {
+        self.positions.contains_key(value)
+    }
+
+    /// Keeps only the values for which `f` returns `true`. The values that are kept stay in the
+    /// same order.
+    pub fn retain<F: FnMut(&V) -> bool>(&mut self, f: F) {
+        self.values.retain(f);
+        self.positions.clear();
+        for (i, value) in self.values.iter().enumerate() {
+            self.positions.insert(value.clone(), i);
+        }
+    }
+
+    /// Returns the values as a slice.
+    pub fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Counts the values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over the values.
+    pub fn iter(&self) -> std::slice::Iter<'_, V> {
+        self.values.iter()
+    }
+}
+
+impl<V> Default for ValueSet<V> {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+}
+
+impl<V: Hash + Eq + Clone> ValueContainer<V> for ValueSet<V> {
+    fn insert(&mut self, value: V) -> bool {
+        ValueSet::insert(self, value)
+    }
+
+    fn remove(&mut self, value: &V) -> bool {
+        ValueSet::remove(self, value)
+    }
+
+    fn retain<F: FnMut(&V) -> bool>(&mut self, f: F) {
+        ValueSet::retain(self, f)
+    }
+
+    fn as_slice(&self) -> &[V] {
+        ValueSet::as_slice(self)
+    }
+}
+
+/// Iterates over the values, in the same order as [`ValueSet::as_slice`].
+impl<V> IntoIterator for ValueSet<V> {
+    type Item = V;
+    type IntoIter = std::vec::IntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+/// Two [`ValueSet`]s are equal if they contain the same values, in any order.
+impl<V: Hash + Eq> PartialEq for ValueSet<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values.len() == other.values.len()
+            && self.values.iter().all(|v| other.positions.contains_key(v))
+    }
+}
+
+impl<V: Hash + Eq> Eq for ValueSet<V> {}
@@ -12,6 +12,8 @@
 //! Diamonds.
 
 use derivative::Derivative;
+use std::fmt::Display;
+use std::str::FromStr;
 
 /// Represents a standard playing card with a suit and a rank.
 ///
@@ -106,8 +108,98 @@ impl Card {
     pub fn rank(&self) -> Rank {
         self.rank
     }
+
+    /// A unique number from 0 to 51 for every card. Unlike [`Card`] equality, this does not ignore
+    /// the suit.
+    ///
+    /// Cards are numbered by rank first and then in the order of [`Suit::ALL`], so cards with
+    /// smaller indexes never have higher ranks.
+    pub(crate) fn index(self) -> usize {
+        self.rank.index() * Suit::ALL.len() + self.suit as usize
+    }
+
+    /// The card with the given [`Card::index`].
+    pub(crate) fn from_index(index: usize) -> Self {
+        Card::new(
+            Suit::ALL[index % Suit::ALL.len()],
+            Rank::ALL[index / Suit::ALL.len()],
+        )
+    }
+}
+
+/// Formats a card in short notation: its rank followed by its suit, such as `Ts` for the Ten of
+/// Spades or `Ah` for the Ace of Hearts.
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+/// Parses a card in short notation, such as `Ts` or `Ah`. This is the inverse of the [`Display`]
+/// implementation, except that ranks and suits are also accepted in either case.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::card::{Card, Suit, Rank};
+/// #
+/// let card: Card = "Ts".parse().unwrap();
+/// assert_eq!(card.suit(), Suit::Spade);
+/// assert_eq!(card.rank(), Rank::Ten);
+/// assert_eq!(card.to_string(), "Ts");
+///
+/// assert!("1s".parse::<Card>().is_err());
+/// ```
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(rank), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(ParseCardError::InvalidLength(s.to_string()));
+        };
+
+        let rank =
+            Rank::from_char(rank).ok_or_else(|| ParseCardError::InvalidRank(s.to_string()))?;
+        let suit =
+            Suit::from_char(suit).ok_or_else(|| ParseCardError::InvalidSuit(s.to_string()))?;
+
+        Ok(Card::new(suit, rank))
+    }
+}
+
+/// The error returned when parsing a [`Card`] (or a [`Hand`] of them) fails. Every variant holds
+/// the token that could not be parsed.
+///
+/// [`Hand`]: crate::hand::Hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    /// The token was not exactly two characters long.
+    InvalidLength(String),
+    /// The first character of the token was not a rank (`2`-`9`, `T`, `J`, `Q`, `K` or `A`).
+    InvalidRank(String),
+    /// The second character of the token was not a suit (`d`, `c`, `h` or `s`).
+    InvalidSuit(String),
+    /// The same card appeared more than once.
+    DuplicateCard(String),
+    /// There were not exactly as many cards as expected. Holds the whole input.
+    WrongCardCount(String),
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength(token) => write!(f, "`{token}` is not a two-character card"),
+            Self::InvalidRank(token) => write!(f, "`{token}` does not start with a valid rank"),
+            Self::InvalidSuit(token) => write!(f, "`{token}` does not end with a valid suit"),
+            Self::DuplicateCard(token) => write!(f, "`{token}` appears more than once"),
+            Self::WrongCardCount(input) => write!(f, "`{input}` has the wrong number of cards"),
+        }
+    }
 }
 
+impl std::error::Error for ParseCardError {}
+
 /// Represents the four possible suits in a standard deck of playing cards.
 ///
 /// The suits are ordered in the traditional manner:
@@ -137,6 +229,35 @@ pub enum Suit {
     Spade,
 }
 
+impl Suit {
+    /// All four suits, from lowest to highest.
+    pub const ALL: [Suit; 4] = [Suit::Diamond, Suit::Club, Suit::Heart, Suit::Spade];
+
+    /// Returns the suit written as `c` in short notation, ignoring case.
+    fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            'd' => Some(Suit::Diamond),
+            'c' => Some(Suit::Club),
+            'h' => Some(Suit::Heart),
+            's' => Some(Suit::Spade),
+            _ => None,
+        }
+    }
+}
+
+/// Formats a suit as a single lowercase letter: `d`, `c`, `h` or `s`.
+impl Display for Suit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Suit::Diamond => 'd',
+            Suit::Club => 'c',
+            Suit::Heart => 'h',
+            Suit::Spade => 's',
+        };
+        write!(f, "{c}")
+    }
+}
+
 /// Represents the rank of a playing card, which can be either a number card (2-10) or a face card
 /// (Ace, King, Queen, Jack).
 ///
@@ -178,3 +299,66 @@ pub enum Rank {
     /// The Ace card, which often has special rules in many card games.
     Ace = 14,
 }
+
+impl Rank {
+    /// All thirteen ranks, from lowest to highest.
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
+    /// The characters used for each rank in short notation, from Two to Ace.
+    const SYMBOLS: [char; 13] = [
+        '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+    ];
+
+    /// The English names of each rank, from Two to Ace.
+    const NAMES: [&str; 13] = [
+        "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack", "Queen",
+        "King", "Ace",
+    ];
+
+    /// Returns the English name of the rank, such as `"Ten"` or `"Ace"`.
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self.index()]
+    }
+
+    /// Returns the plural English name of the rank, such as `"Sixes"` or `"Aces"`.
+    pub fn plural(self) -> String {
+        match self {
+            Rank::Six => "Sixes".to_string(),
+            _ => format!("{}s", self.name()),
+        }
+    }
+
+    /// Returns the rank written as `c` in short notation, ignoring case.
+    pub(crate) fn from_char(c: char) -> Option<Self> {
+        let c = c.to_ascii_uppercase();
+        let index = Self::SYMBOLS.iter().position(|&symbol| symbol == c)?;
+
+        Some(Self::ALL[index])
+    }
+
+    /// The position of the rank in [`Rank::ALL`], from 0 (Two) to 12 (Ace).
+    pub(crate) fn index(self) -> usize {
+        self as usize - Rank::Two as usize
+    }
+}
+
+/// Formats a rank as a single character: `2`-`9`, `T`, `J`, `Q`, `K` or `A`.
+impl Display for Rank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Self::SYMBOLS[self.index()])
+    }
+}
@@ -0,0 +1,146 @@
+//! This module contains the definition and implementation of [`Deck`], a pile of [`Card`]s that
+//! can be shuffled and dealt from.
+//!
+//! Besides the standard 52-card deck, a [`Deck`] can also be built for short-deck poker (where all
+//! cards below a certain rank are removed) or out of several standard decks shuffled together.
+
+use crate::card::{Card, Rank, Suit};
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// A pile of cards that can be shuffled and dealt from the top.
+///
+/// Since [`Card`] equality ignores suits, every method here that looks for a specific card
+/// compares both the rank and the suit.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::card::{Card, Rank, Suit};
+/// # use pokerlab_ref::deck::Deck;
+/// # use rand::SeedableRng;
+/// # use rand::rngs::StdRng;
+/// #
+/// let mut deck = Deck::new();
+/// deck.shuffle(&mut StdRng::seed_from_u64(42));
+///
+/// // Take our pocket cards out of the deck, and deal the flop.
+/// assert!(deck.remove(&Card::new(Suit::Spade, Rank::Ace)));
+/// assert!(deck.remove(&Card::new(Suit::Heart, Rank::Ace)));
+/// deck.burn();
+/// let flop = deck.deal(3).unwrap();
+///
+/// assert_eq!(flop.len(), 3);
+/// assert_eq!(deck.remaining(), 52 - 2 - 1 - 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Deck {
+    /// The cards left in the deck. The top of the deck is the end of the vector.
+    cards: Vec<Card>,
+}
+
+impl Deck {
+    /// Creates a standard, unshuffled 52-card deck.
+    pub fn new() -> Self {
+        Self::with_ranks(Rank::Two, 1)
+    }
+
+    /// Creates an unshuffled 36-card short deck, where every card below a Six is removed.
+    pub fn short() -> Self {
+        Self::with_ranks(Rank::Six, 1)
+    }
+
+    /// Creates an unshuffled deck out of `decks` copies of every card ranked `lowest` or higher.
+    ///
+    /// For example, `Deck::with_ranks(Rank::Two, 1)` is a standard deck, while
+    /// `Deck::with_ranks(Rank::Two, 6)` is the six-deck shoe used in many casino games.
+    pub fn with_ranks(lowest: Rank, decks: usize) -> Self {
+        let single = Suit::ALL.into_iter().flat_map(|suit| {
+            Rank::ALL
+                .into_iter()
+                .filter(move |&rank| rank >= lowest)
+                .map(move |rank| Card::new(suit, rank))
+        });
+
+        let cards = (0..decks).flat_map(|_| single.clone()).collect();
+        Self { cards }
+    }
+
+    /// Creates a deck out of any cards, where the last card is on top.
+    pub fn from_cards(cards: Vec<Card>) -> Self {
+        Self { cards }
+    }
+
+    /// Shuffles the cards left in the deck.
+    ///
+    /// Passing in a seeded RNG (such as [`rand::rngs::StdRng::seed_from_u64`]) makes the shuffle
+    /// reproducible, which is useful for simulations and tests.
+    ///
+    /// [`rand::rngs::StdRng::seed_from_u64`]: rand::SeedableRng::seed_from_u64
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+    }
+
+    /// Deals `n` cards off the top of the deck, in the order they were dealt.
+    ///
+    /// Returns `None` (and leaves the deck untouched) if there are fewer than `n` cards left.
+    pub fn deal(&mut self, n: usize) -> Option<Vec<Card>> {
+        let start = self.cards.len().checked_sub(n)?;
+        Some(self.cards.drain(start..).rev().collect())
+    }
+
+    /// Deals a single card off the top of the deck, or returns `None` if the deck is empty.
+    pub fn deal_one(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    /// Discards the top card of the deck, returning it (or `None` if the deck is empty).
+    pub fn burn(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    /// Removes one copy of a known card from the deck, such as a card that has already been dealt
+    /// to a player. Returns whether the card was in the deck.
+    pub fn remove(&mut self, card: &Card) -> bool {
+        match self.position(card) {
+            Some(i) => {
+                self.cards.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether the deck contains at least one copy of `card`.
+    pub fn contains(&self, card: &Card) -> bool {
+        self.position(card).is_some()
+    }
+
+    /// Returns the number of cards left in the deck.
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Returns whether there are no cards left in the deck.
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Returns the cards left in the deck, where the last card is on top.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Finds the index of a card, comparing both rank and suit.
+    fn position(&self, card: &Card) -> Option<usize> {
+        self.cards
+            .iter()
+            .rposition(|c| c.rank() == card.rank() && c.suit() == card.suit())
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,412 @@
+//! Module for computing the equity of hold'em hands: how often each player wins, ties, or loses
+//! once all five community cards are out.
+//!
+//! When only a few runouts (ways to finish the board) are left, we enumerate every one of them and
+//! the results are exact. Otherwise (for example, preflop, where there are over a million runouts
+//! even with just two players), we deal a large number of random runouts from a seeded RNG instead.
+//! Either way, the work is split into chunks that are evaluated in parallel with [`rayon`].
+//!
+//! Showdowns are decided with [`evaluate`], which orders hands exactly like [`PokerHand`].
+//!
+//! [`PokerHand`]: crate::hand::PokerHand
+
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::eval::evaluate;
+use crate::hand::binomial;
+use crate::range::Range;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+/// The number of runouts evaluated by each parallel task.
+const CHUNK_SIZE: u64 = 4096;
+
+/// Settings that control how equity is computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    /// Every runout is enumerated if there are at most this many of them.
+    pub max_exact_runouts: u64,
+    /// The number of random runouts to deal when there are too many to enumerate. If this is 0,
+    /// only spots with few enough runouts to enumerate can be computed.
+    pub samples: u64,
+    /// The seed of the RNG used to deal random runouts.
+    pub seed: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            max_exact_runouts: 100_000,
+            samples: 200_000,
+            seed: 0,
+        }
+    }
+}
+
+/// How one player fared over all of the runouts that were evaluated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Equity {
+    /// The number of runouts the player won outright.
+    wins: u64,
+    /// The number of runouts the player tied for the best hand.
+    ties: u64,
+    /// The total fraction of the pot the player won, where a tie splits the pot evenly.
+    pot_share: f64,
+    /// The number of runouts that were evaluated.
+    runouts: u64,
+}
+
+impl Equity {
+    /// An equity with no runouts evaluated yet.
+    fn empty() -> Self {
+        Self {
+            wins: 0,
+            ties: 0,
+            pot_share: 0.0,
+            runouts: 0,
+        }
+    }
+
+    /// The probability that the player wins outright.
+    pub fn win(&self) -> f64 {
+        self.wins as f64 / self.runouts as f64
+    }
+
+    /// The probability that the player ties for the best hand.
+    pub fn tie(&self) -> f64 {
+        self.ties as f64 / self.runouts as f64
+    }
+
+    /// The probability that the player loses.
+    pub fn loss(&self) -> f64 {
+        (self.runouts - self.wins - self.ties) as f64 / self.runouts as f64
+    }
+
+    /// The fraction of the pot the player wins on average, where ties split the pot evenly.
+    pub fn equity(&self) -> f64 {
+        self.pot_share / self.runouts as f64
+    }
+
+    /// The number of runouts that were evaluated.
+    pub fn runouts(&self) -> u64 {
+        self.runouts
+    }
+}
+
+/// The result of an equity calculation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Odds {
+    /// The equity of every player, in the order they were given.
+    players: Vec<Equity>,
+    /// Whether every runout was enumerated.
+    exact: bool,
+}
+
+impl Odds {
+    /// Returns the equity of every player, in the order they were given.
+    pub fn players(&self) -> &[Equity] {
+        &self.players
+    }
+
+    /// Returns whether every runout was enumerated, rather than sampled.
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+/// Computes the equity of each player's hole cards, given the community cards dealt so far.
+///
+/// Returns `None` if there are fewer than two players, more than five board cards, any duplicate
+/// cards, or not enough cards left in the deck to finish the board. Also returns `None` if there
+/// are too many runouts to enumerate but [`Settings::samples`] is 0, since there would be nothing
+/// to compute the equity from.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::card::{Card, Rank, Suit};
+/// # use pokerlab_ref::equity::{Settings, calculate};
+/// #
+/// let aces = [Card::new(Suit::Spade, Rank::Ace), Card::new(Suit::Heart, Rank::Ace)];
+/// let kings = [Card::new(Suit::Spade, Rank::King), Card::new(Suit::Heart, Rank::King)];
+/// let flop = [
+///     Card::new(Suit::Club, Rank::King),
+///     Card::new(Suit::Diamond, Rank::Seven),
+///     Card::new(Suit::Club, Rank::Two),
+/// ];
+///
+/// let odds = calculate(&[aces, kings], &flop, &Settings::default()).unwrap();
+/// assert!(odds.is_exact());
+/// assert!(odds.players()[1].win() > 0.9);
+/// ```
+pub fn calculate(players: &[[Card; 2]], board: &[Card], settings: &Settings) -> Option<Odds> {
+    if players.len() < 2 || board.len() > 5 {
+        return None;
+    }
+
+    // Take every known card out of the deck, which also catches duplicates.
+    let mut deck = Deck::new();
+    for card in players.iter().flatten().chain(board) {
+        if !deck.remove(card) {
+            return None;
+        }
+    }
+
+    let missing = 5 - board.len();
+    if deck.remaining() < missing {
+        return None;
+    }
+
+    let total = binomial(deck.remaining() as u64, missing as u64);
+    let exact = total <= settings.max_exact_runouts;
+    let runouts = if exact { total } else { settings.samples };
+    if runouts == 0 {
+        return None;
+    }
+
+    let players_list = players.to_vec();
+    let totals = simulate(
+        players.len(),
+        runouts,
+        settings.seed,
+        |index, rng, holes, full_board| {
+            holes.clone_from(&players_list);
+            full_board.extend_from_slice(board);
+
+            if exact {
+                let cards = deck.cards();
+                let combination = nth_combination(index, cards.len(), missing);
+                full_board.extend(combination.iter().map(|&i| cards[i]));
+            } else {
+                let mut runout = deck.clone();
+                runout.shuffle(rng);
+                full_board.extend(runout.deal(missing).expect("deck has enough cards"));
+            }
+        },
+    );
+
+    Some(Odds {
+        players: totals,
+        exact,
+    })
+}
+
+/// Computes the equity of each player's [`Range`], given the community cards dealt so far.
+///
+/// Every sample deals each player a random combo out of their range (skipping combos that use
+/// cards that were already dealt), and then a random runout. Unless every range holds exactly one
+/// combo (in which case this is the same as [`calculate`]), the results are always sampled.
+///
+/// Returns `None` if there are fewer than two players, more than five board cards, duplicate board
+/// cards, not enough cards to deal every player and finish the board, or if there is no way to deal
+/// every player a combo from their range. Like [`calculate`], it also returns `None` if the results
+/// would have to be sampled but [`Settings::samples`] is 0.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::equity::{Settings, calculate_ranges};
+/// # use pokerlab_ref::range::Range;
+/// #
+/// let ranges: [Range; 2] = ["QQ+, AKs".parse().unwrap(), "22-99".parse().unwrap()];
+/// let settings = Settings { samples: 10_000, ..Settings::default() };
+///
+/// let odds = calculate_ranges(&ranges, &[], &settings).unwrap();
+/// assert!(odds.players()[0].equity() > 0.7);
+/// ```
+pub fn calculate_ranges(ranges: &[Range], board: &[Card], settings: &Settings) -> Option<Odds> {
+    if ranges.len() < 2 || board.len() > 5 || 2 * ranges.len() + 5 > 52 {
+        return None;
+    }
+
+    let mut deck = Deck::new();
+    for card in board {
+        if !deck.remove(card) {
+            return None;
+        }
+    }
+
+    // Combos that use a board card can never be dealt.
+    let combos: Vec<Vec<[Card; 2]>> = ranges
+        .iter()
+        .map(|range| {
+            let mut range = range.clone();
+            range.remove_dead(board);
+            range.iter().collect()
+        })
+        .collect();
+
+    if combos.iter().all(|combos| combos.len() == 1) {
+        let players: Vec<[Card; 2]> = combos.iter().map(|combos| combos[0]).collect();
+        return calculate(&players, board, settings);
+    }
+    if settings.samples == 0 || !can_deal(&combos, &mut Vec::new()) {
+        return None;
+    }
+
+    let missing = 5 - board.len();
+    let totals = simulate(
+        ranges.len(),
+        settings.samples,
+        settings.seed,
+        |_, rng, holes, full_board| {
+            // Keep drawing until every player has a combo that doesn't clash with anyone else's.
+            loop {
+                holes.clear();
+                for combos in &combos {
+                    let combo = combos[rng.gen_range(0..combos.len())];
+                    if clashes(holes, combo) {
+                        break;
+                    }
+                    holes.push(combo);
+                }
+                if holes.len() == combos.len() {
+                    break;
+                }
+            }
+
+            let mut runout = deck.clone();
+            for card in holes.iter().flatten() {
+                runout.remove(card);
+            }
+            runout.shuffle(rng);
+
+            full_board.extend_from_slice(board);
+            full_board.extend(runout.deal(missing).expect("deck has enough cards"));
+        },
+    );
+
+    Some(Odds {
+        players: totals,
+        exact: false,
+    })
+}
+
+/// Evaluates `runouts` showdowns between `num_players` players, split into chunks that run in
+/// parallel, and adds up how every player did.
+///
+/// For every runout, `deal` is given the runout's index and an RNG, and fills in the players' hole
+/// cards and the complete board.
+fn simulate<F>(num_players: usize, runouts: u64, seed: u64, deal: F) -> Vec<Equity>
+where
+    F: Fn(u64, &mut StdRng, &mut Vec<[Card; 2]>, &mut Vec<Card>) + Sync,
+{
+    // Every chunk gets its own RNG seed up front, so the results do not depend on how rayon
+    // schedules the chunks.
+    let mut rng = StdRng::seed_from_u64(seed);
+    let chunks: Vec<(u64, u64)> = (0..runouts.div_ceil(CHUNK_SIZE))
+        .map(|i| (i * CHUNK_SIZE, rng.r#gen()))
+        .collect();
+
+    let tallies: Vec<Vec<Equity>> = chunks
+        .into_par_iter()
+        .map(|(start, seed)| {
+            let end = (start + CHUNK_SIZE).min(runouts);
+            let mut tally = vec![Equity::empty(); num_players];
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut holes = Vec::with_capacity(num_players);
+            let mut full_board = Vec::with_capacity(5);
+
+            for index in start..end {
+                full_board.clear();
+                deal(index, &mut rng, &mut holes, &mut full_board);
+                showdown(&holes, &full_board, &mut tally);
+            }
+
+            tally
+        })
+        .collect();
+
+    // Add up the chunks in order, so that the floating point sums are reproducible.
+    let mut totals = vec![Equity::empty(); num_players];
+    for tally in tallies {
+        for (total, equity) in totals.iter_mut().zip(tally) {
+            total.wins += equity.wins;
+            total.ties += equity.ties;
+            total.pot_share += equity.pot_share;
+            total.runouts += equity.runouts;
+        }
+    }
+
+    totals
+}
+
+/// Returns whether `combo` shares a card with any of the `dealt` combos.
+fn clashes(dealt: &[[Card; 2]], combo: [Card; 2]) -> bool {
+    dealt.iter().flatten().any(|dealt| {
+        combo
+            .iter()
+            .any(|card| card.rank() == dealt.rank() && card.suit() == dealt.suit())
+    })
+}
+
+/// Returns whether every player can be dealt a combo from their list without any clashes, by
+/// trying every choice for one player after another.
+fn can_deal(combos: &[Vec<[Card; 2]>], dealt: &mut Vec<[Card; 2]>) -> bool {
+    let Some((first, rest)) = combos.split_first() else {
+        return true;
+    };
+
+    first.iter().any(|&combo| {
+        if clashes(dealt, combo) {
+            return false;
+        }
+
+        dealt.push(combo);
+        let found = can_deal(rest, dealt);
+        dealt.pop();
+        found
+    })
+}
+
+/// Evaluates every player's hand on a complete board, and records who won.
+fn showdown(players: &[[Card; 2]], board: &[Card], tally: &mut [Equity]) {
+    let ranks: Vec<_> = players
+        .iter()
+        .map(|hole| {
+            let cards = [&hole[..], board].concat();
+            evaluate(&cards).expect("cards are distinct")
+        })
+        .collect();
+
+    let best = *ranks.iter().max().expect("there are at least two players");
+    let winners = ranks.iter().filter(|&&rank| rank == best).count();
+
+    for (equity, rank) in tally.iter_mut().zip(ranks) {
+        equity.runouts += 1;
+        if rank != best {
+            continue;
+        }
+
+        if winners == 1 {
+            equity.wins += 1;
+        } else {
+            equity.ties += 1;
+        }
+        equity.pot_share += 1.0 / winners as f64;
+    }
+}
+
+/// Returns the `index`-th way (in lexicographic order) to pick `k` out of `n` items.
+fn nth_combination(mut index: u64, n: usize, k: usize) -> Vec<usize> {
+    let mut combination = Vec::with_capacity(k);
+    let mut next = 0;
+
+    for remaining in (1..=k).rev() {
+        // Skip over every combination that starts with a smaller item than the one we want.
+        loop {
+            let starting_here = binomial((n - next - 1) as u64, (remaining - 1) as u64);
+            if index < starting_here {
+                break;
+            }
+            index -= starting_here;
+            next += 1;
+        }
+
+        combination.push(next);
+        next += 1;
+    }
+
+    combination
+}
@@ -0,0 +1,203 @@
+//! Module for fast, lookup-table based hand evaluation.
+//!
+//! [`PokerHand::solve`] is easy to read, but it checks for every kind of hand one after another,
+//! which is far too slow when we want to evaluate millions of hands (for example, when simulating
+//! games). This module maps any set of 5 to 7 cards straight to a [`HandRank`], a 16-bit number
+//! that orders hands exactly the same way as [`PokerHand`] does.
+//!
+//! This is the same idea as [Cactus Kev's Poker Hand Evaluator]. There are only 7,462 distinct
+//! five-card hands once suits are ignored, and each of them can be found with a single lookup:
+//!
+//! - Flushes are looked up by the bitmask of their ranks, since every flush has 5 distinct ranks.
+//! - Other hands with 5 distinct ranks (straights and high cards) are looked up the same way, but
+//!   in a separate table.
+//! - Every other hand has a repeated rank. Each rank is assigned a prime number, so multiplying the
+//!   primes of the 5 cards gives a number that is unique to the multiset of ranks, which is looked
+//!   up with a binary search.
+//!
+//! Instead of hard-coding the tables, we build them the first time they are needed by running
+//! [`PokerHand::solve`] on one hand of every kind. This way the two can never disagree.
+//!
+//! [Cactus Kev's Poker Hand Evaluator]: http://suffe.cool/poker/evaluator.html
+
+use crate::card::{Card, Rank, Suit};
+use crate::hand::{Hand, PokerHand, five_card_combinations, rank_multisets};
+use std::sync::OnceLock;
+
+/// The number of distinct five-card hands, ignoring suits.
+pub const NUM_HAND_RANKS: u16 = 7462;
+
+/// A distinct prime number for each rank, from Two to Ace.
+const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// The strength of a poker hand, from 1 (the worst high card hand) to [`NUM_HAND_RANKS`] (a royal
+/// flush).
+///
+/// Two hands have the same `HandRank` exactly when their [`PokerHand`]s are equal, and one
+/// `HandRank` is greater than another exactly when its [`PokerHand`] is greater.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandRank(u16);
+
+impl HandRank {
+    /// Returns the numeric value of the rank, between 1 and [`NUM_HAND_RANKS`].
+    pub fn value(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the [`PokerHand`] of every hand with this rank.
+    pub fn hand(self) -> PokerHand {
+        tables().hands[self.0 as usize - 1]
+    }
+}
+
+/// Evaluates the best five-card hand that can be made out of 5 to 7 cards.
+///
+/// Returns [`None`] if there are fewer than 5 or more than 7 cards, or if any cards are duplicates.
+pub fn evaluate(cards: &[Card]) -> Option<HandRank> {
+    if !(5..=7).contains(&cards.len()) {
+        return None;
+    }
+
+    // Check for duplicates by giving every one of the 52 cards its own bit.
+    let mut seen = 0u64;
+    for card in cards {
+        let bit = 1 << card.index();
+        if seen & bit != 0 {
+            return None;
+        }
+        seen |= bit;
+    }
+
+    let tables = tables();
+    five_card_combinations(cards.len())
+        .map(|indexes| tables.evaluate_five(indexes.map(|i| cards[i])))
+        .max()
+}
+
+/// The lookup tables used to evaluate five-card hands.
+struct Tables {
+    /// Ranks of flushes, indexed by the bitmask of their card ranks.
+    flushes: Vec<u16>,
+    /// Ranks of non-flush hands with 5 distinct card ranks, indexed by the bitmask of those ranks.
+    unique: Vec<u16>,
+    /// Ranks of hands with a repeated card rank, keyed by the product of their card primes and
+    /// sorted by that product.
+    products: Vec<(u32, u16)>,
+    /// Every distinct hand, from worst to best, so that a hand's rank is its position plus one.
+    hands: Vec<PokerHand>,
+}
+
+/// How a kind of five-card hand is found in the [`Tables`].
+enum Key {
+    Flush(usize),
+    Unique(usize),
+    Product(u32),
+}
+
+/// Returns the lookup tables, building them if this is the first time they are needed.
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(Tables::build)
+}
+
+impl Tables {
+    /// Builds the tables by solving one hand of every kind with [`PokerHand::solve`].
+    fn build() -> Self {
+        let mut hands = Vec::with_capacity(NUM_HAND_RANKS as usize);
+
+        for ranks in rank_multisets(5) {
+            // Five of a kind can only be made with wild cards.
+            if ranks[0] == ranks[4] {
+                continue;
+            }
+            let ranks: [Rank; 5] = ranks.try_into().expect("there are 5 ranks");
+
+            let mask = ranks.iter().fold(0, |mask, &rank| mask | rank_bit(rank));
+            let product = ranks.iter().map(|&rank| rank_prime(rank)).product();
+
+            if mask.count_ones() == 5 {
+                // With 5 distinct ranks, the hand can either be suited or not.
+                let suited = ranks.map(|rank| Card::new(Suit::Spade, rank));
+                let mut offsuit = suited;
+                offsuit[0] = Card::new(Suit::Heart, ranks[0]);
+
+                hands.push((solve(suited), Key::Flush(mask)));
+                hands.push((solve(offsuit), Key::Unique(mask)));
+            } else {
+                // Give each copy of a repeated rank a different suit.
+                let cards: [Card; 5] = std::array::from_fn(|i| {
+                    let copy = ranks[..i].iter().filter(|&&rank| rank == ranks[i]).count();
+                    Card::new(Suit::ALL[copy], ranks[i])
+                });
+
+                hands.push((solve(cards), Key::Product(product)));
+            }
+        }
+
+        // Hand ranks are the positions of the hands in sorted order.
+        hands.sort_by_key(|&(hand, _)| hand);
+
+        let mut tables = Self {
+            flushes: vec![0; 1 << 13],
+            unique: vec![0; 1 << 13],
+            products: Vec::new(),
+            hands: Vec::with_capacity(NUM_HAND_RANKS as usize),
+        };
+
+        let mut rank = 0;
+        for (i, (hand, key)) in hands.iter().enumerate() {
+            if i == 0 || hands[i - 1].0 != *hand {
+                rank += 1;
+                tables.hands.push(*hand);
+            }
+
+            match *key {
+                Key::Flush(mask) => tables.flushes[mask] = rank,
+                Key::Unique(mask) => tables.unique[mask] = rank,
+                Key::Product(product) => tables.products.push((product, rank)),
+            }
+        }
+        assert_eq!(rank, NUM_HAND_RANKS, "found the wrong number of hand ranks");
+
+        tables.products.sort_unstable();
+        tables
+    }
+
+    /// Evaluates exactly five distinct cards.
+    fn evaluate_five(&self, cards: [Card; 5]) -> HandRank {
+        let mask = cards
+            .iter()
+            .fold(0, |mask, card| mask | rank_bit(card.rank()));
+
+        if cards.iter().all(|card| card.suit() == cards[0].suit()) {
+            return HandRank(self.flushes[mask]);
+        }
+
+        if mask.count_ones() == 5 {
+            return HandRank(self.unique[mask]);
+        }
+
+        let product: u32 = cards.iter().map(|card| rank_prime(card.rank())).product();
+        let i = self
+            .products
+            .binary_search_by_key(&product, |&(product, _)| product)
+            .expect("every product of five primes is in the table");
+
+        HandRank(self.products[i].1)
+    }
+}
+
+/// Solves five cards that are known to be distinct.
+fn solve(cards: [Card; 5]) -> PokerHand {
+    PokerHand::solve(Hand::new(cards).expect("cards are distinct"))
+}
+
+/// The bit representing a rank in a rank bitmask.
+fn rank_bit(rank: Rank) -> usize {
+    1 << rank.index()
+}
+
+/// The prime number representing a rank in a prime product.
+fn rank_prime(rank: Rank) -> u32 {
+    PRIMES[rank.index()]
+}
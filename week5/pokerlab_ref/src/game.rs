@@ -0,0 +1,757 @@
+//! Module for playing out a full hand of no-limit Texas Hold'em.
+//!
+//! A [`Game`] tracks the players' stacks, the blinds, each betting round, and the community cards,
+//! and rejects any action that would be illegal at the table with a descriptive [`GameError`].
+//! Once the last betting round is over, [`Game::showdown`] compares hands using [`PokerHand`]
+//! ordering and pays out the main pot and any side pots, splitting them on ties.
+//!
+//! Hands can also be replayed from a simple textual hand history with [`replay`]:
+//!
+//! ```text
+//! blinds 5 10
+//! seat Alice 1000
+//! seat Bob 1000
+//! hole Alice As Ah
+//! hole Bob Kd Kc
+//! Alice raises 30
+//! Bob calls
+//! flop 2c 7d 9h
+//! Bob checks
+//! Alice bets 50
+//! Bob folds
+//! ```
+//!
+//! Seat 0 has the dealer button. With two players the button posts the small blind, and otherwise
+//! the two seats after the button post the blinds.
+//!
+//! To keep things simple, an all-in raise that is smaller than a full raise still lets players who
+//! have already acted raise again, which is not allowed in most casinos.
+
+use crate::card::{Card, ParseCardError};
+use crate::hand::PokerHand;
+use std::fmt::Display;
+
+/// A betting round, or the end of the hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Street {
+    /// Before any community cards are dealt.
+    Preflop,
+    /// After the first three community cards are dealt.
+    Flop,
+    /// After the fourth community card is dealt.
+    Turn,
+    /// After the fifth and final community card is dealt.
+    River,
+    /// The hand is over and the pot has been paid out.
+    Finished,
+}
+
+/// Formats a street in lowercase, such as `flop`.
+impl Display for Street {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Street::Preflop => "preflop",
+            Street::Flop => "flop",
+            Street::Turn => "turn",
+            Street::River => "river",
+            Street::Finished => "finished",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Something a player can do when it is their turn to act.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Give up on the hand.
+    Fold,
+    /// Pass without betting, which is only allowed if there is nothing to call.
+    Check,
+    /// Match the current bet, going all-in if the player does not have enough chips.
+    Call,
+    /// Open the betting on this street, putting in this many chips in total.
+    Bet(u64),
+    /// Raise the current bet, so that the player has put in this many chips in total on this
+    /// street.
+    Raise(u64),
+    /// Put every remaining chip in, as either a call or a raise.
+    AllIn,
+}
+
+/// The reasons an action (or a line of a hand history) can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    /// A game needs at least two players.
+    NotEnoughPlayers,
+    /// Blinds must be positive, and the big blind must be at least the small blind.
+    InvalidBlinds,
+    /// Every player must start with a positive stack.
+    EmptyStack(String),
+    /// Two players have the same name.
+    DuplicatePlayer(String),
+    /// No player has this name (or seat).
+    UnknownPlayer(String),
+    /// The hand is already over.
+    HandFinished,
+    /// A player acted out of turn.
+    NotYourTurn { expected: String, actual: String },
+    /// The betting round is over, so the next community cards (or the showdown) are due.
+    BettingClosed,
+    /// The betting round is not over yet.
+    BettingNotFinished,
+    /// A player tried to check while facing a bet.
+    CannotCheck { to_call: u64 },
+    /// A player tried to call when there was no bet to call.
+    NothingToCall,
+    /// A player tried to bet when there was already a bet, instead of raising.
+    AlreadyBet { current_bet: u64 },
+    /// A player tried to raise when there was no bet to raise.
+    NothingToRaise,
+    /// A bet or raise was smaller than the minimum (and not all-in).
+    RaiseTooSmall { minimum: u64 },
+    /// A player tried to put in more chips than they have.
+    NotEnoughChips { stack: u64 },
+    /// A hand history dealt community cards for a different street than the next one.
+    WrongStreet { expected: Street, actual: Street },
+    /// The wrong number of community cards were dealt for this street.
+    WrongBoardSize { expected: usize, actual: usize },
+    /// A card was dealt twice.
+    DuplicateCard(Card),
+    /// A player still in the hand has no hole cards at showdown.
+    MissingHoleCards(String),
+    /// A line of a hand history could not be understood.
+    InvalidLine(String),
+    /// A card in a hand history could not be parsed.
+    InvalidCard(ParseCardError),
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEnoughPlayers => write!(f, "a game needs at least two players"),
+            Self::InvalidBlinds => write!(f, "blinds must be positive, with the big blind larger"),
+            Self::EmptyStack(name) => write!(f, "{name} has no chips"),
+            Self::DuplicatePlayer(name) => write!(f, "{name} is seated twice"),
+            Self::UnknownPlayer(name) => write!(f, "there is no player named {name}"),
+            Self::HandFinished => write!(f, "the hand is already over"),
+            Self::NotYourTurn { expected, actual } => {
+                write!(f, "{actual} acted out of turn, it is {expected}'s turn")
+            }
+            Self::BettingClosed => write!(f, "the betting round is over"),
+            Self::BettingNotFinished => write!(f, "the betting round is not over yet"),
+            Self::CannotCheck { to_call } => write!(f, "cannot check facing a bet of {to_call}"),
+            Self::NothingToCall => write!(f, "there is no bet to call"),
+            Self::AlreadyBet { current_bet } => {
+                write!(f, "there is already a bet of {current_bet}, raise instead")
+            }
+            Self::NothingToRaise => write!(f, "there is no bet to raise, bet instead"),
+            Self::RaiseTooSmall { minimum } => write!(f, "must bet or raise to at least {minimum}"),
+            Self::NotEnoughChips { stack } => write!(f, "only {stack} chips are left"),
+            Self::WrongStreet { expected, actual } => {
+                write!(f, "the {expected} is next, not the {actual}")
+            }
+            Self::WrongBoardSize { expected, actual } => {
+                write!(f, "expected {expected} community cards, got {actual}")
+            }
+            Self::DuplicateCard(card) => write!(f, "{card} was already dealt"),
+            Self::MissingHoleCards(name) => write!(f, "{name}'s hole cards are unknown"),
+            Self::InvalidLine(line) => write!(f, "cannot understand `{line}`"),
+            Self::InvalidCard(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<ParseCardError> for GameError {
+    fn from(error: ParseCardError) -> Self {
+        Self::InvalidCard(error)
+    }
+}
+
+/// A player seated at the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    /// The player's name.
+    name: String,
+    /// The chips the player has not put into the pot.
+    stack: u64,
+    /// The player's hole cards, if they are known.
+    hole: Option<[Card; 2]>,
+    /// The chips the player has put in on the current street.
+    street_bet: u64,
+    /// The chips the player has put in over the whole hand.
+    total_bet: u64,
+    /// Whether the player has folded.
+    folded: bool,
+    /// Whether the player has acted since the last full raise on this street.
+    acted: bool,
+}
+
+impl Player {
+    /// Returns the player's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the chips the player has behind (not in the pot).
+    pub fn stack(&self) -> u64 {
+        self.stack
+    }
+
+    /// Returns the player's hole cards, if they are known.
+    pub fn hole_cards(&self) -> Option<[Card; 2]> {
+        self.hole
+    }
+
+    /// Returns the chips the player has put into the pot over the whole hand.
+    pub fn total_bet(&self) -> u64 {
+        self.total_bet
+    }
+
+    /// Returns whether the player has folded.
+    pub fn has_folded(&self) -> bool {
+        self.folded
+    }
+
+    /// Returns whether the player is still in the hand and able to bet.
+    fn can_act(&self) -> bool {
+        !self.folded && self.stack > 0
+    }
+
+    /// Moves chips from the player's stack into the pot.
+    fn put_in(&mut self, chips: u64) {
+        self.stack -= chips;
+        self.street_bet += chips;
+        self.total_bet += chips;
+    }
+}
+
+/// A single hand of no-limit Texas Hold'em.
+#[derive(Debug, Clone)]
+pub struct Game {
+    /// The players, in seat order. Seat 0 has the dealer button.
+    players: Vec<Player>,
+    /// The big blind, which is also the smallest allowed bet.
+    big_blind: u64,
+    /// The community cards dealt so far.
+    board: Vec<Card>,
+    /// The current betting round.
+    street: Street,
+    /// The most chips any player has put in on this street.
+    current_bet: u64,
+    /// The smallest amount a raise must add to the current bet.
+    min_raise: u64,
+    /// The seat whose turn it is, or `None` if the betting round is over.
+    to_act: Option<usize>,
+}
+
+impl Game {
+    /// Seats the players (as `(name, stack)` pairs, starting with the button) and posts the blinds.
+    pub fn new(
+        players: &[(&str, u64)],
+        small_blind: u64,
+        big_blind: u64,
+    ) -> Result<Self, GameError> {
+        if players.len() < 2 {
+            return Err(GameError::NotEnoughPlayers);
+        }
+        if small_blind == 0 || big_blind < small_blind {
+            return Err(GameError::InvalidBlinds);
+        }
+
+        let mut seated: Vec<Player> = Vec::with_capacity(players.len());
+        for &(name, stack) in players {
+            if stack == 0 {
+                return Err(GameError::EmptyStack(name.to_string()));
+            }
+            if seated.iter().any(|player| player.name == name) {
+                return Err(GameError::DuplicatePlayer(name.to_string()));
+            }
+
+            seated.push(Player {
+                name: name.to_string(),
+                stack,
+                hole: None,
+                street_bet: 0,
+                total_bet: 0,
+                folded: false,
+                acted: false,
+            });
+        }
+
+        let mut game = Self {
+            players: seated,
+            big_blind,
+            board: Vec::new(),
+            street: Street::Preflop,
+            current_bet: 0,
+            min_raise: big_blind,
+            to_act: None,
+        };
+
+        // Heads-up, the button posts the small blind.
+        let (small, big) = if game.players.len() == 2 {
+            (0, 1)
+        } else {
+            (1, 2)
+        };
+        for (seat, blind) in [(small, small_blind), (big, big_blind)] {
+            let player = &mut game.players[seat];
+            player.put_in(blind.min(player.stack));
+        }
+        game.current_bet = game.players.iter().map(|p| p.street_bet).max().unwrap_or(0);
+        game.to_act = game.next_to_act(big);
+
+        Ok(game)
+    }
+
+    /// Returns the players, in seat order.
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Returns the seat of the player with this name.
+    pub fn seat_of(&self, name: &str) -> Option<usize> {
+        self.players.iter().position(|player| player.name == name)
+    }
+
+    /// Returns the community cards dealt so far.
+    pub fn board(&self) -> &[Card] {
+        &self.board
+    }
+
+    /// Returns the current betting round.
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /// Returns the seat whose turn it is, or `None` if nobody can act right now.
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    /// Returns the total number of chips in the pot.
+    pub fn pot(&self) -> u64 {
+        self.players.iter().map(|player| player.total_bet).sum()
+    }
+
+    /// Returns whether the hand is over.
+    pub fn is_finished(&self) -> bool {
+        self.street == Street::Finished
+    }
+
+    /// Deals a player their hole cards. This is optional until the showdown, where every player
+    /// still in the hand must have hole cards.
+    pub fn deal_hole_cards(&mut self, seat: usize, cards: [Card; 2]) -> Result<(), GameError> {
+        if self.is_finished() {
+            return Err(GameError::HandFinished);
+        }
+        if seat >= self.players.len() {
+            return Err(GameError::UnknownPlayer(format!("in seat {seat}")));
+        }
+
+        let previous = self.players[seat].hole.take();
+        if let Err(error) = self.check_new_cards(&cards) {
+            self.players[seat].hole = previous;
+            return Err(error);
+        }
+
+        self.players[seat].hole = Some(cards);
+        Ok(())
+    }
+
+    /// Deals the community cards for the next street: 3 for the flop, then 1 each for the turn and
+    /// the river. This is only allowed once the current betting round is over.
+    pub fn deal(&mut self, cards: &[Card]) -> Result<(), GameError> {
+        if self.is_finished() {
+            return Err(GameError::HandFinished);
+        }
+        if self.to_act.is_some() {
+            return Err(GameError::BettingNotFinished);
+        }
+
+        let (next, expected) = match self.street {
+            Street::Preflop => (Street::Flop, 3),
+            Street::Flop => (Street::Turn, 1),
+            Street::Turn => (Street::River, 1),
+            // Every community card is already out.
+            _ => (Street::Finished, 0),
+        };
+        if cards.len() != expected || cards.is_empty() {
+            return Err(GameError::WrongBoardSize {
+                expected,
+                actual: cards.len(),
+            });
+        }
+        self.check_new_cards(cards)?;
+
+        self.board.extend_from_slice(cards);
+        self.street = next;
+        self.current_bet = 0;
+        self.min_raise = self.big_blind;
+        for player in &mut self.players {
+            player.street_bet = 0;
+            player.acted = false;
+        }
+
+        // After the flop, the first player left of the button acts first.
+        self.to_act = self.next_to_act(0);
+        Ok(())
+    }
+
+    /// Makes the player in `seat` take an action.
+    pub fn act(&mut self, seat: usize, action: Action) -> Result<(), GameError> {
+        if self.is_finished() {
+            return Err(GameError::HandFinished);
+        }
+        let Some(player) = self.players.get(seat) else {
+            return Err(GameError::UnknownPlayer(format!("in seat {seat}")));
+        };
+        let Some(to_act) = self.to_act else {
+            return Err(GameError::BettingClosed);
+        };
+        if seat != to_act {
+            return Err(GameError::NotYourTurn {
+                expected: self.players[to_act].name.clone(),
+                actual: player.name.clone(),
+            });
+        }
+
+        let to_call = self.current_bet - player.street_bet;
+        let all_in = player.street_bet + player.stack;
+
+        // Work out how many chips the player puts in this street in total, if any.
+        let raise_to = match action {
+            Action::Fold => {
+                self.players[seat].folded = true;
+                None
+            }
+            Action::Check if to_call > 0 => return Err(GameError::CannotCheck { to_call }),
+            Action::Check => None,
+            Action::Call if to_call == 0 => return Err(GameError::NothingToCall),
+            Action::Call => Some(self.current_bet.min(all_in)),
+            Action::Bet(_) if self.current_bet > 0 => {
+                return Err(GameError::AlreadyBet {
+                    current_bet: self.current_bet,
+                });
+            }
+            Action::Raise(_) if self.current_bet == 0 => return Err(GameError::NothingToRaise),
+            Action::Bet(to) | Action::Raise(to) => {
+                if to > all_in {
+                    return Err(GameError::NotEnoughChips {
+                        stack: player.stack,
+                    });
+                }
+                let minimum = self.current_bet + self.min_raise;
+                if to < minimum && to != all_in || to <= self.current_bet {
+                    return Err(GameError::RaiseTooSmall { minimum });
+                }
+                Some(to)
+            }
+            Action::AllIn => Some(all_in),
+        };
+
+        if let Some(to) = raise_to {
+            let player = &mut self.players[seat];
+            player.put_in(to - player.street_bet);
+
+            if to > self.current_bet {
+                // Only a full raise changes the minimum raise and makes everyone act again.
+                let raise = to - self.current_bet;
+                if raise >= self.min_raise {
+                    self.min_raise = raise;
+                    for other in &mut self.players {
+                        other.acted = false;
+                    }
+                }
+                self.current_bet = to;
+            }
+        }
+        self.players[seat].acted = true;
+
+        // If everyone else folded, the last player standing wins without a showdown.
+        let pot = self.pot();
+        let mut remaining = self.players.iter_mut().filter(|player| !player.folded);
+        if let (Some(winner), None) = (remaining.next(), remaining.next()) {
+            winner.stack += pot;
+            self.finish();
+            return Ok(());
+        }
+
+        self.to_act = self.next_to_act(seat);
+        Ok(())
+    }
+
+    /// Compares the hands of everyone still in the hand, and pays out the main pot and any side
+    /// pots. Ties split a pot evenly, with any odd chips going to the winners closest to the left
+    /// of the button.
+    ///
+    /// This is only allowed once the river betting round is over.
+    pub fn showdown(&mut self) -> Result<(), GameError> {
+        match self.street {
+            Street::Finished => return Err(GameError::HandFinished),
+            Street::River if self.to_act.is_none() => {}
+            _ => return Err(GameError::BettingNotFinished),
+        }
+
+        let mut hands = Vec::with_capacity(self.players.len());
+        for player in &self.players {
+            if player.folded {
+                hands.push(None);
+                continue;
+            }
+
+            let hole = player
+                .hole
+                .ok_or_else(|| GameError::MissingHoleCards(player.name.clone()))?;
+            let cards = [&hole[..], &self.board].concat();
+            hands.push(Some(
+                PokerHand::best_from(&cards).expect("cards were checked when dealt"),
+            ));
+        }
+
+        // Every distinct amount put in by a player still in the hand caps a pot, which is contested
+        // by everyone who put in at least that much.
+        let mut levels: Vec<u64> = self
+            .players
+            .iter()
+            .filter(|player| !player.folded)
+            .map(|player| player.total_bet)
+            .collect();
+        levels.sort_unstable();
+        levels.dedup();
+
+        let most_put_in = self.players.iter().map(|p| p.total_bet).max().unwrap_or(0);
+        let mut previous = 0;
+        for (i, &level) in levels.iter().enumerate() {
+            // The last pot also collects anything folded players put in beyond the other caps.
+            let cap = if i + 1 == levels.len() {
+                most_put_in
+            } else {
+                level
+            };
+            let pot: u64 = self
+                .players
+                .iter()
+                .map(|p| p.total_bet.min(cap) - p.total_bet.min(previous))
+                .sum();
+            previous = cap;
+
+            let contenders = (0..self.players.len()).filter(|&seat| {
+                !self.players[seat].folded && self.players[seat].total_bet >= level
+            });
+            let best = contenders.clone().filter_map(|seat| hands[seat]).max();
+
+            // Hand out the pot starting with the first seat left of the button.
+            let mut winners: Vec<usize> = contenders.filter(|&seat| hands[seat] == best).collect();
+            winners.sort_by_key(|&seat| (seat + self.players.len() - 1) % self.players.len());
+
+            let share = pot / winners.len() as u64;
+            let odd_chips = (pot % winners.len() as u64) as usize;
+            for (i, &seat) in winners.iter().enumerate() {
+                self.players[seat].stack += share + u64::from(i < odd_chips);
+            }
+        }
+
+        self.finish();
+        Ok(())
+    }
+
+    /// Ends the hand, once the pot has been paid out.
+    fn finish(&mut self) {
+        self.street = Street::Finished;
+        self.to_act = None;
+        for player in &mut self.players {
+            player.street_bet = 0;
+            player.total_bet = 0;
+        }
+    }
+
+    /// Finds the next seat after `seat` that still has a decision to make on this street, or
+    /// returns `None` if the betting round is over.
+    fn next_to_act(&self, seat: usize) -> Option<usize> {
+        let can_act = self
+            .players
+            .iter()
+            .filter(|player| player.can_act())
+            .count();
+        let n = self.players.len();
+
+        (1..=n).map(|offset| (seat + offset) % n).find(|&next| {
+            let player = &self.players[next];
+            if !player.can_act() {
+                return false;
+            }
+
+            // A player who is matched up needs to act only if they have not had the chance to yet,
+            // and there is someone left who could still call a bet.
+            let matched = player.street_bet == self.current_bet;
+            !matched || !player.acted && can_act > 1
+        })
+    }
+
+    /// Checks that none of `cards` have been dealt already (or are repeated).
+    fn check_new_cards(&self, cards: &[Card]) -> Result<(), GameError> {
+        let mut dealt: Vec<Card> = self.board.clone();
+        dealt.extend(self.players.iter().filter_map(|p| p.hole).flatten());
+
+        for &card in cards {
+            // `Card` equality ignores suits, so compare them separately.
+            if dealt
+                .iter()
+                .any(|c| c.rank() == card.rank() && c.suit() == card.suit())
+            {
+                return Err(GameError::DuplicateCard(card));
+            }
+            dealt.push(card);
+        }
+
+        Ok(())
+    }
+}
+
+/// The error returned by [`replay`], which says which line of the hand history was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayError {
+    /// The line number (starting at 1) that was rejected.
+    pub line: usize,
+    /// Why the line was rejected.
+    pub error: GameError,
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Replays a hand history, returning the game once every line has been played. If the history
+/// ends right after the river betting round, the showdown is played as well.
+///
+/// A hand history starts with a `blinds <small> <big>` line and a `seat <name> <stack>` line for
+/// every player, starting with the button. After that, each line is one of:
+///
+/// - `hole <name> <card> <card>`, to deal a player their hole cards.
+/// - `flop <card> <card> <card>`, `turn <card>` or `river <card>`, to deal community cards.
+/// - `<name> folds`, `<name> checks`, `<name> calls`, `<name> bets <chips>`,
+///   `<name> raises <chips>` (the total the player puts in on the street) or `<name> all-in`.
+/// - `showdown`, which is optional at the very end.
+///
+/// Empty lines and lines starting with `#` are ignored.
+pub fn replay(history: &str) -> Result<Game, ReplayError> {
+    let mut blinds = None;
+    let mut seats: Vec<(&str, u64)> = Vec::new();
+    let mut game: Option<Game> = None;
+    let mut last_line = 0;
+
+    for (i, line) in history.lines().enumerate() {
+        last_line = i + 1;
+        let at_line = |error| ReplayError { line: i + 1, error };
+        let invalid = || at_line(GameError::InvalidLine(line.trim().to_string()));
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() || words[0].starts_with('#') {
+            continue;
+        }
+
+        // The table is set up by the header lines, which all come first.
+        match (game.is_some(), words.as_slice()) {
+            (false, ["blinds", small, big]) => {
+                let small = small.parse().map_err(|_| invalid())?;
+                let big = big.parse().map_err(|_| invalid())?;
+                blinds = Some((small, big));
+                continue;
+            }
+            (false, ["seat", name, stack]) => {
+                seats.push((name, stack.parse().map_err(|_| invalid())?));
+                continue;
+            }
+            (false, _) => {
+                let (small, big) = blinds.ok_or_else(invalid)?;
+                game = Some(Game::new(&seats, small, big).map_err(at_line)?);
+            }
+            (true, _) => {}
+        }
+
+        let game = game.as_mut().expect("the table was just set up");
+        let seat_of = |name: &str| {
+            game.seat_of(name)
+                .ok_or_else(|| at_line(GameError::UnknownPlayer(name.to_string())))
+        };
+        let parse_cards = |cards: &[&str]| {
+            cards
+                .iter()
+                .map(|card| card.parse::<Card>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|error| at_line(error.into()))
+        };
+
+        let result = match words.as_slice() {
+            ["hole", name, first, second] => {
+                let seat = seat_of(name)?;
+                let cards = parse_cards(&[first, second])?;
+                game.deal_hole_cards(seat, [cards[0], cards[1]])
+            }
+            [street @ ("flop" | "turn" | "river"), cards @ ..] => {
+                let actual = match *street {
+                    "flop" => Street::Flop,
+                    "turn" => Street::Turn,
+                    _ => Street::River,
+                };
+                // Only check the label when there is a street left to deal, so that dealing after
+                // the river is reported the same way as any other extra cards.
+                let expected = match game.street() {
+                    Street::Preflop => Some(Street::Flop),
+                    Street::Flop => Some(Street::Turn),
+                    Street::Turn => Some(Street::River),
+                    _ => None,
+                };
+                if let Some(expected) = expected
+                    && expected != actual
+                {
+                    return Err(at_line(GameError::WrongStreet { expected, actual }));
+                }
+
+                game.deal(&parse_cards(cards)?)
+            }
+            ["showdown"] => game.showdown(),
+            [name, verb, rest @ ..] => {
+                let seat = seat_of(name)?;
+                let chips = || match rest {
+                    [chips] => chips.parse().map_err(|_| invalid()),
+                    _ => Err(invalid()),
+                };
+
+                let action = match (*verb, rest) {
+                    ("folds", []) => Action::Fold,
+                    ("checks", []) => Action::Check,
+                    ("calls", []) => Action::Call,
+                    ("bets", _) => Action::Bet(chips()?),
+                    ("raises", _) => Action::Raise(chips()?),
+                    ("all-in", []) => Action::AllIn,
+                    _ => return Err(invalid()),
+                };
+                game.act(seat, action)
+            }
+            _ => return Err(invalid()),
+        };
+        result.map_err(at_line)?;
+    }
+
+    let mut game = game.ok_or(ReplayError {
+        line: last_line,
+        error: GameError::NotEnoughPlayers,
+    })?;
+
+    if game.street() == Street::River && game.to_act().is_none() {
+        game.showdown().map_err(|error| ReplayError {
+            line: last_line,
+            error,
+        })?;
+    }
+
+    Ok(game)
+}
@@ -20,7 +20,9 @@
 //!
 //! [Wikipedia]: https://en.wikipedia.org/wiki/List_of_poker_hands
 
-use crate::card::{Card, Rank};
+use crate::card::{Card, ParseCardError, Rank};
+use std::fmt::Display;
+use std::str::FromStr;
 
 /// Represents different poker hand rankings with their respective cards. Each variant contains the
 /// relevant cards that make up the hand.
@@ -43,6 +45,7 @@ pub enum PokerHand {
     FullHouse(FullHouse),
     FourOfAKind(FourOfAKind),
     StraightFlush(StraightFlush),
+    FiveOfAKind(FiveOfAKind),
 }
 
 /// Represents a high card hand, consisting of five unpaired cards.
@@ -128,6 +131,21 @@ pub struct StraightFlush {
     high_card: Rank,
 }
 
+/// Represents five cards of the same rank, which is only possible when playing with wild cards
+/// (see [`crate::wild`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FiveOfAKind {
+    /// The rank shared by all five cards.
+    rank: Rank,
+}
+
+impl FiveOfAKind {
+    /// Creates a five of a kind of the given rank.
+    pub(crate) fn new(rank: Rank) -> Self {
+        Self { rank }
+    }
+}
+
 impl PokerHand {
     /// Given 5 cards as input, creates a `PokerHand` with the correct ranking.
     ///
@@ -186,12 +204,58 @@ impl PokerHand {
             ],
         })
     }
+
+    /// Given between 5 and 7 cards, finds the best `PokerHand` that can be made out of any 5 of
+    /// them. This is how hands are ranked in games like Texas Hold'em, where each player has 2 hole
+    /// cards and shares 5 board cards with everyone else.
+    ///
+    /// Returns [`None`] if there are fewer than 5 or more than 7 cards, or if any cards are
+    /// duplicates.
+    pub fn best_from(cards: &[Card]) -> Option<Self> {
+        Hand::best_of(cards).map(Self::solve)
+    }
+}
+
+/// Describes a hand the way a dealer would announce it, such as `"Straight, Ace high"` or
+/// `"Full house, Tens full of Fours"`. Kickers are left out.
+impl Display for PokerHand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PokerHand::HighCard(hand) => write!(f, "High card, {} high", hand.high_card.name()),
+            PokerHand::OnePair(hand) => write!(f, "One pair, {}", hand.pair.plural()),
+            PokerHand::TwoPair(hand) => write!(
+                f,
+                "Two pair, {} and {}",
+                hand.first_pair.plural(),
+                hand.second_pair.plural()
+            ),
+            PokerHand::ThreeOfAKind(hand) => {
+                write!(f, "Three of a kind, {}", hand.triple.plural())
+            }
+            PokerHand::Straight(hand) => write!(f, "Straight, {} high", hand.high_card.name()),
+            PokerHand::Flush(hand) => write!(f, "Flush, {} high", hand.ranks[0].name()),
+            PokerHand::FullHouse(hand) => write!(
+                f,
+                "Full house, {} full of {}",
+                hand.triple.plural(),
+                hand.pair.plural()
+            ),
+            PokerHand::FourOfAKind(hand) => write!(f, "Four of a kind, {}", hand.quad.plural()),
+            PokerHand::StraightFlush(StraightFlush {
+                high_card: Rank::Ace,
+            }) => write!(f, "Royal flush"),
+            PokerHand::StraightFlush(hand) => {
+                write!(f, "Straight flush, {} high", hand.high_card.name())
+            }
+            PokerHand::FiveOfAKind(hand) => write!(f, "Five of a kind, {}", hand.rank.plural()),
+        }
+    }
 }
 
 /// Represents a standard `Hand` of 5 playing [`Card`]s.
 ///
 /// Note that the cards will always be stored in sorted descending order (only by [`Rank`]).
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Hand {
     /// The cards in the hand.
     cards: [Card; 5],
@@ -219,6 +283,37 @@ impl Hand {
         Some(Self { cards })
     }
 
+    /// Returns the cards in the hand, in descending order.
+    pub fn cards(&self) -> [Card; 5] {
+        self.cards
+    }
+
+    /// Given between 5 and 7 cards, finds the 5 of them that make the best [`PokerHand`].
+    ///
+    /// If several choices of 5 cards make equally good hands (for example, when a card that can't
+    /// play is swapped for another one that can't play), any one of them may be returned.
+    ///
+    /// Returns [`None`] if there are fewer than 5 or more than 7 cards, or if any cards are
+    /// duplicates.
+    pub fn best_of(cards: &[Card]) -> Option<Self> {
+        if !(5..=7).contains(&cards.len()) {
+            return None;
+        }
+
+        // Try every way of picking 5 of the cards (at most 7 choose 5 = 21 of them).
+        let mut best: Option<(PokerHand, Hand)> = None;
+        for indexes in five_card_combinations(cards.len()) {
+            let hand = Hand::new(indexes.map(|i| cards[i]))?;
+            let solved = PokerHand::solve(hand);
+
+            if best.is_none_or(|(best_solved, _)| solved > best_solved) {
+                best = Some((solved, hand));
+            }
+        }
+
+        best.map(|(_, hand)| hand)
+    }
+
     /// Returns a [`StraightFlush`] if the hand contains consecutive cards of the same suit,
     /// otherwise returns `None`.
     ///
@@ -425,3 +520,96 @@ impl Hand {
         None
     }
 }
+
+/// Formats the cards of a hand in short notation, from highest to lowest, such as
+/// `"As Kd Qh Jc Ts"`.
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, card) in self.cards.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{card}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses 5 cards in short notation separated by whitespace, such as `"As Kd Qh Jc Ts"`. The cards
+/// can be given in any order.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::hand::{Hand, PokerHand};
+/// #
+/// let hand: Hand = "Ts Jc Qh Kd As".parse().unwrap();
+/// assert_eq!(hand.to_string(), "As Kd Qh Jc Ts");
+/// assert_eq!(PokerHand::solve(hand).to_string(), "Straight, Ace high");
+/// ```
+impl FromStr for Hand {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards = Vec::with_capacity(5);
+        for token in s.split_whitespace() {
+            let card: Card = token.parse()?;
+
+            // `Card` equality ignores suits, so compare them separately.
+            if cards
+                .iter()
+                .any(|c: &Card| c.rank() == card.rank() && c.suit() == card.suit())
+            {
+                return Err(ParseCardError::DuplicateCard(token.to_string()));
+            }
+            cards.push(card);
+        }
+
+        let cards: [Card; 5] = cards
+            .try_into()
+            .map_err(|_| ParseCardError::WrongCardCount(s.to_string()))?;
+
+        Ok(Hand::new(cards).expect("cards were checked for duplicates"))
+    }
+}
+
+/// Returns the indexes of every way to pick 5 out of `n` items, in lexicographic order.
+pub(crate) fn five_card_combinations(n: usize) -> impl Iterator<Item = [usize; 5]> {
+    (0..n).flat_map(move |a| {
+        (a + 1..n).flat_map(move |b| {
+            (b + 1..n).flat_map(move |c| {
+                (c + 1..n).flat_map(move |d| (d + 1..n).map(move |e| [a, b, c, d, e]))
+            })
+        })
+    })
+}
+
+/// Returns every multiset of `n` ranks, as non-decreasing sequences.
+pub(crate) fn rank_multisets(n: usize) -> Vec<Vec<Rank>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut multisets = Vec::new();
+    for shorter in rank_multisets(n - 1) {
+        let lowest = shorter.last().copied().unwrap_or(Rank::Two);
+        for rank in Rank::ALL.into_iter().filter(|&rank| rank >= lowest) {
+            let mut multiset = shorter.clone();
+            multiset.push(rank);
+            multisets.push(multiset);
+        }
+    }
+
+    multisets
+}
+
+/// Computes the binomial coefficient `n` choose `k`.
+pub(crate) fn binomial(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+
+    // Each partial product is itself a binomial coefficient, so the division is always exact.
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
@@ -1,4 +1,13 @@
 #![doc = include_str!("../README.md")]
 
 pub mod card;
+pub mod deck;
+pub mod equity;
+pub mod eval;
+pub mod game;
 pub mod hand;
+pub mod low;
+pub mod omaha;
+pub mod range;
+pub mod stats;
+pub mod wild;
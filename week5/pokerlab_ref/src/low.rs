@@ -0,0 +1,284 @@
+//! Module for low hand rankings, and for splitting pots between the best high and low hands.
+//!
+//! In lowball games the worst hand wins, but there are two common ways of deciding what "worst"
+//! means:
+//!
+//! - **Ace-to-five** (used in Razz and in the low half of hi/lo games): Aces are always low, and
+//!   straights and flushes do not count. The best hand is 5-4-3-2-A, known as "the wheel".
+//! - **Deuce-to-seven** (used in Kansas City lowball): Aces are always high, and straights and
+//!   flushes count against you. The best hand is 7-5-4-3-2 of at least two suits.
+//!
+//! Either way, hands are first compared by pairs (fewer and smaller is better) and then by their
+//! highest card, then their second highest card, and so on.
+//!
+//! Just like [`PokerHand`], the types in this module are ordered so that the _better_ hand is the
+//! _greater_ one, even though a better low hand has smaller cards.
+
+use crate::card::{Card, Rank};
+use crate::hand::{Hand, PokerHand, five_card_combinations};
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+/// The kinds of hands, ordered from best to worst for a low hand. Straights and flushes only
+/// matter in deuce-to-seven.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+/// What a low hand is compared by. A smaller `LowKey` is a better low hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct LowKey {
+    /// The kind of hand.
+    category: Category,
+    /// The value of every card, with Aces as either 1 or 14. Cards that are part of a pair (or
+    /// better) come first, and otherwise cards are in descending order.
+    values: [u8; 5],
+}
+
+impl LowKey {
+    /// Computes the key of a hand, given the value of every card and whether straights and flushes
+    /// count.
+    fn new(hand: Hand, value: fn(Rank) -> u8, straights_and_flushes: bool) -> Self {
+        let cards = hand.cards();
+        let mut values = cards.map(|card| value(card.rank()));
+
+        // Sort by how many times each value appears, and then by the value itself.
+        let count = |v: u8| values.iter().filter(|&&other| other == v).count();
+        let counts = values.map(count);
+        let mut order = [0, 1, 2, 3, 4];
+        order.sort_by_key(|&i| std::cmp::Reverse((counts[i], values[i])));
+        values = order.map(|i| values[i]);
+
+        let mut shape = counts;
+        shape.sort_unstable_by(|a, b| b.cmp(a));
+
+        let straight = shape[0] == 1 && values[0] - values[4] == 4;
+        let flush = cards.iter().all(|card| card.suit() == cards[0].suit());
+
+        let category = match (
+            shape,
+            straights_and_flushes && straight,
+            straights_and_flushes && flush,
+        ) {
+            (_, true, true) => Category::StraightFlush,
+            ([4, ..], _, _) => Category::FourOfAKind,
+            ([3, 3, 3, 2, 2], _, _) => Category::FullHouse,
+            (_, _, true) => Category::Flush,
+            (_, true, _) => Category::Straight,
+            ([3, ..], _, _) => Category::ThreeOfAKind,
+            ([2, 2, 2, 2, 1], _, _) => Category::TwoPair,
+            ([2, ..], _, _) => Category::OnePair,
+            _ => Category::HighCard,
+        };
+
+        Self { category, values }
+    }
+
+    /// Writes the card values separated by dashes, such as `8-6-4-3-A`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, &value) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, "-")?;
+            }
+            // Aces are written as `A` whether they are worth 1 or 14.
+            let rank = match value {
+                1 => Rank::Ace,
+                value => Rank::ALL[value as usize - 2],
+            };
+            write!(f, "{rank}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the best hand out of 5 to 7 cards, according to `solve`.
+fn best_of<T: Ord>(cards: &[Card], solve: fn(Hand) -> T) -> Option<T> {
+    if !(5..=7).contains(&cards.len()) {
+        return None;
+    }
+
+    let mut best = None;
+    for indexes in five_card_combinations(cards.len()) {
+        let solved = solve(Hand::new(indexes.map(|i| cards[i]))?);
+        best = best.max(Some(solved));
+    }
+
+    best
+}
+
+/// An ace-to-five low hand, where Aces are low and straights and flushes do not count.
+///
+/// A greater `AceToFiveLow` is a _better_ (lower) hand.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::low::AceToFiveLow;
+/// #
+/// let wheel = AceToFiveLow::solve("5s 4h 3d 2c Ah".parse().unwrap());
+/// let eight_low = AceToFiveLow::solve("8s 6h 4d 3c Ah".parse().unwrap());
+///
+/// assert!(wheel > eight_low);
+/// assert!(eight_low.is_eight_or_better());
+/// assert_eq!(eight_low.to_string(), "8-6-4-3-A");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AceToFiveLow {
+    key: LowKey,
+}
+
+impl AceToFiveLow {
+    /// Ranks 5 cards as an ace-to-five low hand.
+    pub fn solve(hand: Hand) -> Self {
+        let value = |rank| match rank {
+            Rank::Ace => 1,
+            rank => rank as u8,
+        };
+
+        Self {
+            key: LowKey::new(hand, value, false),
+        }
+    }
+
+    /// Given between 5 and 7 cards, finds the best ace-to-five low hand that can be made out of any
+    /// 5 of them, such as in Razz.
+    ///
+    /// Returns [`None`] if there are fewer than 5 or more than 7 cards, or if any cards are
+    /// duplicates.
+    pub fn best_from(cards: &[Card]) -> Option<Self> {
+        best_of(cards, Self::solve)
+    }
+
+    /// Returns whether the hand qualifies for the low half of the pot in "eight-or-better" hi/lo
+    /// games, which means it has 5 different ranks that are all Eight or lower.
+    pub fn is_eight_or_better(&self) -> bool {
+        self.key.category == Category::HighCard && self.key.values[0] <= 8
+    }
+}
+
+impl PartialOrd for AceToFiveLow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AceToFiveLow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The smaller key is the better hand.
+        other.key.cmp(&self.key)
+    }
+}
+
+/// Formats the hand from its most important card to its least, such as `8-6-4-3-A`.
+impl Display for AceToFiveLow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.key.fmt(f)
+    }
+}
+
+/// A deuce-to-seven low hand, where Aces are high and straights and flushes count against you.
+///
+/// A greater `DeuceToSevenLow` is a _better_ (lower) hand.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::low::DeuceToSevenLow;
+/// #
+/// let number_one = DeuceToSevenLow::solve("7s 5h 4d 3c 2h".parse().unwrap());
+/// let wheel = DeuceToSevenLow::solve("5s 4h 3d 2c Ah".parse().unwrap());
+///
+/// // Aces are high, so A-5-4-3-2 is just an Ace-high hand.
+/// assert!(number_one > wheel);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeuceToSevenLow {
+    key: LowKey,
+}
+
+impl DeuceToSevenLow {
+    /// Ranks 5 cards as a deuce-to-seven low hand.
+    pub fn solve(hand: Hand) -> Self {
+        Self {
+            key: LowKey::new(hand, |rank| rank as u8, true),
+        }
+    }
+
+    /// Given between 5 and 7 cards, finds the best deuce-to-seven low hand that can be made out of
+    /// any 5 of them.
+    ///
+    /// Returns [`None`] if there are fewer than 5 or more than 7 cards, or if any cards are
+    /// duplicates.
+    pub fn best_from(cards: &[Card]) -> Option<Self> {
+        best_of(cards, Self::solve)
+    }
+}
+
+impl PartialOrd for DeuceToSevenLow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DeuceToSevenLow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The smaller key is the better hand.
+        other.key.cmp(&self.key)
+    }
+}
+
+/// Formats the hand from its most important card to its least, such as `7-5-4-3-2`.
+impl Display for DeuceToSevenLow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.key.fmt(f)
+    }
+}
+
+/// Splits a pot in a hi/lo game, where every player is given as their best high hand and their best
+/// qualifying low hand (if they have one). Returns how many chips each player wins, in order.
+///
+/// Half of the pot goes to the best high hand and half to the best low hand, with ties splitting
+/// their half evenly. If the pot does not split evenly, the odd chip goes to the high half, and odd
+/// chips within a half go to the tied players that come first. If nobody has a qualifying low, the
+/// best high hand wins the whole pot.
+pub fn split_hi_lo(pot: u64, players: &[(PokerHand, Option<AceToFiveLow>)]) -> Vec<u64> {
+    let mut shares = vec![0; players.len()];
+
+    let best_low = players.iter().filter_map(|(_, low)| *low).max();
+    let (high_half, low_half) = match best_low {
+        Some(_) => (pot - pot / 2, pot / 2),
+        None => (pot, 0),
+    };
+
+    let best_high = players.iter().map(|(high, _)| *high).max();
+    let high_winners: Vec<usize> = (0..players.len())
+        .filter(|&i| Some(players[i].0) == best_high)
+        .collect();
+    let low_winners: Vec<usize> = (0..players.len())
+        .filter(|&i| best_low.is_some() && players[i].1 == best_low)
+        .collect();
+
+    for (half, winners) in [(high_half, high_winners), (low_half, low_winners)] {
+        if winners.is_empty() {
+            continue;
+        }
+
+        let share = half / winners.len() as u64;
+        let odd_chips = (half % winners.len() as u64) as usize;
+        for (i, &winner) in winners.iter().enumerate() {
+            shares[winner] += share + u64::from(i < odd_chips);
+        }
+    }
+
+    shares
+}
@@ -0,0 +1,40 @@
+use pokerlab_ref::stats::Statistics;
+use std::process;
+use std::time::Instant;
+
+/// How to invoke the `pokerlab_ref` binary.
+const USAGE: &str = "usage: pokerlab_ref [5|7]
+
+Counts every kind of hand out of every possible deal of 5 or 7 cards (or both, if neither is
+given). Run with `--release`!";
+
+/// Prints an error message and the usage string, and then exits.
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("error: {message}\n{USAGE}");
+    process::exit(1);
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (five, seven) = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => (true, true),
+        ["5"] => (true, false),
+        ["7"] => (false, true),
+        _ => exit_with_usage(&format!("invalid arguments `{}`", args.join(" "))),
+    };
+
+    let runs: [(bool, fn() -> Statistics); 2] = [
+        (five, Statistics::five_card),
+        (seven, Statistics::seven_card),
+    ];
+
+    for (_, run) in runs.into_iter().filter(|&(enabled, _)| enabled) {
+        let start = Instant::now();
+        let stats = run();
+        let elapsed = start.elapsed();
+
+        println!("{stats}");
+        println!("Elapsed time: {:?}\n", elapsed);
+    }
+}
@@ -0,0 +1,95 @@
+//! Module for evaluating Omaha hands.
+//!
+//! In Omaha, every player gets 4 hole cards instead of 2, and shares up to 5 community cards with
+//! everyone else. Unlike in Texas Hold'em, a player must use _exactly_ two of their hole cards and
+//! exactly three of the community cards. For example, a player holding four Hearts with only two
+//! Hearts on the board does not have a flush!
+//!
+//! In Omaha Hi-Lo (also called Omaha eight-or-better), the pot is split between the best high hand
+//! and the best qualifying [`AceToFiveLow`] hand, which [`split_hi_lo`] takes care of.
+//!
+//! [`split_hi_lo`]: crate::low::split_hi_lo
+
+use crate::card::Card;
+use crate::hand::{Hand, PokerHand};
+use crate::low::AceToFiveLow;
+
+/// A player's 4 hole cards, together with the community cards dealt so far.
+#[derive(Debug, Clone)]
+pub struct OmahaHand {
+    /// The player's hole cards.
+    hole: [Card; 4],
+    /// The community cards.
+    board: Vec<Card>,
+}
+
+impl OmahaHand {
+    /// Creates an Omaha hand out of 4 hole cards and between 3 and 5 community cards.
+    ///
+    /// Returns [`None`] if there are not between 3 and 5 community cards, or if any cards are
+    /// duplicates.
+    pub fn new(hole: [Card; 4], board: &[Card]) -> Option<Self> {
+        if !(3..=5).contains(&board.len()) {
+            return None;
+        }
+
+        let all: Vec<Card> = hole.iter().chain(board).copied().collect();
+        for (i, card) in all.iter().enumerate() {
+            // `Card` equality ignores suits, so compare them separately.
+            if all[..i]
+                .iter()
+                .any(|c| c.rank() == card.rank() && c.suit() == card.suit())
+            {
+                return None;
+            }
+        }
+
+        Some(Self {
+            hole,
+            board: board.to_vec(),
+        })
+    }
+
+    /// Returns the best high hand made from exactly two hole cards and three community cards.
+    pub fn high(&self) -> PokerHand {
+        self.hands()
+            .map(PokerHand::solve)
+            .max()
+            .expect("there is always at least one hand")
+    }
+
+    /// Returns the best eight-or-better low hand made from exactly two hole cards and three
+    /// community cards, or [`None`] if no such hand qualifies.
+    pub fn low(&self) -> Option<AceToFiveLow> {
+        self.hands()
+            .map(AceToFiveLow::solve)
+            .filter(AceToFiveLow::is_eight_or_better)
+            .max()
+    }
+
+    /// Returns every hand made from exactly two hole cards and three community cards.
+    fn hands(&self) -> impl Iterator<Item = Hand> + '_ {
+        pairs(4).flat_map(move |[a, b]| {
+            triples(self.board.len()).map(move |[c, d, e]| {
+                let cards = [
+                    self.hole[a],
+                    self.hole[b],
+                    self.board[c],
+                    self.board[d],
+                    self.board[e],
+                ];
+                Hand::new(cards).expect("cards were checked for duplicates")
+            })
+        })
+    }
+}
+
+/// Returns the indexes of every way to pick 2 out of `n` items.
+fn pairs(n: usize) -> impl Iterator<Item = [usize; 2]> {
+    (0..n).flat_map(move |a| (a + 1..n).map(move |b| [a, b]))
+}
+
+/// Returns the indexes of every way to pick 3 out of `n` items.
+fn triples(n: usize) -> impl Iterator<Item = [usize; 3]> {
+    pairs(n).flat_map(move |[a, b]| (b + 1..n).map(move |c| [a, b, c]))
+}
@@ -0,0 +1,319 @@
+//! Module for hand ranges: sets of two-card starting hands ("combos") that a player might hold.
+//!
+//! Ranges are usually written in a shorthand notation, where hands are separated by commas:
+//!
+//! - `QQ` is every pair of Queens (6 combos), and `AKs` and `AKo` are every suited (4 combos) and
+//!   offsuit (12 combos) Ace-King. `AK` is both (16 combos).
+//! - `QQ+` is every pair of Queens or better, and `A5o+` is every offsuit Ace with a Five or better
+//!   kicker (up to `AKo`).
+//! - `QQ-99` is every pair from Queens down to Nines, `A5s-A2s` is every suited Ace with a Two to
+//!   Five kicker, and `T9s-76s` is every suited connector from Ten-Nine down to Seven-Six.
+//! - `AsKh` is exactly one combo.
+//!
+//! For example, `"AKs, QQ+, T9s-76s, A5o+"` parses into 4 + 18 + 16 + 108 = 146 combos.
+
+use crate::card::{Card, Rank, Suit};
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A set of two-card starting hands.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::range::Range;
+/// # use pokerlab_ref::card::Card;
+/// #
+/// let mut range: Range = "AKs, QQ+, T9s-76s, A5o+".parse().unwrap();
+/// assert_eq!(range.len(), 146);
+///
+/// // Holding the Ace of Spades ourselves removes every combo that uses it.
+/// range.remove_dead(&["As".parse::<Card>().unwrap()]);
+/// assert_eq!(range.len(), 146 - 1 - 3 - 27);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Range {
+    /// The combos in the range, as pairs of card indexes (see `Card::index`), higher index first.
+    combos: BTreeSet<(u8, u8)>,
+}
+
+impl Range {
+    /// Creates an empty range.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a single combo to the range. Returns `false` (and does nothing) if both cards are the
+    /// same.
+    pub fn insert(&mut self, combo: [Card; 2]) -> bool {
+        let (a, b) = (combo[0].index() as u8, combo[1].index() as u8);
+        if a == b {
+            return false;
+        }
+
+        self.combos.insert((a.max(b), a.min(b)));
+        true
+    }
+
+    /// Returns whether the range contains a combo, in either order.
+    pub fn contains(&self, combo: [Card; 2]) -> bool {
+        let (a, b) = (combo[0].index() as u8, combo[1].index() as u8);
+        self.combos.contains(&(a.max(b), a.min(b)))
+    }
+
+    /// Returns the number of combos in the range.
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Returns whether the range has no combos.
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Iterates over the combos in the range.
+    pub fn iter(&self) -> impl Iterator<Item = [Card; 2]> + '_ {
+        self.combos
+            .iter()
+            .map(|&(a, b)| [Card::from_index(a as usize), Card::from_index(b as usize)])
+    }
+
+    /// Removes every combo that uses any of the `dead` cards, such as our own hole cards or the
+    /// community cards.
+    pub fn remove_dead(&mut self, dead: &[Card]) {
+        let dead: Vec<u8> = dead.iter().map(|card| card.index() as u8).collect();
+        self.combos
+            .retain(|(a, b)| !dead.contains(a) && !dead.contains(b));
+    }
+
+    /// Returns the combos that are in either range.
+    pub fn union(&self, other: &Range) -> Range {
+        Range {
+            combos: self.combos.union(&other.combos).copied().collect(),
+        }
+    }
+
+    /// Returns the combos that are in both ranges.
+    pub fn intersection(&self, other: &Range) -> Range {
+        Range {
+            combos: self.combos.intersection(&other.combos).copied().collect(),
+        }
+    }
+
+    /// Returns the number of combos of every starting hand, laid out in the usual 13x13 grid.
+    ///
+    /// Rows and columns go from Ace (index 0) down to Two (index 12). Pairs are on the diagonal,
+    /// suited hands are above it (in the row of their higher rank), and offsuit hands are below it
+    /// (in the column of their higher rank). For example, `grid[0][1]` counts `AKs` and
+    /// `grid[1][0]` counts `AKo`.
+    pub fn grid(&self) -> [[u8; 13]; 13] {
+        let mut grid = [[0; 13]; 13];
+
+        for [first, second] in self.iter() {
+            let (x, y) = (grid_index(first.rank()), grid_index(second.rank()));
+            let (high, low) = (x.min(y), x.max(y));
+
+            if first.suit() == second.suit() {
+                grid[high][low] += 1;
+            } else {
+                grid[low][high] += 1;
+            }
+        }
+
+        grid
+    }
+
+    /// Adds every combo of a starting hand, such as `AKs`.
+    fn insert_class(&mut self, class: Class) {
+        for a in Suit::ALL {
+            for b in Suit::ALL {
+                let matches = match class.suited {
+                    _ if class.high == class.low => (a as u8) < (b as u8),
+                    Some(true) => a == b,
+                    Some(false) => a != b,
+                    None => true,
+                };
+
+                if matches {
+                    self.insert([Card::new(a, class.high), Card::new(b, class.low)]);
+                }
+            }
+        }
+    }
+}
+
+/// Parses comma-separated hands in range notation. See the [module-level documentation](self) for
+/// the notation.
+impl FromStr for Range {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::new();
+
+        for token in s
+            .split(',')
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+        {
+            let invalid = || ParseRangeError::InvalidHand(token.to_string());
+
+            // A single, specific combo.
+            if let (Some(first), Some(second)) = (token.get(..2), token.get(2..))
+                && let (Ok(first), Ok(second)) = (first.parse::<Card>(), second.parse::<Card>())
+            {
+                if !range.insert([first, second]) {
+                    return Err(invalid());
+                }
+                continue;
+            }
+
+            let classes = if let Some((from, to)) = token.split_once('-') {
+                let (from, to) = (
+                    Class::parse(from).ok_or_else(invalid)?,
+                    Class::parse(to).ok_or_else(invalid)?,
+                );
+                Class::span(from, to)
+                    .ok_or_else(|| ParseRangeError::InvalidSpan(token.to_string()))?
+            } else if let Some(class) = token.strip_suffix('+') {
+                Class::parse(class).ok_or_else(invalid)?.and_better()
+            } else {
+                vec![Class::parse(token).ok_or_else(invalid)?]
+            };
+
+            for class in classes {
+                range.insert_class(class);
+            }
+        }
+
+        Ok(range)
+    }
+}
+
+/// The error returned when parsing a [`Range`] fails. Every variant holds the token that could not
+/// be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeError {
+    /// The token is not a hand (such as `AKs` or `QQ+`) or a specific combo (such as `AsKh`).
+    InvalidHand(String),
+    /// The two ends of a span (such as `T9s-76s`) do not describe a sequence of hands.
+    InvalidSpan(String),
+}
+
+impl Display for ParseRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHand(token) => write!(f, "`{token}` is not a valid hand"),
+            Self::InvalidSpan(token) => write!(f, "`{token}` is not a valid span of hands"),
+        }
+    }
+}
+
+impl std::error::Error for ParseRangeError {}
+
+/// A starting hand without suits, such as `AKs`, `QQ` or `T9`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Class {
+    /// The higher rank.
+    high: Rank,
+    /// The lower rank (equal to `high` for pairs).
+    low: Rank,
+    /// Whether the hand must be suited (`s`) or offsuit (`o`), or `None` if it can be either.
+    suited: Option<bool>,
+}
+
+impl Class {
+    /// Parses a hand like `AKs`, `QQ` or `T9`. The ranks may come in either order.
+    fn parse(s: &str) -> Option<Self> {
+        let mut chars = s.chars();
+        let first = Rank::from_char(chars.next()?)?;
+        let second = Rank::from_char(chars.next()?)?;
+        let suited = match (chars.next(), chars.next()) {
+            (None, _) => None,
+            (Some('s' | 'S'), None) => Some(true),
+            (Some('o' | 'O'), None) => Some(false),
+            _ => return None,
+        };
+
+        // Pairs can't be suited or offsuit.
+        if first == second && suited.is_some() {
+            return None;
+        }
+
+        Some(Self {
+            high: first.max(second),
+            low: first.min(second),
+            suited,
+        })
+    }
+
+    /// Expands `XX+` into every pair from `XX` to `AA`, and `XY+` into every hand from `XY` up to
+    /// (but not including) a pair.
+    fn and_better(self) -> Vec<Self> {
+        let top = if self.high == self.low {
+            Rank::Ace
+        } else {
+            Rank::ALL[self.high.index() - 1]
+        };
+
+        Rank::ALL[self.low.index()..=top.index()]
+            .iter()
+            .map(|&low| {
+                let high = if self.high == self.low {
+                    low
+                } else {
+                    self.high
+                };
+                Self { high, low, ..self }
+            })
+            .collect()
+    }
+
+    /// Expands a span between two hands, such as `QQ-99`, `A5s-A2s` or `T9s-76s`, in either
+    /// order. Returns `None` if the two hands are not the ends of such a span.
+    fn span(from: Self, to: Self) -> Option<Vec<Self>> {
+        let (top, bottom) = if from.low >= to.low {
+            (from, to)
+        } else {
+            (to, from)
+        };
+        if top.suited != bottom.suited {
+            return None;
+        }
+
+        let steps = top.low.index() - bottom.low.index();
+        let is_pair = |class: Self| class.high == class.low;
+
+        // Either both ranks move together (pairs and connectors), or only the lower one does.
+        let both_move = if is_pair(top) && is_pair(bottom) {
+            true
+        } else if is_pair(top) || is_pair(bottom) {
+            return None;
+        } else if top.high == bottom.high {
+            false
+        } else if top.high.index() - top.low.index() == bottom.high.index() - bottom.low.index() {
+            true
+        } else {
+            return None;
+        };
+
+        Some(
+            (0..=steps)
+                .map(|step| Self {
+                    high: if both_move {
+                        Rank::ALL[bottom.high.index() + step]
+                    } else {
+                        top.high
+                    },
+                    low: Rank::ALL[bottom.low.index() + step],
+                    suited: top.suited,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The row or column of a rank in [`Range::grid`], from 0 (Ace) to 12 (Two).
+fn grid_index(rank: Rank) -> usize {
+    12 - rank.index()
+}
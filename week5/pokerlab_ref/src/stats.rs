@@ -0,0 +1,388 @@
+//! Module for exhaustive statistics about poker hands: how many of each kind of hand there are out
+//! of every possible deal, and how many distinct hands (ignoring suits) there are of each kind.
+//!
+//! [`Statistics::five_card`] deals all C(52, 5) = 2,598,960 five-card hands and ranks every one of
+//! them with [`PokerHand::solve`], so comparing its output against the well-known table is a good
+//! check of the `get_*` methods on [`Hand`]. There should be 7,462 distinct hands.
+//!
+//! [`Statistics::seven_card`] deals all C(52, 7) = 133,784,560 seven-card hands and counts the best
+//! five-card hand out of each one, as in Texas Hold'em. Calling [`evaluate`] on every one of them
+//! would take minutes, so instead we use two extra tables built with [`evaluate`]:
+//!
+//! - Without a flush, the best hand only depends on the multiset of the 7 ranks, of which there are
+//!   only about 50,000.
+//! - With a flush (5 or more cards of one suit), the best flush only depends on the ranks of that
+//!   suit, so it can be looked up by their bitmask.
+//!
+//! The best hand is then whichever of the two is better. Either way, the deals are split up by
+//! their first two cards and counted in parallel with [`rayon`].
+
+use crate::card::{Card, Rank, Suit};
+use crate::eval::{HandRank, NUM_HAND_RANKS, evaluate};
+use crate::hand::{Hand, PokerHand, binomial, rank_multisets};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// The kinds of hands, as they are usually listed in tables of poker probabilities. Unlike
+/// [`PokerHand`], royal flushes get their own category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    RoyalFlush,
+}
+
+impl Category {
+    /// Every category, from worst to best.
+    pub const ALL: [Category; 10] = [
+        Category::HighCard,
+        Category::OnePair,
+        Category::TwoPair,
+        Category::ThreeOfAKind,
+        Category::Straight,
+        Category::Flush,
+        Category::FullHouse,
+        Category::FourOfAKind,
+        Category::StraightFlush,
+        Category::RoyalFlush,
+    ];
+
+    /// Returns the category of a hand, or [`None`] for five of a kind, which can only be made with
+    /// wild cards.
+    pub fn of(hand: &PokerHand) -> Option<Self> {
+        Some(match hand {
+            PokerHand::HighCard(_) => Category::HighCard,
+            PokerHand::OnePair(_) => Category::OnePair,
+            PokerHand::TwoPair(_) => Category::TwoPair,
+            PokerHand::ThreeOfAKind(_) => Category::ThreeOfAKind,
+            PokerHand::Straight(_) => Category::Straight,
+            PokerHand::Flush(_) => Category::Flush,
+            PokerHand::FullHouse(_) => Category::FullHouse,
+            PokerHand::FourOfAKind(_) => Category::FourOfAKind,
+            PokerHand::StraightFlush(_) if *hand == royal_flush() => Category::RoyalFlush,
+            PokerHand::StraightFlush(_) => Category::StraightFlush,
+            PokerHand::FiveOfAKind(_) => return None,
+        })
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Category::HighCard => "High card",
+            Category::OnePair => "One pair",
+            Category::TwoPair => "Two pair",
+            Category::ThreeOfAKind => "Three of a kind",
+            Category::Straight => "Straight",
+            Category::Flush => "Flush",
+            Category::FullHouse => "Full house",
+            Category::FourOfAKind => "Four of a kind",
+            Category::StraightFlush => "Straight flush",
+            Category::RoyalFlush => "Royal flush",
+        };
+        f.pad(name)
+    }
+}
+
+/// How many hands of every [`Category`] there are out of every possible deal of some number of
+/// cards.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::stats::{Category, Statistics};
+/// #
+/// let stats = Statistics::five_card();
+/// assert_eq!(stats.total_hands(), 2_598_960);
+/// assert_eq!(stats.total_classes(), 7462);
+/// assert_eq!(stats.hands(Category::FullHouse), 3744);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statistics {
+    /// The number of cards in every deal.
+    cards: usize,
+    /// The number of deals of every category, indexed like [`Category::ALL`].
+    hands: [u64; 10],
+    /// The number of distinct hands of every category, indexed like [`Category::ALL`].
+    classes: [u64; 10],
+}
+
+impl Statistics {
+    /// Ranks every five-card hand with [`PokerHand::solve`].
+    pub fn five_card() -> Self {
+        let counts = first_two_cards()
+            .into_par_iter()
+            .map(|(a, b)| {
+                let mut counts: BTreeMap<PokerHand, u64> = BTreeMap::new();
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let cards = [a, b, c, d, e].map(Card::from_index);
+                            let hand = Hand::new(cards).expect("cards are distinct");
+                            *counts.entry(PokerHand::solve(hand)).or_default() += 1;
+                        }
+                    }
+                }
+                counts
+            })
+            .reduce(BTreeMap::new, |mut total, counts| {
+                for (hand, count) in counts {
+                    *total.entry(hand).or_default() += count;
+                }
+                total
+            });
+
+        Self::from_counts(5, counts)
+    }
+
+    /// Finds the best five-card hand out of every seven-card hand.
+    pub fn seven_card() -> Self {
+        let tables = SevenCardTables::build();
+
+        let counts = first_two_cards()
+            .into_par_iter()
+            .map(|(a, b)| tables.count_from(a, b))
+            .reduce(
+                || vec![0; NUM_HAND_RANKS as usize + 1],
+                |mut total, counts| {
+                    for (total, count) in total.iter_mut().zip(counts) {
+                        *total += count;
+                    }
+                    total
+                },
+            );
+
+        let counts = tables
+            .ranks
+            .iter()
+            .flatten()
+            .map(|rank| (rank.hand(), counts[rank.value() as usize]))
+            .filter(|&(_, count)| count > 0);
+
+        Self::from_counts(7, counts)
+    }
+
+    /// Adds up the number of deals of every distinct hand by category.
+    fn from_counts(cards: usize, counts: impl IntoIterator<Item = (PokerHand, u64)>) -> Self {
+        let mut stats = Self {
+            cards,
+            hands: [0; 10],
+            classes: [0; 10],
+        };
+
+        for (hand, count) in counts {
+            let category = Category::of(&hand).expect("no five of a kind without wild cards");
+            stats.hands[category as usize] += count;
+            stats.classes[category as usize] += 1;
+        }
+
+        stats
+    }
+
+    /// Returns the number of cards in every deal.
+    pub fn cards(&self) -> usize {
+        self.cards
+    }
+
+    /// Returns the number of deals whose best hand is in `category`.
+    pub fn hands(&self, category: Category) -> u64 {
+        self.hands[category as usize]
+    }
+
+    /// Returns the number of distinct hands (ignoring suits) in `category`.
+    pub fn classes(&self, category: Category) -> u64 {
+        self.classes[category as usize]
+    }
+
+    /// Returns the probability that a random deal's best hand is in `category`.
+    pub fn probability(&self, category: Category) -> f64 {
+        self.hands(category) as f64 / self.total_hands() as f64
+    }
+
+    /// Returns the total number of deals.
+    pub fn total_hands(&self) -> u64 {
+        self.hands.iter().sum()
+    }
+
+    /// Returns the total number of distinct hands.
+    pub fn total_classes(&self) -> u64 {
+        self.classes.iter().sum()
+    }
+}
+
+/// Formats the statistics as a table, from the best category to the worst.
+impl Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = format!("{}-card hand", self.cards);
+        writeln!(
+            f,
+            "{title:<16} {:>12} {:>14} {:>9}",
+            "Hands", "Probability", "Distinct"
+        )?;
+
+        for category in Category::ALL.into_iter().rev() {
+            writeln!(
+                f,
+                "{category:<16} {:>12} {:>13.6}% {:>9}",
+                self.hands(category),
+                100.0 * self.probability(category),
+                self.classes(category)
+            )?;
+        }
+
+        write!(
+            f,
+            "{:<16} {:>12} {:>13.6}% {:>9}",
+            "Total",
+            self.total_hands(),
+            100.0,
+            self.total_classes()
+        )
+    }
+}
+
+/// Lookup tables for the best five-card hand out of seven cards. See the
+/// [module-level documentation](self).
+struct SevenCardTables {
+    /// The rank of the best hand without a flush, indexed by
+    /// [`multiset_index`](Self::multiset_index).
+    unsuited: Vec<u16>,
+    /// The rank of the best flush out of cards of a single suit, indexed by the bitmask of their
+    /// ranks, or 0 if there are fewer than 5 ranks in the bitmask.
+    flushes: Vec<u16>,
+    /// Every rank in the other two tables, indexed by its value.
+    ranks: Vec<Option<HandRank>>,
+    /// `terms[i][r]` is what a card of rank index `r` adds to the
+    /// [`multiset_index`](Self::multiset_index) when it is the `i`th card out of 7, in
+    /// non-decreasing order of rank.
+    terms: [[usize; 13]; 7],
+}
+
+impl SevenCardTables {
+    /// Builds the tables by evaluating one hand of every kind with [`evaluate`].
+    fn build() -> Self {
+        let mut terms = [[0; 13]; 7];
+        for (i, terms) in terms.iter_mut().enumerate() {
+            for (r, term) in terms.iter_mut().enumerate() {
+                *term = binomial((r + i) as u64, (i + 1) as u64) as usize;
+            }
+        }
+
+        let mut tables = Self {
+            unsuited: vec![0; binomial(13 + 6, 7) as usize],
+            flushes: vec![0; 1 << 13],
+            ranks: vec![None; NUM_HAND_RANKS as usize + 1],
+            terms,
+        };
+
+        // Deal the cards round-robin over the 4 suits, which never makes a flush, and never gives
+        // two cards of the same rank the same suit (since they are next to each other).
+        for ranks in rank_multisets(7) {
+            if Rank::ALL
+                .iter()
+                .any(|r| ranks.iter().filter(|&rank| rank == r).count() > 4)
+            {
+                continue;
+            }
+            let cards: Vec<Card> = (0..7)
+                .map(|i| Card::new(Suit::ALL[i % 4], ranks[i]))
+                .collect();
+
+            let index = tables.multiset_index(&ranks);
+            tables.unsuited[index] = tables.evaluate(&cards);
+        }
+
+        for mask in 0..1usize << 13 {
+            let suited: Vec<Card> = (0..13)
+                .filter(|r| mask & (1 << r) != 0)
+                .map(|r| Card::new(Suit::ALL[0], Rank::ALL[r]))
+                .collect();
+
+            if (5..=7).contains(&suited.len()) {
+                tables.flushes[mask] = tables.evaluate(&suited);
+            }
+        }
+
+        tables
+    }
+
+    /// Evaluates cards that are known to be distinct, remembering their rank.
+    fn evaluate(&mut self, cards: &[Card]) -> u16 {
+        let rank = evaluate(cards).expect("cards are distinct");
+        self.ranks[rank.value() as usize] = Some(rank);
+        rank.value()
+    }
+
+    /// The index of a non-decreasing sequence of 7 ranks in the `unsuited` table.
+    fn multiset_index(&self, ranks: &[Rank]) -> usize {
+        ranks
+            .iter()
+            .enumerate()
+            .map(|(i, &r)| self.terms[i][r.index()])
+            .sum()
+    }
+
+    /// Counts how many seven-card hands that start with the cards `a` and `b` (see `Card::index`)
+    /// there are of every rank. The other 5 cards all come after `b`.
+    ///
+    /// Since cards are numbered by rank first, picking them in increasing order picks their ranks
+    /// in non-decreasing order, so the index of the rank multiset can be built up one card at a
+    /// time, and so can the rank bitmasks of every suit.
+    fn count_from(&self, a: usize, b: usize) -> Vec<u64> {
+        let mut counts = vec![0; NUM_HAND_RANKS as usize + 1];
+
+        let add = |index: usize, masks: [usize; 4], i: usize, card: usize| {
+            let mut masks = masks;
+            masks[card % 4] |= 1 << (card / 4);
+            (index + self.terms[i][card / 4], masks)
+        };
+
+        let start = add(0, [0; 4], 0, a);
+        let start = add(start.0, start.1, 1, b);
+
+        for c in b + 1..52 {
+            let (index, masks) = add(start.0, start.1, 2, c);
+            for d in c + 1..52 {
+                let (index, masks) = add(index, masks, 3, d);
+                for e in d + 1..52 {
+                    let (index, masks) = add(index, masks, 4, e);
+                    for f in e + 1..52 {
+                        let (index, masks) = add(index, masks, 5, f);
+                        for g in f + 1..52 {
+                            let (index, masks) = add(index, masks, 6, g);
+                            let best = masks
+                                .iter()
+                                .map(|&mask| self.flushes[mask])
+                                .fold(self.unsuited[index], u16::max);
+                            counts[best as usize] += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        counts
+    }
+}
+
+/// An Ace-high straight flush.
+fn royal_flush() -> PokerHand {
+    let cards = [Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Ten]
+        .map(|rank| Card::new(Suit::Spade, rank));
+    PokerHand::solve(Hand::new(cards).expect("cards are distinct"))
+}
+
+/// Every pair of indexes of the first two cards out of 52, in order.
+fn first_two_cards() -> Vec<(usize, usize)> {
+    (0..52)
+        .flat_map(|a| (a + 1..52).map(move |b| (a, b)))
+        .collect()
+}
@@ -0,0 +1,148 @@
+//! Module for evaluating hands that contain wild cards, such as jokers or "deuces wild" games.
+//!
+//! A wild card can stand in for any card that is not already in the hand, and we always pick the
+//! substitution that makes the best [`PokerHand`]. The one exception to the "not already in the
+//! hand" rule is [`FiveOfAKind`], which beats every other hand (even a royal flush) and can only be
+//! made with wild cards.
+//!
+//! Instead of trying every one of the 52 cards for every wild card, we only try every multiset of
+//! ranks for the wild cards, since suits only matter when the natural cards could make a flush.
+//! Hands without wild cards are ranked exactly like [`PokerHand::solve`].
+//!
+//! [`FiveOfAKind`]: crate::hand::FiveOfAKind
+
+use crate::card::{Card, Rank, Suit};
+use crate::hand::{FiveOfAKind, Hand, PokerHand, rank_multisets};
+
+/// Finds the best five-card hand that can be made out of the natural (non-wild) cards and `wilds`
+/// wild cards, such as jokers.
+///
+/// Returns [`None`] if there are fewer than 5 or more than 7 cards in total, or if any natural cards
+/// are duplicates.
+///
+/// # Examples
+///
+/// ```
+/// # use pokerlab_ref::card::Card;
+/// # use pokerlab_ref::wild::best_hand;
+/// #
+/// let naturals: Vec<Card> = ["Ks", "Kd", "5h", "5c"].map(|c| c.parse().unwrap()).to_vec();
+/// let best = best_hand(&naturals, 1).unwrap();
+/// assert_eq!(best.to_string(), "Full house, Kings full of Fives");
+/// ```
+pub fn best_hand(naturals: &[Card], wilds: usize) -> Option<PokerHand> {
+    if !(5..=7).contains(&(naturals.len() + wilds)) {
+        return None;
+    }
+    for (i, card) in naturals.iter().enumerate() {
+        // `Card` equality ignores suits, so compare them separately.
+        if naturals[..i]
+            .iter()
+            .any(|c| c.rank() == card.rank() && c.suit() == card.suit())
+        {
+            return None;
+        }
+    }
+
+    // A wild card can always become the natural card it replaces, so it never hurts to keep as
+    // many wild cards as possible.
+    let kept_wilds = wilds.min(5);
+    let kept_naturals = 5 - kept_wilds;
+
+    (0u32..1 << naturals.len())
+        .filter(|mask| mask.count_ones() as usize == kept_naturals)
+        .map(|mask| {
+            let picked: Vec<Card> = (0..naturals.len())
+                .filter(|&i| mask & (1 << i) != 0)
+                .map(|i| naturals[i])
+                .collect();
+            best_five(&picked, kept_wilds)
+        })
+        .max()
+}
+
+/// Finds the best hand in a "deuces wild" game, where every Two is a wild card.
+///
+/// Returns [`None`] if there are fewer than 5 or more than 7 cards, or if any cards are duplicates.
+pub fn deuces_wild(cards: &[Card]) -> Option<PokerHand> {
+    let naturals: Vec<Card> = cards
+        .iter()
+        .copied()
+        .filter(|card| card.rank() != Rank::Two)
+        .collect();
+    let wilds = cards.len() - naturals.len();
+
+    // The Twos themselves can't be duplicates of any natural card, but they could be of each other.
+    let mut suits: Vec<Suit> = Vec::with_capacity(wilds);
+    for card in cards.iter().filter(|card| card.rank() == Rank::Two) {
+        if suits.contains(&card.suit()) {
+            return None;
+        }
+        suits.push(card.suit());
+    }
+
+    best_hand(&naturals, wilds)
+}
+
+/// Finds the best hand made out of exactly 5 cards, `wilds` of which are wild.
+fn best_five(naturals: &[Card], wilds: usize) -> PokerHand {
+    if wilds == 0 {
+        let cards = naturals.try_into().expect("there are 5 natural cards");
+        return PokerHand::solve(Hand::new(cards).expect("cards were checked for duplicates"));
+    }
+
+    // If every natural card has the same rank, the wild cards can all match it.
+    let rank = naturals.first().map_or(Rank::Ace, |card| card.rank());
+    if naturals.iter().all(|card| card.rank() == rank) {
+        return PokerHand::FiveOfAKind(FiveOfAKind::new(rank));
+    }
+
+    let flush_suit = naturals
+        .iter()
+        .all(|card| card.suit() == naturals[0].suit())
+        .then_some(naturals[0].suit());
+
+    let mut best = None;
+    for wild_ranks in rank_multisets(wilds) {
+        let mut cards = naturals.to_vec();
+
+        // Without a flush, give every wild card a suit that its rank does not have yet.
+        for &rank in &wild_ranks {
+            let suit = Suit::ALL
+                .into_iter()
+                .find(|&suit| !cards.iter().any(|c| c.rank() == rank && c.suit() == suit));
+            let Some(suit) = suit else {
+                // All four cards of this rank are already in the hand.
+                break;
+            };
+            cards.push(Card::new(suit, rank));
+        }
+        if cards.len() == 5 {
+            best = best.max(Some(solve(&cards)));
+        }
+
+        // With a possible flush, try giving every wild card the same suit as the natural cards.
+        if let Some(suit) = flush_suit {
+            let mut ranks: Vec<Rank> = naturals.iter().map(|card| card.rank()).collect();
+            ranks.extend(&wild_ranks);
+            ranks.sort_unstable();
+            ranks.dedup();
+
+            if ranks.len() == 5 {
+                let cards: Vec<Card> = ranks
+                    .into_iter()
+                    .map(|rank| Card::new(suit, rank))
+                    .collect();
+                best = best.max(Some(solve(&cards)));
+            }
+        }
+    }
+
+    best.expect("some substitution is always possible")
+}
+
+/// Solves 5 distinct cards.
+fn solve(cards: &[Card]) -> PokerHand {
+    let cards = cards.try_into().expect("there are 5 cards");
+    PokerHand::solve(Hand::new(cards).expect("substitutions never duplicate a card"))
+}
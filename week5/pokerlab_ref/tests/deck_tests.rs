@@ -0,0 +1,117 @@
+use pokerlab_ref::card::{Card, Rank, Suit};
+use pokerlab_ref::deck::Deck;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Returns every card in a deck as `(suit, rank)` pairs, since [`Card`] equality ignores suits.
+fn identities(cards: &[Card]) -> Vec<(Suit, Rank)> {
+    cards
+        .iter()
+        .map(|card| (card.suit(), card.rank()))
+        .collect()
+}
+
+#[test]
+fn test_standard_deck() {
+    let deck = Deck::new();
+    assert_eq!(deck.remaining(), 52);
+
+    // Every card appears exactly once.
+    for suit in Suit::ALL {
+        for rank in Rank::ALL {
+            let card = Card::new(suit, rank);
+            let copies = identities(deck.cards())
+                .into_iter()
+                .filter(|&identity| identity == (suit, rank))
+                .count();
+            assert_eq!(copies, 1, "{card:?}");
+        }
+    }
+}
+
+#[test]
+fn test_short_and_multiple_decks() {
+    let short = Deck::short();
+    assert_eq!(short.remaining(), 36);
+    assert!(short.cards().iter().all(|card| card.rank() >= Rank::Six));
+    assert!(!short.contains(&Card::new(Suit::Spade, Rank::Five)));
+
+    let shoe = Deck::with_ranks(Rank::Two, 6);
+    assert_eq!(shoe.remaining(), 6 * 52);
+
+    let mut two_decks = Deck::with_ranks(Rank::Two, 2);
+    let ace_spades = Card::new(Suit::Spade, Rank::Ace);
+    assert!(two_decks.remove(&ace_spades));
+    assert!(two_decks.remove(&ace_spades));
+    assert!(!two_decks.remove(&ace_spades));
+    assert_eq!(two_decks.remaining(), 102);
+}
+
+#[test]
+fn test_seeded_shuffle() {
+    let mut a = Deck::new();
+    let mut b = Deck::new();
+    a.shuffle(&mut StdRng::seed_from_u64(7));
+    b.shuffle(&mut StdRng::seed_from_u64(7));
+    assert_eq!(identities(a.cards()), identities(b.cards()));
+
+    let mut c = Deck::new();
+    c.shuffle(&mut StdRng::seed_from_u64(8));
+    assert_ne!(identities(a.cards()), identities(c.cards()));
+
+    // Shuffling only changes the order of the cards.
+    let mut shuffled = identities(a.cards());
+    let mut fresh = identities(Deck::new().cards());
+    shuffled.sort_by_key(|&(suit, rank)| (suit as u8, rank));
+    fresh.sort_by_key(|&(suit, rank)| (suit as u8, rank));
+    assert_eq!(shuffled, fresh);
+}
+
+#[test]
+fn test_deal_and_burn() {
+    let cards = vec![
+        Card::new(Suit::Club, Rank::Two),
+        Card::new(Suit::Club, Rank::Three),
+        Card::new(Suit::Club, Rank::Four),
+        Card::new(Suit::Club, Rank::Five),
+        Card::new(Suit::Club, Rank::Six),
+    ];
+    let mut deck = Deck::from_cards(cards.clone());
+
+    // The last card is on top, so it is dealt first.
+    assert_eq!(
+        identities(&deck.deal(2).unwrap()),
+        identities(&[cards[4], cards[3]])
+    );
+    assert_eq!(identities(&[deck.burn().unwrap()]), identities(&[cards[2]]));
+
+    // Asking for too many cards deals nothing.
+    assert_eq!(deck.deal(3), None);
+    assert_eq!(deck.remaining(), 2);
+
+    assert_eq!(deck.deal(0), Some(vec![]));
+    assert_eq!(deck.deal_one().map(|card| card.rank()), Some(Rank::Three));
+    assert_eq!(deck.deal_one().map(|card| card.rank()), Some(Rank::Two));
+    assert!(deck.is_empty());
+    assert_eq!(deck.deal_one(), None);
+    assert_eq!(deck.burn(), None);
+}
+
+#[test]
+fn test_remove_checks_suit() {
+    let mut deck = Deck::new();
+    let ace_spades = Card::new(Suit::Spade, Rank::Ace);
+
+    assert!(deck.remove(&ace_spades));
+    assert!(!deck.contains(&ace_spades));
+
+    // The other aces are still there, even though they compare equal to the Ace of Spades.
+    assert!(!deck.remove(&ace_spades));
+    assert!(deck.contains(&Card::new(Suit::Heart, Rank::Ace)));
+    assert_eq!(deck.remaining(), 51);
+
+    // Known cards never get dealt.
+    deck.shuffle(&mut StdRng::seed_from_u64(0));
+    let dealt = deck.deal(51).unwrap();
+    assert!(!identities(&dealt).contains(&(Suit::Spade, Rank::Ace)));
+}
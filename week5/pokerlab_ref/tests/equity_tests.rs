@@ -0,0 +1,215 @@
+use pokerlab_ref::card::{Card, Rank::*, Suit::*};
+use pokerlab_ref::equity::{Settings, calculate};
+use pokerlab_ref::hand::PokerHand;
+
+/// Asserts that two probabilities are within `tolerance` of each other.
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "expected {expected} ± {tolerance}, got {actual}"
+    );
+}
+
+#[test]
+fn test_aces_against_kings_preflop() {
+    let aces = [Card::new(Spade, Ace), Card::new(Heart, Ace)];
+    let kings = [Card::new(Club, King), Card::new(Diamond, King)];
+
+    let settings = Settings {
+        samples: 50_000,
+        ..Settings::default()
+    };
+    let odds = calculate(&[aces, kings], &[], &settings).unwrap();
+    assert!(!odds.is_exact());
+
+    let [aces, kings] = odds.players() else {
+        panic!("expected two players");
+    };
+    assert_eq!(aces.runouts(), settings.samples);
+    assert_close(aces.equity(), 0.819, 0.01);
+    assert_close(kings.equity(), 0.181, 0.01);
+    assert_close(aces.win() + aces.tie() + aces.loss(), 1.0, 1e-9);
+    assert_eq!(aces.tie(), kings.tie());
+}
+
+#[test]
+fn test_seeded_results_are_reproducible() {
+    let players = [
+        [Card::new(Spade, Ace), Card::new(Spade, King)],
+        [Card::new(Heart, Queen), Card::new(Club, Queen)],
+        [Card::new(Diamond, Seven), Card::new(Diamond, Eight)],
+    ];
+    let settings = Settings {
+        samples: 20_000,
+        ..Settings::default()
+    };
+
+    let first = calculate(&players, &[], &settings).unwrap();
+    let second = calculate(&players, &[], &settings).unwrap();
+    assert_eq!(first, second);
+
+    let reseeded = calculate(
+        &players,
+        &[],
+        &Settings {
+            seed: 1,
+            ..settings
+        },
+    )
+    .unwrap();
+    assert_ne!(first, reseeded);
+
+    // Every runout's pot is handed out to someone.
+    let total: f64 = first.players().iter().map(|equity| equity.equity()).sum();
+    assert_close(total, 1.0, 1e-9);
+}
+
+#[test]
+fn test_exact_matches_best_from() {
+    let players = [
+        [Card::new(Spade, Ace), Card::new(Heart, Ace)],
+        [Card::new(Club, Nine), Card::new(Club, Ten)],
+    ];
+    let flop = [
+        Card::new(Club, Jack),
+        Card::new(Diamond, Eight),
+        Card::new(Club, Two),
+    ];
+
+    let odds = calculate(&players, &flop, &Settings::default()).unwrap();
+    assert!(odds.is_exact());
+    assert_eq!(odds.players()[0].runouts(), 45 * 44 / 2);
+
+    // Enumerate the turn and river by hand, using `PokerHand` to decide every showdown.
+    let known = [&players[0][..], &players[1][..], &flop[..]].concat();
+    let deck: Vec<Card> = pokerlab_ref::deck::Deck::new()
+        .cards()
+        .iter()
+        .copied()
+        .filter(|card| {
+            !known
+                .iter()
+                .any(|k| k.rank() == card.rank() && k.suit() == card.suit())
+        })
+        .collect();
+
+    let mut wins = [0; 2];
+    let mut ties = 0;
+    for turn in 0..deck.len() {
+        for river in turn + 1..deck.len() {
+            let board = [&flop[..], &[deck[turn], deck[river]]].concat();
+            let hands = players.map(|hole| PokerHand::best_from(&[&hole[..], &board].concat()));
+            match hands[0].cmp(&hands[1]) {
+                std::cmp::Ordering::Greater => wins[0] += 1,
+                std::cmp::Ordering::Less => wins[1] += 1,
+                std::cmp::Ordering::Equal => ties += 1,
+            }
+        }
+    }
+
+    let runouts = odds.players()[0].runouts() as f64;
+    assert_close(odds.players()[0].win(), wins[0] as f64 / runouts, 1e-12);
+    assert_close(odds.players()[1].win(), wins[1] as f64 / runouts, 1e-12);
+    assert_close(odds.players()[0].tie(), ties as f64 / runouts, 1e-12);
+}
+
+#[test]
+fn test_sampling_agrees_with_enumeration() {
+    let players = [
+        [Card::new(Spade, Queen), Card::new(Spade, Jack)],
+        [Card::new(Heart, Six), Card::new(Club, Six)],
+    ];
+    let flop = [
+        Card::new(Spade, Ten),
+        Card::new(Diamond, Two),
+        Card::new(Spade, Three),
+    ];
+
+    let exact = calculate(&players, &flop, &Settings::default()).unwrap();
+    let sampled = calculate(
+        &players,
+        &flop,
+        &Settings {
+            max_exact_runouts: 0,
+            samples: 50_000,
+            ..Settings::default()
+        },
+    )
+    .unwrap();
+    assert!(exact.is_exact());
+    assert!(!sampled.is_exact());
+
+    for (exact, sampled) in exact.players().iter().zip(sampled.players()) {
+        assert_close(sampled.equity(), exact.equity(), 0.01);
+    }
+}
+
+#[test]
+fn test_complete_board() {
+    // Both players play the royal flush on the board, and split the pot.
+    let board = [
+        Card::new(Heart, Ace),
+        Card::new(Heart, King),
+        Card::new(Heart, Queen),
+        Card::new(Heart, Jack),
+        Card::new(Heart, Ten),
+    ];
+    let players = [
+        [Card::new(Spade, Ace), Card::new(Spade, King)],
+        [Card::new(Club, Two), Card::new(Diamond, Three)],
+    ];
+
+    let odds = calculate(&players, &board, &Settings::default()).unwrap();
+    assert!(odds.is_exact());
+    for equity in odds.players() {
+        assert_eq!(equity.runouts(), 1);
+        assert_eq!(equity.tie(), 1.0);
+        assert_eq!(equity.equity(), 0.5);
+    }
+}
+
+#[test]
+fn test_invalid_input() {
+    let aces = [Card::new(Spade, Ace), Card::new(Heart, Ace)];
+    let kings = [Card::new(Spade, King), Card::new(Heart, King)];
+    let settings = Settings::default();
+
+    // Not enough players.
+    assert_eq!(calculate(&[aces], &[], &settings), None);
+
+    // The same card twice.
+    let board = [Card::new(Spade, Ace)];
+    assert_eq!(calculate(&[aces, kings], &board, &settings), None);
+
+    // Too many board cards.
+    let board = [
+        Card::new(Club, Two),
+        Card::new(Club, Three),
+        Card::new(Club, Four),
+        Card::new(Club, Five),
+        Card::new(Club, Six),
+        Card::new(Club, Seven),
+    ];
+    assert_eq!(calculate(&[aces, kings], &board, &settings), None);
+
+    // Too many runouts to enumerate, but no samples either.
+    let no_samples = Settings {
+        samples: 0,
+        ..settings
+    };
+    assert_eq!(calculate(&[aces, kings], &[], &no_samples), None);
+
+    // Without samples, spots that can be enumerated still work.
+    let flop = [
+        Card::new(Club, Two),
+        Card::new(Diamond, Seven),
+        Card::new(Club, Nine),
+    ];
+    let odds = calculate(&[aces, kings], &flop, &no_samples).unwrap();
+    assert!(odds.is_exact());
+    assert!(
+        odds.players()
+            .iter()
+            .all(|player| !player.equity().is_nan())
+    );
+}
@@ -0,0 +1,125 @@
+//! Tests for the lookup-table based [`evaluate`] function, checking that it agrees with
+//! [`PokerHand`] ordering.
+
+use pokerlab_ref::card::{Card, Rank, Rank::*, Suit, Suit::*};
+use pokerlab_ref::eval::{HandRank, NUM_HAND_RANKS, evaluate};
+use pokerlab_ref::hand::{Hand, PokerHand};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::collections::BTreeMap;
+
+/// Returns all 52 cards of a standard deck.
+fn deck() -> Vec<Card> {
+    Suit::ALL
+        .into_iter()
+        .flat_map(|suit| Rank::ALL.into_iter().map(move |rank| Card::new(suit, rank)))
+        .collect()
+}
+
+#[test]
+fn test_exhaustive_five_card_hands() {
+    let deck = deck();
+    let mut ranks: BTreeMap<PokerHand, HandRank> = BTreeMap::new();
+    let mut num_hands = 0;
+
+    for a in 0..52 {
+        for b in a + 1..52 {
+            for c in b + 1..52 {
+                for d in c + 1..52 {
+                    for e in d + 1..52 {
+                        let cards = [deck[a], deck[b], deck[c], deck[d], deck[e]];
+                        let solved = PokerHand::solve(Hand::new(cards).unwrap());
+                        let rank = evaluate(&cards).unwrap();
+
+                        // Equal poker hands must always get the same rank.
+                        assert_eq!(*ranks.entry(solved).or_insert(rank), rank, "{cards:?}");
+                        num_hands += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    assert_eq!(num_hands, 2_598_960);
+    assert_eq!(ranks.len(), NUM_HAND_RANKS as usize);
+
+    // Walking through the poker hands in order, the ranks must go up by exactly one each time.
+    for (i, rank) in ranks.values().enumerate() {
+        assert_eq!(rank.value() as usize, i + 1);
+    }
+}
+
+#[test]
+fn test_seven_card_hands_match_best_from() {
+    let mut deck = deck();
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+
+    let mut previous: Option<(Vec<Card>, PokerHand, HandRank)> = None;
+    for _ in 0..20_000 {
+        deck.shuffle(&mut rng);
+        let cards = deck[..7].to_vec();
+
+        let solved = PokerHand::best_from(&cards).unwrap();
+        let rank = evaluate(&cards).unwrap();
+
+        // Compare against the previous hand, so that we also check the ordering between hands.
+        if let Some((previous_cards, previous_solved, previous_rank)) = previous {
+            assert_eq!(
+                solved.cmp(&previous_solved),
+                rank.cmp(&previous_rank),
+                "{cards:?} vs {previous_cards:?}"
+            );
+        }
+        previous = Some((cards, solved, rank));
+    }
+}
+
+#[test]
+fn test_known_ranks() {
+    // Royal flush: (A♠ K♠ Q♠ J♠ T♠).
+    let royal_flush = [
+        Card::new(Spade, Ace),
+        Card::new(Spade, King),
+        Card::new(Spade, Queen),
+        Card::new(Spade, Jack),
+        Card::new(Spade, Ten),
+    ];
+    assert_eq!(evaluate(&royal_flush).unwrap().value(), NUM_HAND_RANKS);
+
+    // The worst possible hand: (7♠ 5♥ 4♣ 3♦ 2♠).
+    let seven_high = [
+        Card::new(Spade, Seven),
+        Card::new(Heart, Five),
+        Card::new(Club, Four),
+        Card::new(Diamond, Three),
+        Card::new(Spade, Two),
+    ];
+    assert_eq!(evaluate(&seven_high).unwrap().value(), 1);
+
+    // Adding two more cards can only make a hand better.
+    let mut seven_cards = seven_high.to_vec();
+    seven_cards.extend([Card::new(Heart, Seven), Card::new(Club, Seven)]);
+    assert!(evaluate(&seven_cards) > evaluate(&seven_high));
+}
+
+#[test]
+fn test_invalid_input() {
+    let cards = [
+        Card::new(Spade, Ace),
+        Card::new(Spade, King),
+        Card::new(Spade, Queen),
+        Card::new(Spade, Jack),
+        Card::new(Spade, Ten),
+        Card::new(Heart, Two),
+        Card::new(Heart, Three),
+        Card::new(Heart, Four),
+    ];
+
+    assert_eq!(evaluate(&cards[..4]), None);
+    assert_eq!(evaluate(&cards), None);
+
+    // The Ace of Spades twice.
+    let duplicates = [&cards[..5], &[Card::new(Spade, Ace)]].concat();
+    assert_eq!(evaluate(&duplicates), None);
+}
@@ -0,0 +1,323 @@
+//! Tests for playing out hold'em hands with [`Game`] and [`replay`].
+
+use pokerlab_ref::card::Card;
+use pokerlab_ref::game::{Action, Game, GameError, ReplayError, Street, replay};
+
+/// Returns every player's stack, in seat order.
+fn stacks(game: &Game) -> Vec<u64> {
+    game.players().iter().map(|player| player.stack()).collect()
+}
+
+/// Parses cards in short notation.
+fn cards(cards: &str) -> Vec<Card> {
+    cards
+        .split_whitespace()
+        .map(|c| c.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_replay_fold() {
+    let game = replay(
+        "
+        blinds 5 10
+        seat Alice 1000
+        seat Bob 1000
+        hole Alice As Ah
+        hole Bob Kd Kc
+        Alice raises 30
+        Bob calls
+        flop 2c 7d 9h
+        Bob checks
+        Alice bets 50
+        Bob folds
+        ",
+    )
+    .unwrap();
+
+    assert!(game.is_finished());
+    assert_eq!(stacks(&game), [1030, 970]);
+}
+
+#[test]
+fn test_replay_side_pots() {
+    // Bob has the best hand but can only win the main pot. Carol wins the side pot over Alice, who
+    // gets back the part of her bet nobody could call.
+    let game = replay(
+        "
+        blinds 5 10
+        seat Alice 1000
+        seat Bob 200
+        seat Carol 500
+        hole Alice Qs Qh
+        hole Bob As Ah
+        hole Carol Ks Kh
+        Alice raises 100
+        Bob all-in
+        Carol all-in
+        Alice calls
+        flop 2c 7d 9h
+        turn Jc
+        river 3s
+        ",
+    )
+    .unwrap();
+
+    assert!(game.is_finished());
+    assert_eq!(stacks(&game), [500, 600, 600]);
+}
+
+#[test]
+fn test_replay_split_pot() {
+    // Everyone plays the royal flush on the board. Bob folded his small blind, which leaves an odd
+    // chip for Carol, the first winner left of the button.
+    let game = replay(
+        "
+        blinds 5 10
+        seat Alice 1000
+        seat Bob 1000
+        seat Carol 1000
+        hole Alice 2c 3c
+        hole Carol 2d 3d
+        Alice calls
+        Bob folds
+        Carol checks
+        flop As Ks Qs
+        Carol checks
+        Alice checks
+        turn Js
+        Carol checks
+        Alice checks
+        river Ts
+        Carol checks
+        Alice checks
+        showdown
+        ",
+    )
+    .unwrap();
+
+    assert_eq!(stacks(&game), [1002, 995, 1003]);
+}
+
+#[test]
+fn test_big_blind_option() {
+    let mut game = Game::new(&[("Alice", 100), ("Bob", 100), ("Carol", 100)], 1, 2).unwrap();
+    assert_eq!(game.to_act(), Some(0));
+
+    game.act(0, Action::Call).unwrap();
+    game.act(1, Action::Call).unwrap();
+
+    // Everyone has matched the big blind, but Carol still gets to raise.
+    assert_eq!(game.to_act(), Some(2));
+    game.act(2, Action::Raise(8)).unwrap();
+    game.act(0, Action::Call).unwrap();
+    game.act(1, Action::Fold).unwrap();
+    assert_eq!(game.to_act(), None);
+    assert_eq!(game.pot(), 18);
+
+    game.deal(&cards("2c 7d 9h")).unwrap();
+    assert_eq!(game.street(), Street::Flop);
+    assert_eq!(game.to_act(), Some(2));
+}
+
+#[test]
+fn test_minimum_raises() {
+    let mut game = Game::new(&[("Alice", 100), ("Bob", 35)], 5, 10).unwrap();
+
+    // The first raise must at least double the big blind.
+    assert_eq!(
+        game.act(0, Action::Raise(15)),
+        Err(GameError::RaiseTooSmall { minimum: 20 })
+    );
+    game.act(0, Action::Raise(25)).unwrap();
+
+    // Re-raising must add at least as much as the last raise (15), unless it is all-in.
+    assert_eq!(
+        game.act(1, Action::Raise(36)),
+        Err(GameError::NotEnoughChips { stack: 25 })
+    );
+    game.act(1, Action::AllIn).unwrap();
+    assert_eq!(game.players()[1].stack(), 0);
+
+    // Alice faces the short all-in, and can only call the extra 10 or raise by 15 more.
+    assert_eq!(
+        game.act(0, Action::Raise(45)),
+        Err(GameError::RaiseTooSmall { minimum: 50 })
+    );
+    game.act(0, Action::Call).unwrap();
+    assert_eq!(game.to_act(), None);
+    assert_eq!(game.pot(), 70);
+}
+
+#[test]
+fn test_illegal_actions() {
+    let mut game = Game::new(&[("Alice", 100), ("Bob", 100), ("Carol", 100)], 1, 2).unwrap();
+
+    assert_eq!(
+        game.act(1, Action::Call),
+        Err(GameError::NotYourTurn {
+            expected: "Alice".to_string(),
+            actual: "Bob".to_string(),
+        })
+    );
+    assert_eq!(
+        game.act(0, Action::Check),
+        Err(GameError::CannotCheck { to_call: 2 })
+    );
+    assert_eq!(
+        game.act(0, Action::Bet(10)),
+        Err(GameError::AlreadyBet { current_bet: 2 })
+    );
+    assert_eq!(
+        game.act(0, Action::Raise(101)),
+        Err(GameError::NotEnoughChips { stack: 100 })
+    );
+    assert_eq!(
+        game.deal(&cards("2c 7d 9h")),
+        Err(GameError::BettingNotFinished)
+    );
+    assert_eq!(game.showdown(), Err(GameError::BettingNotFinished));
+    assert!(matches!(
+        game.act(5, Action::Fold),
+        Err(GameError::UnknownPlayer(_))
+    ));
+
+    game.deal_hole_cards(0, [cards("As")[0], cards("Ah")[0]])
+        .unwrap();
+    assert_eq!(
+        game.deal_hole_cards(1, [cards("As")[0], cards("Kh")[0]]),
+        Err(GameError::DuplicateCard(cards("As")[0]))
+    );
+
+    // Rejected actions do not change anything.
+    assert_eq!(game.to_act(), Some(0));
+    assert_eq!(game.pot(), 3);
+
+    game.act(0, Action::Call).unwrap();
+    game.act(1, Action::Call).unwrap();
+    game.act(2, Action::Check).unwrap();
+    assert_eq!(game.act(0, Action::Check), Err(GameError::BettingClosed));
+
+    assert_eq!(
+        game.deal(&cards("2c 7d")),
+        Err(GameError::WrongBoardSize {
+            expected: 3,
+            actual: 2
+        })
+    );
+    assert_eq!(
+        game.deal(&cards("2c 7d Ah")),
+        Err(GameError::DuplicateCard(cards("Ah")[0]))
+    );
+    game.deal(&cards("2c 7d 9h")).unwrap();
+
+    assert_eq!(game.act(1, Action::Call), Err(GameError::NothingToCall));
+    assert_eq!(
+        game.act(1, Action::Raise(4)),
+        Err(GameError::NothingToRaise)
+    );
+    assert_eq!(
+        game.act(1, Action::Bet(1)),
+        Err(GameError::RaiseTooSmall { minimum: 2 })
+    );
+}
+
+#[test]
+fn test_showdown_needs_hole_cards() {
+    let error = replay(
+        "
+        blinds 1 2
+        seat Alice 10
+        seat Bob 10
+        hole Alice As Ah
+        Alice all-in
+        Bob calls
+        flop 2c 7d 9h
+        turn Jc
+        river 3s
+        ",
+    )
+    .unwrap_err();
+
+    assert_eq!(error.error, GameError::MissingHoleCards("Bob".to_string()));
+}
+
+#[test]
+fn test_replay_errors() {
+    let history = "\
+blinds 1 2
+seat Alice 10
+seat Bob 10
+Bob calls
+";
+    assert_eq!(
+        replay(history).unwrap_err(),
+        ReplayError {
+            line: 4,
+            error: GameError::NotYourTurn {
+                expected: "Alice".to_string(),
+                actual: "Bob".to_string(),
+            },
+        }
+    );
+    assert_eq!(
+        replay(history).unwrap_err().to_string(),
+        "line 4: Bob acted out of turn, it is Alice's turn"
+    );
+
+    let error = replay("blinds 1 2\nseat Alice 10\nseat Bob 10\nhole Alice As 1h\n").unwrap_err();
+    assert_eq!(error.line, 4);
+    assert!(matches!(error.error, GameError::InvalidCard(_)));
+
+    let error = replay("blinds 1 2\nseat Alice 10\nseat Bob 10\nAlice dances\n").unwrap_err();
+    assert_eq!(
+        error.error,
+        GameError::InvalidLine("Alice dances".to_string())
+    );
+
+    let error = replay("blinds 1 2\nseat Alice 10\nDave folds\n").unwrap_err();
+    assert_eq!(error.error, GameError::NotEnoughPlayers);
+
+    // A street that is labelled as the wrong one is rejected, even with the right number of cards.
+    let history = "\
+blinds 1 2
+seat Alice 10
+seat Bob 10
+Alice calls
+Bob checks
+turn 2c 7d 9h
+";
+    let error = replay(history).unwrap_err();
+    assert_eq!(
+        error,
+        ReplayError {
+            line: 6,
+            error: GameError::WrongStreet {
+                expected: Street::Flop,
+                actual: Street::Turn,
+            },
+        }
+    );
+    assert_eq!(error.to_string(), "line 6: the flop is next, not the turn");
+
+    let history = "\
+blinds 1 2
+seat Alice 10
+seat Bob 10
+Alice calls
+Bob checks
+flop 2c 7d 9h
+Bob checks
+Alice checks
+river Ks
+";
+    let error = replay(history).unwrap_err();
+    assert_eq!(error.line, 9);
+    assert_eq!(
+        error.error,
+        GameError::WrongStreet {
+            expected: Street::Turn,
+            actual: Street::River,
+        }
+    );
+}
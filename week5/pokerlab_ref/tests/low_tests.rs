@@ -0,0 +1,416 @@
+//! Tests for low hand rankings ([`AceToFiveLow`] and [`DeuceToSevenLow`]) and hi/lo pot splitting.
+//!
+//! Note that we are importing every variant of both [`Rank`] and [`Suit`] directly into this module
+//! to make things easier to read.
+//!
+//! [`Rank`]: pokerlab_ref::card::Rank
+//! [`Suit`]: pokerlab_ref::card::Suit
+
+use pokerlab_ref::card::{Card, Rank::*, Suit::*};
+use pokerlab_ref::hand::{Hand, PokerHand};
+use pokerlab_ref::low::{AceToFiveLow, DeuceToSevenLow, split_hi_lo};
+
+fn assert_tie_a5(hand1: Hand, hand2: Hand) {
+    assert_eq!(AceToFiveLow::solve(hand1), AceToFiveLow::solve(hand2));
+}
+
+fn assert_winner_a5(winner: Hand, loser: Hand) {
+    assert!(AceToFiveLow::solve(winner) > AceToFiveLow::solve(loser));
+}
+
+fn assert_tie_d7(hand1: Hand, hand2: Hand) {
+    assert_eq!(DeuceToSevenLow::solve(hand1), DeuceToSevenLow::solve(hand2));
+}
+
+fn assert_winner_d7(winner: Hand, loser: Hand) {
+    assert!(DeuceToSevenLow::solve(winner) > DeuceToSevenLow::solve(loser));
+}
+
+#[test]
+fn test_ace_to_five_wheel_vs_six_low() {
+    // Wheel: (5♠ 4♣ 3♦ 2♥ A♣).
+    let wheel_hand = Hand::new([
+        Card::new(Spade, Five),
+        Card::new(Club, Four),
+        Card::new(Diamond, Three),
+        Card::new(Heart, Two),
+        Card::new(Club, Ace),
+    ])
+    .unwrap();
+
+    // Six low: (6♠ 4♣ 3♦ 2♥ A♣).
+    let six_low_hand = Hand::new([
+        Card::new(Spade, Six),
+        Card::new(Club, Four),
+        Card::new(Diamond, Three),
+        Card::new(Heart, Two),
+        Card::new(Club, Ace),
+    ])
+    .unwrap();
+
+    assert_winner_a5(wheel_hand, six_low_hand);
+}
+
+#[test]
+fn test_ace_to_five_ignores_straights_and_flushes() {
+    // Straights and flushes do not count, so a suited wheel is still the best low.
+    // Suited wheel: (5♠ 4♠ 3♠ 2♠ A♠).
+    let wheel_flush_hand = Hand::new([
+        Card::new(Spade, Five),
+        Card::new(Spade, Four),
+        Card::new(Spade, Three),
+        Card::new(Spade, Two),
+        Card::new(Spade, Ace),
+    ])
+    .unwrap();
+
+    // Six low: (6♠ 4♣ 3♦ 2♥ A♣).
+    let six_low_hand = Hand::new([
+        Card::new(Spade, Six),
+        Card::new(Club, Four),
+        Card::new(Diamond, Three),
+        Card::new(Heart, Two),
+        Card::new(Club, Ace),
+    ])
+    .unwrap();
+
+    assert_winner_a5(wheel_flush_hand, six_low_hand);
+}
+
+#[test]
+fn test_ace_to_five_ace_is_low() {
+    // Seven low with an Ace: (7♠ 5♣ 4♦ 3♥ A♣).
+    let ace_low_hand = Hand::new([
+        Card::new(Spade, Seven),
+        Card::new(Club, Five),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Ace),
+    ])
+    .unwrap();
+
+    // Seven low with a Two: (7♣ 5♦ 4♥ 3♠ 2♣).
+    let deuce_low_hand = Hand::new([
+        Card::new(Club, Seven),
+        Card::new(Diamond, Five),
+        Card::new(Heart, Four),
+        Card::new(Spade, Three),
+        Card::new(Club, Two),
+    ])
+    .unwrap();
+
+    assert_winner_a5(ace_low_hand, deuce_low_hand);
+}
+
+#[test]
+fn test_ace_to_five_second_card_decides() {
+    // Eight-six low: (8♠ 6♣ 4♦ 3♥ 2♣).
+    let eight_six_hand = Hand::new([
+        Card::new(Spade, Eight),
+        Card::new(Club, Six),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Two),
+    ])
+    .unwrap();
+
+    // Eight-seven low: (8♣ 7♦ 3♠ 2♥ A♦).
+    let eight_seven_hand = Hand::new([
+        Card::new(Club, Eight),
+        Card::new(Diamond, Seven),
+        Card::new(Spade, Three),
+        Card::new(Heart, Two),
+        Card::new(Diamond, Ace),
+    ])
+    .unwrap();
+
+    assert_winner_a5(eight_six_hand, eight_seven_hand);
+}
+
+#[test]
+fn test_ace_to_five_no_pair_vs_pair() {
+    // King low: (K♠ Q♣ J♦ T♥ 9♣).
+    let king_low_hand = Hand::new([
+        Card::new(Spade, King),
+        Card::new(Club, Queen),
+        Card::new(Diamond, Jack),
+        Card::new(Heart, Ten),
+        Card::new(Club, Nine),
+    ])
+    .unwrap();
+
+    // Pair of Aces: (A♠ A♣ 2♦ 3♥ 4♣).
+    let pair_of_aces_hand = Hand::new([
+        Card::new(Spade, Ace),
+        Card::new(Club, Ace),
+        Card::new(Diamond, Two),
+        Card::new(Heart, Three),
+        Card::new(Club, Four),
+    ])
+    .unwrap();
+
+    assert_winner_a5(king_low_hand, pair_of_aces_hand);
+}
+
+#[test]
+fn test_ace_to_five_pair_vs_pair() {
+    // Aces are low, so a pair of Aces is a smaller pair than a pair of Twos.
+    // Pair of Aces: (A♠ A♣ 5♦ 3♥ 4♣).
+    let pair_of_aces_hand = Hand::new([
+        Card::new(Spade, Ace),
+        Card::new(Club, Ace),
+        Card::new(Diamond, Five),
+        Card::new(Heart, Three),
+        Card::new(Club, Four),
+    ])
+    .unwrap();
+
+    // Pair of Twos: (2♠ 2♣ A♦ 3♥ 4♣).
+    let pair_of_twos_hand = Hand::new([
+        Card::new(Spade, Two),
+        Card::new(Club, Two),
+        Card::new(Diamond, Ace),
+        Card::new(Heart, Three),
+        Card::new(Club, Four),
+    ])
+    .unwrap();
+
+    assert_winner_a5(pair_of_aces_hand, pair_of_twos_hand);
+}
+
+#[test]
+fn test_ace_to_five_tie() {
+    // First seven low: (7♠ 5♣ 4♦ 3♥ A♣).
+    let first_hand = Hand::new([
+        Card::new(Spade, Seven),
+        Card::new(Club, Five),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Ace),
+    ])
+    .unwrap();
+
+    // Second seven low: (7♥ 5♦ 4♠ 3♣ A♦).
+    let second_hand = Hand::new([
+        Card::new(Heart, Seven),
+        Card::new(Diamond, Five),
+        Card::new(Spade, Four),
+        Card::new(Club, Three),
+        Card::new(Diamond, Ace),
+    ])
+    .unwrap();
+
+    assert_tie_a5(first_hand, second_hand);
+}
+
+#[test]
+fn test_deuce_to_seven_number_one_vs_wheel() {
+    // Aces are high, so A-5-4-3-2 is an Ace-high hand rather than a straight.
+    // Seven-five low: (7♠ 5♣ 4♦ 3♥ 2♣).
+    let number_one_hand = Hand::new([
+        Card::new(Spade, Seven),
+        Card::new(Club, Five),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Two),
+    ])
+    .unwrap();
+
+    // A-5-4-3-2: (A♠ 5♣ 4♦ 3♥ 2♣).
+    let ace_high_hand = Hand::new([
+        Card::new(Spade, Ace),
+        Card::new(Club, Five),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Two),
+    ])
+    .unwrap();
+
+    assert_winner_d7(number_one_hand, ace_high_hand);
+}
+
+#[test]
+fn test_deuce_to_seven_straight_counts() {
+    // Eight low: (8♠ 6♣ 5♦ 4♥ 3♣).
+    let eight_low_hand = Hand::new([
+        Card::new(Spade, Eight),
+        Card::new(Club, Six),
+        Card::new(Diamond, Five),
+        Card::new(Heart, Four),
+        Card::new(Club, Three),
+    ])
+    .unwrap();
+
+    // Seven-high straight: (7♠ 6♣ 5♦ 4♥ 3♣).
+    let straight_hand = Hand::new([
+        Card::new(Spade, Seven),
+        Card::new(Club, Six),
+        Card::new(Diamond, Five),
+        Card::new(Heart, Four),
+        Card::new(Club, Three),
+    ])
+    .unwrap();
+
+    assert_winner_d7(eight_low_hand, straight_hand);
+}
+
+#[test]
+fn test_deuce_to_seven_flush_counts() {
+    // Pair of Twos: (2♠ 2♣ 7♦ 5♥ 3♣).
+    let pair_hand = Hand::new([
+        Card::new(Spade, Two),
+        Card::new(Club, Two),
+        Card::new(Diamond, Seven),
+        Card::new(Heart, Five),
+        Card::new(Club, Three),
+    ])
+    .unwrap();
+
+    // Seven-high flush: (7♥ 5♥ 4♥ 3♥ 2♥).
+    let flush_hand = Hand::new([
+        Card::new(Heart, Seven),
+        Card::new(Heart, Five),
+        Card::new(Heart, Four),
+        Card::new(Heart, Three),
+        Card::new(Heart, Two),
+    ])
+    .unwrap();
+
+    assert_winner_d7(pair_hand, flush_hand);
+}
+
+#[test]
+fn test_deuce_to_seven_tie() {
+    // First eight low: (8♠ 6♣ 5♦ 4♥ 3♣).
+    let first_hand = Hand::new([
+        Card::new(Spade, Eight),
+        Card::new(Club, Six),
+        Card::new(Diamond, Five),
+        Card::new(Heart, Four),
+        Card::new(Club, Three),
+    ])
+    .unwrap();
+
+    // Second eight low: (8♥ 6♦ 5♠ 4♣ 3♦).
+    let second_hand = Hand::new([
+        Card::new(Heart, Eight),
+        Card::new(Diamond, Six),
+        Card::new(Spade, Five),
+        Card::new(Club, Four),
+        Card::new(Diamond, Three),
+    ])
+    .unwrap();
+
+    assert_tie_d7(first_hand, second_hand);
+}
+
+#[test]
+fn test_eight_or_better() {
+    // Eight low: (8♠ 7♣ 4♦ 3♥ A♣).
+    let eight_low_hand = Hand::new([
+        Card::new(Spade, Eight),
+        Card::new(Club, Seven),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Ace),
+    ])
+    .unwrap();
+    assert!(AceToFiveLow::solve(eight_low_hand).is_eight_or_better());
+
+    // Nine low: (9♠ 5♣ 4♦ 3♥ A♣).
+    let nine_low_hand = Hand::new([
+        Card::new(Spade, Nine),
+        Card::new(Club, Five),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Ace),
+    ])
+    .unwrap();
+    assert!(!AceToFiveLow::solve(nine_low_hand).is_eight_or_better());
+
+    // Paired wheel cards: (5♠ 5♣ 4♦ 3♥ A♣).
+    let paired_hand = Hand::new([
+        Card::new(Spade, Five),
+        Card::new(Club, Five),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Ace),
+    ])
+    .unwrap();
+    assert!(!AceToFiveLow::solve(paired_hand).is_eight_or_better());
+}
+
+#[test]
+fn test_razz_best_from_seven() {
+    // Seven cards: (K♠ 8♣ 5♦ 5♥ 3♣ 2♠ A♦).
+    let cards = [
+        Card::new(Spade, King),
+        Card::new(Club, Eight),
+        Card::new(Diamond, Five),
+        Card::new(Heart, Five),
+        Card::new(Club, Three),
+        Card::new(Spade, Two),
+        Card::new(Diamond, Ace),
+    ];
+
+    // The best low ignores the King and the second Five.
+    // Best low: (8♣ 5♦ 3♣ 2♠ A♦).
+    let best_hand = Hand::new([
+        Card::new(Club, Eight),
+        Card::new(Diamond, Five),
+        Card::new(Club, Three),
+        Card::new(Spade, Two),
+        Card::new(Diamond, Ace),
+    ])
+    .unwrap();
+    assert_eq!(
+        AceToFiveLow::best_from(&cards),
+        Some(AceToFiveLow::solve(best_hand))
+    );
+    assert_eq!(AceToFiveLow::best_from(&cards[..4]), None);
+}
+
+#[test]
+fn test_split_hi_lo() {
+    // Flush: (K♠ J♠ 9♠ 6♠ 4♠).
+    let high_hand = Hand::new([
+        Card::new(Spade, King),
+        Card::new(Spade, Jack),
+        Card::new(Spade, Nine),
+        Card::new(Spade, Six),
+        Card::new(Spade, Four),
+    ])
+    .unwrap();
+
+    // Wheel straight: (5♣ 4♦ 3♥ 2♣ A♦).
+    let low_hand = Hand::new([
+        Card::new(Club, Five),
+        Card::new(Diamond, Four),
+        Card::new(Heart, Three),
+        Card::new(Club, Two),
+        Card::new(Diamond, Ace),
+    ])
+    .unwrap();
+
+    let high = (PokerHand::solve(high_hand), None);
+    let low = (
+        PokerHand::solve(low_hand),
+        Some(AceToFiveLow::solve(low_hand)),
+    );
+
+    // The flush scoops the high half, and the wheel takes the low half (plus the odd chip goes
+    // high).
+    assert_eq!(split_hi_lo(101, &[high, low]), [51, 50]);
+
+    // Without a qualifying low, the high hand takes everything.
+    assert_eq!(
+        split_hi_lo(101, &[high, (PokerHand::solve(low_hand), None)]),
+        [101, 0]
+    );
+
+    // The same hand can win both halves.
+    assert_eq!(
+        split_hi_lo(100, &[low, (PokerHand::solve(low_hand), None)]),
+        [75, 25]
+    );
+}
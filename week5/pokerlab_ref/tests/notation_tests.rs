@@ -0,0 +1,119 @@
+//! Tests for parsing and formatting cards and hands in short notation.
+
+use pokerlab_ref::card::{Card, ParseCardError, Rank, Suit};
+use pokerlab_ref::hand::{Hand, PokerHand};
+
+#[test]
+fn test_card_round_trip() {
+    let mut seen = Vec::new();
+
+    for suit in Suit::ALL {
+        for rank in Rank::ALL {
+            let card = Card::new(suit, rank);
+            let formatted = card.to_string();
+            let parsed: Card = formatted.parse().unwrap();
+
+            assert_eq!((parsed.suit(), parsed.rank()), (suit, rank), "{formatted}");
+            seen.push(formatted);
+        }
+    }
+
+    // All 52 cards are formatted differently.
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 52);
+}
+
+#[test]
+fn test_card_notation() {
+    assert_eq!(Card::new(Suit::Spade, Rank::Ten).to_string(), "Ts");
+    assert_eq!(Card::new(Suit::Heart, Rank::Ace).to_string(), "Ah");
+    assert_eq!(Card::new(Suit::Diamond, Rank::Two).to_string(), "2d");
+    assert_eq!(Card::new(Suit::Club, Rank::Queen).to_string(), "Qc");
+
+    // Either case is accepted when parsing.
+    let card: Card = "tS".parse().unwrap();
+    assert_eq!((card.suit(), card.rank()), (Suit::Spade, Rank::Ten));
+}
+
+#[test]
+fn test_card_errors() {
+    assert_eq!(
+        "".parse::<Card>(),
+        Err(ParseCardError::InvalidLength("".to_string()))
+    );
+    assert_eq!(
+        "10s".parse::<Card>(),
+        Err(ParseCardError::InvalidLength("10s".to_string()))
+    );
+    assert_eq!(
+        "1s".parse::<Card>(),
+        Err(ParseCardError::InvalidRank("1s".to_string()))
+    );
+    assert_eq!(
+        "Ax".parse::<Card>(),
+        Err(ParseCardError::InvalidSuit("Ax".to_string()))
+    );
+    assert_eq!(
+        "Ax".parse::<Card>().unwrap_err().to_string(),
+        "`Ax` does not end with a valid suit"
+    );
+}
+
+#[test]
+fn test_hand_round_trip() {
+    let hand: Hand = "Ts Jc Qh Kd As".parse().unwrap();
+    assert_eq!(hand.to_string(), "As Kd Qh Jc Ts");
+
+    let reparsed: Hand = hand.to_string().parse().unwrap();
+    assert_eq!(reparsed.to_string(), hand.to_string());
+
+    // Any amount of whitespace separates cards.
+    let spaced: Hand = "  2c\t3c  4c 5c\n6c ".parse().unwrap();
+    assert_eq!(spaced.to_string(), "6c 5c 4c 3c 2c");
+}
+
+#[test]
+fn test_hand_errors() {
+    assert_eq!(
+        "As Kd Qh Jc".parse::<Hand>().unwrap_err(),
+        ParseCardError::WrongCardCount("As Kd Qh Jc".to_string())
+    );
+    assert_eq!(
+        "As Kd Qh Jc Ts 9s".parse::<Hand>().unwrap_err(),
+        ParseCardError::WrongCardCount("As Kd Qh Jc Ts 9s".to_string())
+    );
+    assert_eq!(
+        "As Kd Qh As Ts".parse::<Hand>().unwrap_err(),
+        ParseCardError::DuplicateCard("As".to_string())
+    );
+    assert_eq!(
+        "As Kd Qz Jc Ts".parse::<Hand>().unwrap_err(),
+        ParseCardError::InvalidSuit("Qz".to_string())
+    );
+
+    // The same rank in different suits is not a duplicate.
+    assert!("As Ad Qh Jc Ts".parse::<Hand>().is_ok());
+}
+
+#[test]
+fn test_poker_hand_display() {
+    let cases = [
+        ("As Kd 9h 7c 3s", "High card, Ace high"),
+        ("Ks Kd 9h 7c 3s", "One pair, Kings"),
+        ("Ks Kd 5h 5c 3s", "Two pair, Kings and Fives"),
+        ("7s 7d 7h Kc 3s", "Three of a kind, Sevens"),
+        ("As Kd Qh Jc Ts", "Straight, Ace high"),
+        ("5s 4d 3h 2c As", "Straight, Five high"),
+        ("Kh 9h 7h 4h 2h", "Flush, King high"),
+        ("Ts Td Th 4c 4s", "Full house, Tens full of Fours"),
+        ("6s 6d 6h 6c 2s", "Four of a kind, Sixes"),
+        ("9c 8c 7c 6c 5c", "Straight flush, Nine high"),
+        ("Ad Kd Qd Jd Td", "Royal flush"),
+    ];
+
+    for (hand, expected) in cases {
+        let solved = PokerHand::solve(hand.parse().unwrap());
+        assert_eq!(solved.to_string(), expected, "{hand}");
+    }
+}
@@ -0,0 +1,224 @@
+//! Tests for [`OmahaHand`], where exactly two hole cards and three community cards must be used.
+//!
+//! Note that we are importing every variant of both [`Rank`] and [`Suit`] directly into this module
+//! to make things easier to read.
+//!
+//! [`Rank`]: pokerlab_ref::card::Rank
+//! [`Suit`]: pokerlab_ref::card::Suit
+
+use pokerlab_ref::card::{Card, Rank::*, Suit::*};
+use pokerlab_ref::hand::{Hand, PokerHand};
+use pokerlab_ref::low::{AceToFiveLow, split_hi_lo};
+use pokerlab_ref::omaha::OmahaHand;
+
+#[test]
+fn test_four_flush_in_hand_is_not_a_flush() {
+    // Four Hearts in hand but only two on the board is not a flush.
+    // Hole cards: (A♥ K♥ 7♥ 6♥).
+    let hole = [
+        Card::new(Heart, Ace),
+        Card::new(Heart, King),
+        Card::new(Heart, Seven),
+        Card::new(Heart, Six),
+    ];
+    // Board: (Q♥ 9♥ 4♣ 3♠ 2♦).
+    let board = [
+        Card::new(Heart, Queen),
+        Card::new(Heart, Nine),
+        Card::new(Club, Four),
+        Card::new(Spade, Three),
+        Card::new(Diamond, Two),
+    ];
+    let omaha = OmahaHand::new(hole, &board).unwrap();
+
+    // Ace-high: (A♥ K♥ Q♥ 9♥ 4♣).
+    let best_high = Hand::new([
+        Card::new(Heart, Ace),
+        Card::new(Heart, King),
+        Card::new(Heart, Queen),
+        Card::new(Heart, Nine),
+        Card::new(Club, Four),
+    ])
+    .unwrap();
+    assert_eq!(omaha.high(), PokerHand::solve(best_high));
+
+    // Six low: (6♥ A♥ 4♣ 3♠ 2♦).
+    let best_low = Hand::new([
+        Card::new(Heart, Six),
+        Card::new(Heart, Ace),
+        Card::new(Club, Four),
+        Card::new(Spade, Three),
+        Card::new(Diamond, Two),
+    ])
+    .unwrap();
+    assert_eq!(omaha.low(), Some(AceToFiveLow::solve(best_low)));
+}
+
+#[test]
+fn test_board_quads_need_two_hole_cards() {
+    // The board has four Queens, but only three of them can play.
+    // Hole cards: (A♠ A♦ 9♣ 8♣).
+    let hole = [
+        Card::new(Spade, Ace),
+        Card::new(Diamond, Ace),
+        Card::new(Club, Nine),
+        Card::new(Club, Eight),
+    ];
+    // Board: (Q♠ Q♥ Q♦ Q♣ 5♥).
+    let board = [
+        Card::new(Spade, Queen),
+        Card::new(Heart, Queen),
+        Card::new(Diamond, Queen),
+        Card::new(Club, Queen),
+        Card::new(Heart, Five),
+    ];
+    let omaha = OmahaHand::new(hole, &board).unwrap();
+
+    // Full house: (Q♠ Q♥ Q♦ A♠ A♦).
+    let best_high = Hand::new([
+        Card::new(Spade, Queen),
+        Card::new(Heart, Queen),
+        Card::new(Diamond, Queen),
+        Card::new(Spade, Ace),
+        Card::new(Diamond, Ace),
+    ])
+    .unwrap();
+    assert_eq!(omaha.high(), PokerHand::solve(best_high));
+    assert_eq!(omaha.low(), None);
+}
+
+#[test]
+fn test_must_use_two_hole_cards() {
+    // One Ace in hand with a four-card straight on the board is not a straight.
+    // Hole cards: (A♠ 9♦ 9♥ 2♣).
+    let hole = [
+        Card::new(Spade, Ace),
+        Card::new(Diamond, Nine),
+        Card::new(Heart, Nine),
+        Card::new(Club, Two),
+    ];
+    // Board: (K♠ Q♦ J♣ T♥ 3♠).
+    let board = [
+        Card::new(Spade, King),
+        Card::new(Diamond, Queen),
+        Card::new(Club, Jack),
+        Card::new(Heart, Ten),
+        Card::new(Spade, Three),
+    ];
+    let omaha = OmahaHand::new(hole, &board).unwrap();
+
+    // Pair of Nines: (K♠ Q♦ J♣ 9♦ 9♥).
+    let best_high = Hand::new([
+        Card::new(Spade, King),
+        Card::new(Diamond, Queen),
+        Card::new(Club, Jack),
+        Card::new(Diamond, Nine),
+        Card::new(Heart, Nine),
+    ])
+    .unwrap();
+    assert_eq!(omaha.high(), PokerHand::solve(best_high));
+    assert_eq!(omaha.low(), None);
+}
+
+#[test]
+fn test_low_counterfeited() {
+    // The Deuce in hand is duplicated on the board, so the best low uses the Ace and Three.
+    // Hole cards: (A♦ 2♦ K♣ K♠).
+    let hole = [
+        Card::new(Diamond, Ace),
+        Card::new(Diamond, Two),
+        Card::new(Club, King),
+        Card::new(Spade, King),
+    ];
+    // Board: (2♣ 4♥ 5♠ 8♦ Q♥).
+    let board = [
+        Card::new(Club, Two),
+        Card::new(Heart, Four),
+        Card::new(Spade, Five),
+        Card::new(Diamond, Eight),
+        Card::new(Heart, Queen),
+    ];
+    let omaha = OmahaHand::new(hole, &board).unwrap();
+
+    // Pair of Kings: (K♣ K♠ Q♥ 8♦ 5♠).
+    let best_high = Hand::new([
+        Card::new(Club, King),
+        Card::new(Spade, King),
+        Card::new(Heart, Queen),
+        Card::new(Diamond, Eight),
+        Card::new(Spade, Five),
+    ])
+    .unwrap();
+    assert_eq!(omaha.high(), PokerHand::solve(best_high));
+
+    // Eight low: (A♦ 8♦ 5♠ 4♥ 2♣).
+    let best_low = Hand::new([
+        Card::new(Diamond, Ace),
+        Card::new(Diamond, Eight),
+        Card::new(Spade, Five),
+        Card::new(Heart, Four),
+        Card::new(Club, Two),
+    ])
+    .unwrap();
+    assert_eq!(omaha.low(), Some(AceToFiveLow::solve(best_low)));
+}
+
+#[test]
+fn test_omaha_hi_lo_split() {
+    // Board: (2♣ 4♥ 5♠ 8♦ K♥).
+    let board = [
+        Card::new(Club, Two),
+        Card::new(Heart, Four),
+        Card::new(Spade, Five),
+        Card::new(Diamond, Eight),
+        Card::new(Heart, King),
+    ];
+    // Nut low hole cards: (A♣ 3♦ J♠ J♣).
+    let nut_low_hole = [
+        Card::new(Club, Ace),
+        Card::new(Diamond, Three),
+        Card::new(Spade, Jack),
+        Card::new(Club, Jack),
+    ];
+    // Set of Kings hole cards: (K♣ K♠ 9♦ 9♥).
+    let set_hole = [
+        Card::new(Club, King),
+        Card::new(Spade, King),
+        Card::new(Diamond, Nine),
+        Card::new(Heart, Nine),
+    ];
+
+    let players = [
+        OmahaHand::new(nut_low_hole, &board).unwrap(),
+        OmahaHand::new(set_hole, &board).unwrap(),
+    ]
+    .map(|omaha| (omaha.high(), omaha.low()));
+
+    // The wheel wins both the high (as a straight) and the low, so it scoops the whole pot.
+    assert_eq!(split_hi_lo(200, &players), [200, 0]);
+}
+
+#[test]
+fn test_invalid_omaha_hands() {
+    // Hole cards: (A♣ 3♦ J♠ J♣).
+    let hole = [
+        Card::new(Club, Ace),
+        Card::new(Diamond, Three),
+        Card::new(Spade, Jack),
+        Card::new(Club, Jack),
+    ];
+
+    // Too few community cards, and a duplicate card.
+    assert!(OmahaHand::new(hole, &[Card::new(Club, Two), Card::new(Heart, Four)]).is_none());
+    assert!(
+        OmahaHand::new(
+            hole,
+            &[
+                Card::new(Club, Ace),
+                Card::new(Heart, Four),
+                Card::new(Spade, Five)
+            ]
+        )
+        .is_none()
+    );
+}
@@ -772,3 +772,181 @@ fn test_straight_flush_vs_straight_flush() {
 
     assert_winner(higher_straight_flush, lower_straight_flush);
 }
+
+#[test]
+fn test_best_from_board_plays() {
+    // Board: (A♠ K♠ Q♦ J♣ T♥), which is already a Broadway straight.
+    let board = [
+        Card::new(Spade, Ace),
+        Card::new(Spade, King),
+        Card::new(Diamond, Queen),
+        Card::new(Club, Jack),
+        Card::new(Heart, Ten),
+    ];
+
+    // Neither player's hole cards improve on the board, so they split the pot.
+    let first_player = [
+        board.as_slice(),
+        &[Card::new(Club, Two), Card::new(Heart, Three)],
+    ]
+    .concat();
+    let second_player = [
+        board.as_slice(),
+        &[Card::new(Diamond, Four), Card::new(Heart, Four)],
+    ]
+    .concat();
+
+    let board_hand = PokerHand::solve(Hand::new(board).unwrap());
+    assert_eq!(PokerHand::best_from(&first_player), Some(board_hand));
+    assert_eq!(PokerHand::best_from(&second_player), Some(board_hand));
+}
+
+#[test]
+fn test_best_from_kickers() {
+    // Seven cards: (K♠ K♥ A♦ Q♣ 9♠ 4♥ 2♣). Only the three highest kickers play.
+    let cards = [
+        Card::new(Spade, King),
+        Card::new(Heart, King),
+        Card::new(Diamond, Ace),
+        Card::new(Club, Queen),
+        Card::new(Spade, Nine),
+        Card::new(Heart, Four),
+        Card::new(Club, Two),
+    ];
+
+    // Best hand: (K♠ K♥ A♦ Q♣ 9♠).
+    let best_hand = Hand::new([
+        Card::new(Spade, King),
+        Card::new(Heart, King),
+        Card::new(Diamond, Ace),
+        Card::new(Club, Queen),
+        Card::new(Spade, Nine),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        PokerHand::best_from(&cards),
+        Some(PokerHand::solve(best_hand))
+    );
+}
+
+#[test]
+fn test_best_from_two_straights() {
+    // Seven cards: (9♠ 8♥ 7♦ 6♣ 5♠ 4♥ K♣), which contain both an 8-high and a 9-high straight.
+    let cards = [
+        Card::new(Spade, Nine),
+        Card::new(Heart, Eight),
+        Card::new(Diamond, Seven),
+        Card::new(Club, Six),
+        Card::new(Spade, Five),
+        Card::new(Heart, Four),
+        Card::new(Club, King),
+    ];
+
+    // Best hand: (9♠ 8♥ 7♦ 6♣ 5♠).
+    let nine_high = Hand::new([
+        Card::new(Spade, Nine),
+        Card::new(Heart, Eight),
+        Card::new(Diamond, Seven),
+        Card::new(Club, Six),
+        Card::new(Spade, Five),
+    ])
+    .unwrap();
+
+    // Lower straight: (8♥ 7♦ 6♣ 5♠ 4♥).
+    let eight_high = Hand::new([
+        Card::new(Heart, Eight),
+        Card::new(Diamond, Seven),
+        Card::new(Club, Six),
+        Card::new(Spade, Five),
+        Card::new(Heart, Four),
+    ])
+    .unwrap();
+
+    let best = PokerHand::best_from(&cards).unwrap();
+    assert_eq!(best, PokerHand::solve(nine_high));
+    assert!(best > PokerHand::solve(eight_high));
+}
+
+// Note that seven cards can never contain both a flush and a full house: five cards of one suit
+// all have different ranks, leaving only two more cards to make a triple and a pair. The next two
+// tests cover the closest cases, where a flush or a full house competes with a near miss of the
+// other.
+
+#[test]
+fn test_best_from_flush_over_two_pair() {
+    // Seven cards: (A♥ Q♥ Q♦ 9♥ 9♣ 5♥ 2♥), which contain two pair and a heart flush.
+    let cards = [
+        Card::new(Heart, Ace),
+        Card::new(Heart, Queen),
+        Card::new(Diamond, Queen),
+        Card::new(Heart, Nine),
+        Card::new(Club, Nine),
+        Card::new(Heart, Five),
+        Card::new(Heart, Two),
+    ];
+
+    // Flush: (A♥ Q♥ 9♥ 5♥ 2♥).
+    let flush = Hand::new([
+        Card::new(Heart, Ace),
+        Card::new(Heart, Queen),
+        Card::new(Heart, Nine),
+        Card::new(Heart, Five),
+        Card::new(Heart, Two),
+    ])
+    .unwrap();
+
+    assert_eq!(PokerHand::best_from(&cards), Some(PokerHand::solve(flush)));
+}
+
+#[test]
+fn test_best_from_full_house_over_four_flush() {
+    // Seven cards: (Q♥ Q♠ Q♦ 9♥ 9♣ 5♥ 2♥), which contain a full house and four hearts.
+    let cards = [
+        Card::new(Heart, Queen),
+        Card::new(Spade, Queen),
+        Card::new(Diamond, Queen),
+        Card::new(Heart, Nine),
+        Card::new(Club, Nine),
+        Card::new(Heart, Five),
+        Card::new(Heart, Two),
+    ];
+
+    // Full house: (Q♥ Q♠ Q♦ 9♥ 9♣).
+    let full_house = Hand::new([
+        Card::new(Heart, Queen),
+        Card::new(Spade, Queen),
+        Card::new(Diamond, Queen),
+        Card::new(Heart, Nine),
+        Card::new(Club, Nine),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        PokerHand::best_from(&cards),
+        Some(PokerHand::solve(full_house))
+    );
+}
+
+#[test]
+fn test_best_from_invalid() {
+    let cards = [
+        Card::new(Spade, Ace),
+        Card::new(Spade, King),
+        Card::new(Spade, Queen),
+        Card::new(Spade, Jack),
+        Card::new(Spade, Ten),
+        Card::new(Heart, Two),
+        Card::new(Heart, Three),
+        Card::new(Heart, Four),
+    ];
+
+    // Too few or too many cards.
+    assert!(PokerHand::best_from(&cards[..4]).is_none());
+    assert!(PokerHand::best_from(&cards).is_none());
+
+    // Duplicate cards.
+    let duplicates = [&cards[..6], &[Card::new(Spade, Ace)]].concat();
+    assert!(PokerHand::best_from(&duplicates).is_none());
+    assert!(Hand::best_of(&duplicates).is_none());
+}
@@ -0,0 +1,223 @@
+use pokerlab_ref::card::{Card, Rank::*, Suit::*};
+use pokerlab_ref::equity::{Settings, calculate_ranges};
+use pokerlab_ref::range::{ParseRangeError, Range};
+
+/// Parses a range, panicking if it is invalid.
+fn range(s: &str) -> Range {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_combo_counts() {
+    assert_eq!(range("AKs").len(), 4);
+    assert_eq!(range("AKo").len(), 12);
+    assert_eq!(range("AK").len(), 16);
+    assert_eq!(range("QQ").len(), 6);
+    assert_eq!(range("QQ+").len(), 18);
+    assert_eq!(range("A5o+").len(), 108);
+    assert_eq!(range("T9s-76s").len(), 16);
+    assert_eq!(range("A5s-A2s").len(), 16);
+    assert_eq!(range("QQ-99").len(), 24);
+    assert_eq!(range("22+").len(), 78);
+    assert_eq!(range("AKs, QQ+, T9s-76s, A5o+").len(), 146);
+}
+
+#[test]
+fn test_spans_in_either_order() {
+    assert_eq!(range("99-QQ"), range("QQ-99"));
+    assert_eq!(range("A2s-A5s"), range("A5s-A2s"));
+    assert_eq!(range("76s-T9s"), range("T9s-76s"));
+    assert_eq!(range("KA"), range("AK"));
+}
+
+#[test]
+fn test_overlapping_hands_are_counted_once() {
+    assert_eq!(range("AK, AKs, AKo").len(), 16);
+    assert_eq!(range("TT+, QQ-JJ").len(), 30);
+}
+
+#[test]
+fn test_specific_combo() {
+    let range = range("AsKh");
+    assert_eq!(range.len(), 1);
+    assert!(range.contains([Card::new(Spade, Ace), Card::new(Heart, King)]));
+    assert!(range.contains([Card::new(Heart, King), Card::new(Spade, Ace)]));
+    assert!(!range.contains([Card::new(Heart, Ace), Card::new(Spade, King)]));
+}
+
+#[test]
+fn test_suited_and_offsuit() {
+    let suited = range("T9s");
+    let offsuit = range("T9o");
+
+    for [first, second] in suited.iter() {
+        assert_eq!(first.suit(), second.suit());
+    }
+    for [first, second] in offsuit.iter() {
+        assert_ne!(first.suit(), second.suit());
+    }
+    assert!(suited.intersection(&offsuit).is_empty());
+    assert_eq!(suited.union(&offsuit), range("T9"));
+}
+
+#[test]
+fn test_invalid_hands() {
+    let invalid = |s: &str| ParseRangeError::InvalidHand(s.to_string());
+
+    assert_eq!("AKx".parse::<Range>().unwrap_err(), invalid("AKx"));
+    assert_eq!("QQs".parse::<Range>().unwrap_err(), invalid("QQs"));
+    assert_eq!("AK, 1Q".parse::<Range>().unwrap_err(), invalid("1Q"));
+    assert_eq!("AsAs".parse::<Range>().unwrap_err(), invalid("AsAs"));
+    assert_eq!("A".parse::<Range>().unwrap_err(), invalid("A"));
+    assert_eq!("AKs-".parse::<Range>().unwrap_err(), invalid("AKs-"));
+}
+
+#[test]
+fn test_invalid_spans() {
+    let invalid = |s: &str| ParseRangeError::InvalidSpan(s.to_string());
+
+    assert_eq!("AKs-AKo".parse::<Range>().unwrap_err(), invalid("AKs-AKo"));
+    assert_eq!("QQ-AK".parse::<Range>().unwrap_err(), invalid("QQ-AK"));
+    assert_eq!("T9s-75s".parse::<Range>().unwrap_err(), invalid("T9s-75s"));
+}
+
+#[test]
+fn test_empty_range() {
+    assert!(range("").is_empty());
+    assert!(range(" , ").is_empty());
+}
+
+#[test]
+fn test_remove_dead() {
+    let mut aces = range("AA");
+    aces.remove_dead(&[Card::new(Spade, Ace)]);
+    assert_eq!(aces.len(), 3);
+    aces.remove_dead(&[Card::new(Heart, Ace), Card::new(Club, Two)]);
+    assert_eq!(aces.len(), 1);
+    assert!(aces.contains([Card::new(Club, Ace), Card::new(Diamond, Ace)]));
+}
+
+#[test]
+fn test_union_and_intersection() {
+    let broadways = range("AK, AQ, KQ");
+    let aces = range("A2+");
+
+    assert_eq!(broadways.intersection(&aces), range("AK, AQ"));
+    assert_eq!(broadways.union(&aces).len(), 12 * 16 + 16);
+}
+
+#[test]
+fn test_insert() {
+    let mut range = Range::new();
+    assert!(range.insert([Card::new(Spade, Seven), Card::new(Spade, Two)]));
+    assert!(!range.insert([Card::new(Spade, Seven), Card::new(Spade, Seven)]));
+    assert_eq!(range.len(), 1);
+    assert_eq!(range, self::range("7s2s"));
+}
+
+#[test]
+fn test_grid() {
+    let grid = range("AK, 22, 32o").grid();
+
+    assert_eq!(grid[0][1], 4);
+    assert_eq!(grid[1][0], 12);
+    assert_eq!(grid[12][12], 6);
+    assert_eq!(grid[12][11], 12);
+    assert_eq!(grid[11][12], 0);
+
+    let total: u32 = grid.iter().flatten().map(|&n| u32::from(n)).sum();
+    assert_eq!(total, 34);
+}
+
+#[test]
+fn test_full_grid() {
+    let grid = range("22+, A2+, K2+, Q2+, J2+, T2+, 92+, 82+, 72+, 62+, 52+, 42+, 32").grid();
+
+    for (row, cells) in grid.iter().enumerate() {
+        for (column, &cell) in cells.iter().enumerate() {
+            let expected = match row.cmp(&column) {
+                std::cmp::Ordering::Equal => 6,
+                std::cmp::Ordering::Less => 4,
+                std::cmp::Ordering::Greater => 12,
+            };
+            assert_eq!(cell, expected, "grid[{row}][{column}]");
+        }
+    }
+}
+
+#[test]
+fn test_range_equity() {
+    let settings = Settings {
+        samples: 50_000,
+        ..Settings::default()
+    };
+    let odds = calculate_ranges(&[range("AA"), range("KK")], &[], &settings).unwrap();
+    assert!(!odds.is_exact());
+
+    let [aces, kings] = odds.players() else {
+        panic!("expected two players");
+    };
+    assert_eq!(aces.runouts(), settings.samples);
+    assert!((aces.equity() - 0.82).abs() < 0.01, "{}", aces.equity());
+    assert!((aces.equity() + kings.equity() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_range_equity_with_single_combos_is_exact() {
+    // The turn and river are the only cards left: (K♣ 7♦ 2♣ 5♥).
+    let board = [
+        Card::new(Club, King),
+        Card::new(Diamond, Seven),
+        Card::new(Club, Two),
+        Card::new(Heart, Five),
+    ];
+    let odds = calculate_ranges(
+        &[range("AsAh"), range("KsKh")],
+        &board,
+        &Settings::default(),
+    );
+    let odds = odds.unwrap();
+
+    // The Aces need one of the two Aces left in the 44 unseen cards.
+    assert!(odds.is_exact());
+    assert_eq!(odds.players()[0].runouts(), 44);
+    assert_eq!(odds.players()[0].win(), 2.0 / 44.0);
+}
+
+#[test]
+fn test_range_equity_removes_board_cards() {
+    // Every combo of Kings but one uses a King on the board: (K♠ K♥ 2♦).
+    let board = [
+        Card::new(Spade, King),
+        Card::new(Heart, King),
+        Card::new(Diamond, Two),
+    ];
+    let odds = calculate_ranges(&[range("AsAh"), range("KK")], &board, &Settings::default());
+    let odds = odds.unwrap();
+
+    // That leaves quad Kings, which only lose if the last two Aces come.
+    assert!(odds.is_exact());
+    assert_eq!(odds.players()[0].runouts(), 990);
+    assert_eq!(odds.players()[0].win(), 1.0 / 990.0);
+}
+
+#[test]
+fn test_range_equity_invalid() {
+    let settings = Settings::default();
+
+    // Fewer than two players.
+    assert!(calculate_ranges(&[range("AA")], &[], &settings).is_none());
+    // An empty range.
+    assert!(calculate_ranges(&[range("AA"), range("")], &[], &settings).is_none());
+    // Both players need the same card.
+    assert!(calculate_ranges(&[range("AsKs"), range("AsQs")], &[], &settings).is_none());
+    // The only combo uses a board card.
+    let board = [Card::new(Spade, Ace)];
+    assert!(calculate_ranges(&[range("AsKs"), range("QQ")], &board, &settings).is_none());
+    // Ranges always need samples.
+    let no_samples = Settings {
+        samples: 0,
+        ..settings
+    };
+    assert!(calculate_ranges(&[range("AA"), range("KK")], &[], &no_samples).is_none());
+}
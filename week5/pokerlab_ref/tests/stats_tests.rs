@@ -0,0 +1,71 @@
+use pokerlab_ref::stats::{Category, Statistics};
+
+/// The published number of hands and distinct hands of every category, from worst to best.
+fn assert_table(stats: &Statistics, expected: [(u64, u64); 10]) {
+    for (category, (hands, classes)) in Category::ALL.into_iter().zip(expected) {
+        assert_eq!(stats.hands(category), hands, "{category} hands");
+        assert_eq!(stats.classes(category), classes, "{category} classes");
+    }
+}
+
+#[test]
+fn test_five_card_table() {
+    let stats = Statistics::five_card();
+
+    assert_eq!(stats.cards(), 5);
+    assert_eq!(stats.total_hands(), 2_598_960);
+    assert_eq!(stats.total_classes(), 7462);
+    assert_table(
+        &stats,
+        [
+            (1_302_540, 1277),
+            (1_098_240, 2860),
+            (123_552, 858),
+            (54_912, 858),
+            (10_200, 10),
+            (5_108, 1277),
+            (3_744, 156),
+            (624, 156),
+            (36, 9),
+            (4, 1),
+        ],
+    );
+
+    let probability: f64 = Category::ALL.map(|c| stats.probability(c)).iter().sum();
+    assert!((probability - 1.0).abs() < 1e-12);
+    assert!((stats.probability(Category::OnePair) - 0.422569).abs() < 1e-6);
+
+    let table = stats.to_string();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 12);
+    assert!(lines[0].starts_with("5-card hand"));
+    assert!(lines[1].starts_with("Royal flush"));
+    assert!(lines[1].ends_with("0.000154%         1"));
+    assert!(lines[10].starts_with("High card"));
+    assert!(lines[11].starts_with("Total"));
+    assert!(lines[11].ends_with("2598960    100.000000%      7462"));
+}
+
+#[test]
+fn test_seven_card_table() {
+    let stats = Statistics::seven_card();
+
+    assert_eq!(stats.cards(), 7);
+    assert_eq!(stats.total_hands(), 133_784_560);
+    assert_eq!(stats.total_classes(), 4824);
+    assert_table(
+        &stats,
+        [
+            (23_294_460, 407),
+            (58_627_800, 1470),
+            (31_433_400, 763),
+            (6_461_620, 575),
+            (6_180_020, 10),
+            (4_047_644, 1277),
+            (3_473_184, 156),
+            (224_848, 156),
+            (37_260, 9),
+            (4_324, 1),
+        ],
+    );
+}
@@ -0,0 +1,151 @@
+//! Tests for evaluating hands with wild cards.
+
+use pokerlab_ref::card::Card;
+use pokerlab_ref::deck::Deck;
+use pokerlab_ref::hand::{Hand, PokerHand};
+use pokerlab_ref::wild::{best_hand, deuces_wild};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Parses cards in short notation.
+fn cards(cards: &str) -> Vec<Card> {
+    cards
+        .split_whitespace()
+        .map(|c| c.parse().unwrap())
+        .collect()
+}
+
+/// Solves a hand without wild cards, given in short notation.
+fn solve(hand: &str) -> PokerHand {
+    PokerHand::solve(hand.parse::<Hand>().unwrap())
+}
+
+#[test]
+fn test_no_wilds_matches_solve() {
+    let mut rng = StdRng::seed_from_u64(37);
+
+    for _ in 0..2_000 {
+        let mut deck = Deck::new();
+        deck.shuffle(&mut rng);
+        let hand = deck.deal(5).unwrap();
+
+        let expected = PokerHand::solve(Hand::new(hand.clone().try_into().unwrap()).unwrap());
+        assert_eq!(best_hand(&hand, 0), Some(expected));
+    }
+}
+
+#[test]
+fn test_five_of_a_kind() {
+    // A joker with four Aces beats a royal flush.
+    let five_aces = best_hand(&cards("As Ah Ad Ac"), 1).unwrap();
+    assert_eq!(five_aces.to_string(), "Five of a kind, Aces");
+    assert!(five_aces > solve("As Ks Qs Js Ts"));
+
+    // Any number of wild cards can match the natural cards.
+    let five_sevens = best_hand(&cards("7s 7h"), 3).unwrap();
+    assert_eq!(five_sevens.to_string(), "Five of a kind, Sevens");
+    assert!(five_aces > five_sevens);
+
+    // Five wild cards make five Aces.
+    assert_eq!(best_hand(&[], 5), Some(five_aces));
+}
+
+#[test]
+fn test_straight_flushes() {
+    // The joker becomes the missing Ten of Spades.
+    assert_eq!(
+        best_hand(&cards("As Ks Qs Js"), 1),
+        Some(solve("As Ks Qs Js Ts"))
+    );
+
+    // An inside straight flush is better than four Nines.
+    assert_eq!(
+        best_hand(&cards("9h 9s 8h 6h"), 2),
+        Some(solve("Th 9h 8h 7h 6h"))
+    );
+
+    // The wheel, where the joker fills in the Two.
+    assert_eq!(
+        best_hand(&cards("5d 4d 3d Ad"), 1),
+        Some(solve("5d 4d 3d 2d Ad"))
+    );
+}
+
+#[test]
+fn test_wilds_cannot_duplicate_cards() {
+    // The joker can't be a second Ace of Spades, so the best flush has a Queen instead.
+    assert_eq!(
+        best_hand(&cards("As Ks 7s 4s"), 1),
+        Some(solve("As Ks Qs 7s 4s"))
+    );
+
+    // Two pair becomes a full house, not a "flush with a pair".
+    assert_eq!(
+        best_hand(&cards("Ks Kd 5h 5c"), 1),
+        Some(solve("Ks Kd Kh 5h 5c"))
+    );
+}
+
+#[test]
+fn test_small_improvements() {
+    // A lone joker with nothing else pairs the highest card.
+    assert_eq!(
+        best_hand(&cards("Ah Js 8d 4c"), 1),
+        Some(solve("Ah As Js 8d 4c"))
+    );
+
+    // A pair and a joker make three of a kind, which beats the straight the joker can't make.
+    assert_eq!(
+        best_hand(&cards("9h 9s 4d 2c"), 1),
+        Some(solve("9h 9s 9d 4d 2c"))
+    );
+
+    // An open-ended straight is completed at the top.
+    assert_eq!(
+        best_hand(&cards("9h 8s 7d 6c"), 1),
+        Some(solve("Th 9h 8s 7d 6c"))
+    );
+}
+
+#[test]
+fn test_seven_cards() {
+    // The joker and the best four of the six natural cards.
+    assert_eq!(
+        best_hand(&cards("Kh Qh Jh Th 3c 3d"), 1),
+        Some(solve("Ah Kh Qh Jh Th"))
+    );
+    assert_eq!(
+        best_hand(&cards("Kh Qh Jh 3c 3d 3s"), 1),
+        Some(solve("3c 3d 3s 3h Kh"))
+    );
+}
+
+#[test]
+fn test_deuces_wild() {
+    assert_eq!(
+        deuces_wild(&cards("2c 2d 2h As Ad")).unwrap().to_string(),
+        "Five of a kind, Aces"
+    );
+    assert_eq!(
+        deuces_wild(&cards("2c 2d As Ad Kh")),
+        Some(solve("As Ad Ah Ac Kh"))
+    );
+    assert_eq!(
+        deuces_wild(&cards("2c Ks Kd 5h 5c")),
+        Some(solve("Ks Kd Kh 5h 5c"))
+    );
+
+    // Without any Twos, it is a regular hand.
+    assert_eq!(
+        deuces_wild(&cards("As Ks Qs Js 9s")),
+        Some(solve("As Ks Qs Js 9s"))
+    );
+}
+
+#[test]
+fn test_invalid() {
+    assert_eq!(best_hand(&cards("As Ks Qs"), 1), None);
+    assert_eq!(best_hand(&cards("As Ks Qs Js Ts 9s"), 2), None);
+    assert_eq!(best_hand(&cards("As As Qs Js"), 1), None);
+    assert_eq!(deuces_wild(&cards("2c 2c As Ad Kh")), None);
+}